@@ -0,0 +1,50 @@
+//! With the `ffi` feature, compiles the C caller in `tests/ffi/` into a static library that
+//! only the test targets link against, for `tests/ffi.rs`. Plain builds (including maturin) do
+//! nothing here.
+
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=include/league_sim.h");
+    println!("cargo:rerun-if-changed=tests/ffi/league_sim_test.c");
+    if env::var_os("CARGO_FEATURE_FFI").is_none() {
+        return;
+    }
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("cargo sets OUT_DIR"));
+    let object = out_dir.join("league_sim_test.o");
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(&compiler)
+        .args([
+            "-c",
+            "-fPIC",
+            "-Iinclude",
+            "tests/ffi/league_sim_test.c",
+            "-o",
+        ])
+        .arg(&object)
+        .status()
+        .unwrap_or_else(|err| panic!("could not run {compiler}: {err}"));
+    assert!(
+        status.success(),
+        "compiling tests/ffi/league_sim_test.c failed"
+    );
+
+    let archiver = env::var("AR").unwrap_or_else(|_| "ar".to_string());
+    let status = Command::new(&archiver)
+        .arg("crs")
+        .arg(out_dir.join("libleague_sim_test.a"))
+        .arg(&object)
+        .status()
+        .unwrap_or_else(|err| panic!("could not run {archiver}: {err}"));
+    assert!(status.success(), "archiving league_sim_test.o failed");
+
+    // Only test targets link the harness; the library and the CLI stay free of it.
+    println!(
+        "cargo:rustc-link-arg-tests={}",
+        out_dir.join("libleague_sim_test.a").display()
+    );
+}
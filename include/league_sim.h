@@ -0,0 +1,65 @@
+#ifndef LEAGUE_SIM_H
+#define LEAGUE_SIM_H
+
+/* Generated with cbindgen --config cbindgen.toml --output include/league_sim.h; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The call succeeded and `*out_json` holds the result.
+ */
+#define LEAGUE_SIM_OK 0
+
+/**
+ * An input or output pointer was null.
+ */
+#define LEAGUE_SIM_NULL_POINTER -1
+
+/**
+ * An input string was not valid UTF-8.
+ */
+#define LEAGUE_SIM_INVALID_UTF8 -2
+
+/**
+ * The JSON could not be parsed or names a team missing from the table.
+ */
+#define LEAGUE_SIM_INVALID_INPUT -3
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Simulates `n_sims` seasons and stores a newly allocated, NUL-terminated JSON object of
+ * position counts in `*out_json` and its length (without the NUL) in `*out_len`. Returns
+ * `LEAGUE_SIM_OK` or a negative `LEAGUE_SIM_*` code, leaving `*out_json` null on failure.
+ *
+ * # Safety
+ *
+ * `base_table_json` and `fixtures_json` must be valid NUL-terminated strings, and `out_json`
+ * and `out_len` must be valid for writes. Release the result with `league_sim_free`.
+ */
+int32_t league_sim_bulk(const char *base_table_json,
+                        const char *fixtures_json,
+                        uintptr_t n_sims,
+                        uint64_t seed,
+                        char **out_json,
+                        uintptr_t *out_len);
+
+/**
+ * Releases a string returned by `league_sim_bulk`; null is ignored.
+ *
+ * # Safety
+ *
+ * `ptr` must be null or a pointer obtained from `league_sim_bulk` that was not freed yet.
+ */
+void league_sim_free(char *ptr);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* LEAGUE_SIM_H */
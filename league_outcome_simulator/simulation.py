@@ -9,6 +9,8 @@ import sys
 from pathlib import Path
 from types import ModuleType
 
+import numpy as np
+
 
 LOGGER = logging.getLogger(__name__)
 PROJECT_ROOT = Path(__file__).resolve().parent.parent
@@ -56,12 +58,257 @@ def simulate_season(
     away_table=None,
     *,
     seed: int | None = None,
+    tiebreaker_stages=None,
+    rho: float | None = None,
+    home_advantage: float | None = None,
+    max_goals: int | None = None,
+    return_fixtures: bool = False,
+    deductions: dict[str, int] | None = None,
+    model: str | None = None,
+    dispersion: float | None = None,
+    lambda_3: float | None = None,
+    return_xpts: bool = False,
+    fixture_dates: list[int] | None = None,
+    fatigue_window: int = 4,
+    fatigue_factor: float = 0.9,
+    red_card_rate: float = 0.03,
+    red_card_penalty: float = 0.3,
+    return_scorelines: bool = False,
     auto_build: bool = False,
 ):
-    """Simulate one season using the Rust backend."""
+    """Simulate one season using the Rust backend.
+
+    ``tiebreaker_stages`` optionally replaces the default ranking with an
+    ordered list such as ``["pts", "h2h", "gd", "gf"]``. ``"h2h_pts"``,
+    ``"h2h_gd"``, ``"h2h_gf"`` and ``"h2h_away_goals"`` apply one criterion
+    to the matches among the tied teams; a
+    ``{"uefa_coefficient": {team: value}}`` dict and ``"random"`` are also
+    accepted as stages. ``rho`` overrides the Dixon-Coles low-score
+    correlation (default ``-0.1``) and must lie in ``(-1, 1)``.
+    ``home_advantage`` fixes the home scoring multiplier (``1.0`` for a
+    neutral venue); by default it is estimated from the home/away tables.
+    ``max_goals`` raises the per-side score ceiling of the model (default
+    ``10``). With ``return_fixtures=True`` the result is a
+    ``(standings, fixture_results)`` tuple whose second item lists
+    ``{"home", "away", "home_goals", "away_goals"}`` dicts in fixture order.
+    ``deductions`` maps teams to points removed from their current total.
+    ``model`` picks the goal distribution: ``"dixon_coles"`` (default),
+    ``"poisson"``, ``"negative_binomial"``, whose ``dispersion`` (default
+    ``5.0``) gives a goal variance of ``lambda + lambda ** 2 / dispersion``,
+    or ``"bivariate_poisson"``, where both sides share a ``Poisson(lambda_3)``
+    goal term (default ``0.1``) that sets their covariance; a ``ValueError``
+    is raised if ``lambda_3`` exceeds either side's expected goals in any
+    remaining fixture.
+    ``return_xpts=True`` adds each team's exact expected points from the
+    simulated fixtures as an ``"xPts"`` entry.
+    ``fixture_dates`` gives each fixture's day of the season; a side that
+    already played in the ``fatigue_window`` days before a fixture has its
+    expected goals there scaled by ``fatigue_factor``.
+    A ``red_card_rate`` share of matches sees a red card in the first hour,
+    shown to either side with equal probability; the side left short scores
+    ``red_card_penalty`` less. Pass ``red_card_rate=0.0`` to turn this off.
+    ``return_scorelines=True`` appends a ``{"scoreline_freq": {"1-1": n, ...}}``
+    dict to the returned tuple, counting each score across the season's
+    simulated fixtures.
+    """
     rust_module = get_rust_module(auto_build=auto_build)
     return rust_module.simulate_season(
-        base_table, fixtures, home_table, away_table, seed
+        base_table,
+        fixtures,
+        home_table,
+        away_table,
+        seed,
+        tiebreaker_stages,
+        rho,
+        home_advantage,
+        max_goals,
+        return_fixtures,
+        deductions,
+        model,
+        dispersion,
+        lambda_3,
+        return_xpts,
+        fixture_dates,
+        fatigue_window,
+        fatigue_factor,
+        red_card_rate,
+        red_card_penalty,
+        return_scorelines,
+    )
+
+
+def simulate_season_form(
+    base_table,
+    fixtures,
+    home_table,
+    away_table,
+    recent_fixtures,
+    form_window: int = 5,
+    form_decay: float = 0.8,
+    *,
+    seed: int | None = None,
+    rho: float | None = None,
+    home_advantage: float | None = None,
+    max_goals: int | None = None,
+    auto_build: bool = False,
+):
+    """Simulate one season with scoring rates taken from recent form.
+
+    ``recent_fixtures`` lists played ``{"home", "away", "home_goals",
+    "away_goals"}`` results from oldest to newest. Each team's last
+    ``form_window`` matches are averaged with the match ``k`` games back
+    weighted by ``form_decay ** k``; teams with no recent results keep their
+    table-based rates.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_season_form(
+        base_table,
+        fixtures,
+        home_table,
+        away_table,
+        recent_fixtures,
+        form_window,
+        form_decay,
+        seed,
+        rho,
+        home_advantage,
+        max_goals,
+    )
+
+
+def simulate_season_zip(
+    base_table,
+    fixtures,
+    home_table,
+    away_table,
+    zero_inflation: float,
+    *,
+    seed: int | None = None,
+    rho: float | None = None,
+    home_advantage: float | None = None,
+    max_goals: int | None = None,
+    auto_build: bool = False,
+):
+    """Simulate one season with extra 0-0 draws.
+
+    ``zero_inflation`` in ``[0, 1)`` is the share of each fixture's
+    probability placed on a goalless draw on top of the Dixon-Coles model;
+    the other scorelines shrink proportionally.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_season_zip(
+        base_table,
+        fixtures,
+        home_table,
+        away_table,
+        zero_inflation,
+        seed,
+        rho,
+        home_advantage,
+        max_goals,
+    )
+
+
+def simulate_season_split(
+    base_table,
+    fixtures,
+    home_table,
+    away_table,
+    split_after: int,
+    top_n: int = 6,
+    *,
+    seed: int | None = None,
+    rho: float | None = None,
+    home_advantage: float | None = None,
+    max_goals: int | None = None,
+    auto_build: bool = False,
+):
+    """Simulate one season of a league that splits in two, like the Scottish Premiership.
+
+    The first ``split_after`` fixtures are played, the table is cut into the
+    top ``top_n`` teams and the rest, and each team then meets every other
+    team in its half once more (the higher-placed side at home). Fixtures
+    listed after the split point are ignored. The final standings keep every
+    top-half team above the bottom half.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_season_split(
+        base_table,
+        fixtures,
+        home_table,
+        away_table,
+        split_after,
+        top_n,
+        seed,
+        rho,
+        home_advantage,
+        max_goals,
+    )
+
+
+def simulate_multi_season(
+    top_league,
+    lower_league,
+    top_fixtures,
+    lower_fixtures,
+    relegation_spots: int,
+    promotion_spots: int,
+    n_years: int,
+    n_sims: int = 1000,
+    *,
+    seed: int | None = None,
+    rho: float | None = None,
+    home_advantage: float | None = None,
+    max_goals: int | None = None,
+    auto_build: bool = False,
+):
+    """Simulate ``n_years`` seasons of two divisions linked by promotion and relegation.
+
+    The current season plays out the given fixtures; every season after it is
+    a full double round-robin. At the end of each season the bottom
+    ``relegation_spots`` of the top division swap places with the top
+    ``promotion_spots`` of the lower one, and teams keep the strength of the
+    table they started in. Returns ``{team: {k: count}}`` with the number of
+    simulations in which the team spent ``k`` seasons in the top division.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_multi_season(
+        top_league,
+        lower_league,
+        top_fixtures,
+        lower_fixtures,
+        relegation_spots,
+        promotion_spots,
+        n_years,
+        n_sims,
+        seed,
+        rho,
+        home_advantage,
+        max_goals,
+    )
+
+
+def simulate_season_dc(
+    base_table,
+    fixtures,
+    team_params,
+    *,
+    seed: int | None = None,
+    rho: float | None = None,
+    home_advantage: float | None = None,
+    max_goals: int | None = None,
+    auto_build: bool = False,
+):
+    """Simulate one season from Dixon-Coles attack/defense strengths.
+
+    ``team_params`` maps teams to ``{"attack": a, "defense": d}``; each
+    fixture uses ``lambda_h = attack_h * defense_a * mu * home_advantage``
+    with ``mu`` the league goals per match. Unlisted teams count as average
+    (``1.0``) and ``home_advantage`` defaults to ``1.25``.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_season_dc(
+        base_table, fixtures, team_params, seed, rho, home_advantage, max_goals
     )
 
 
@@ -74,9 +321,56 @@ def simulate_bulk(
     *,
     seed: int | None = None,
     top_k_tables: int = 25,
+    rho: float | None = None,
+    home_advantage: float | None = None,
+    max_goals: int | None = None,
+    points_distribution: bool = False,
+    deductions: dict[str, int] | None = None,
+    checkpoint_every: int | None = None,
+    checkpoint_path: str | None = None,
+    tiebreaker_stages=None,
+    return_record: bool = False,
+    return_goals: bool = False,
+    return_clean_sheets: bool = False,
+    antithetic: bool = False,
+    stratified: bool = False,
+    timeout_ms: int | None = None,
+    chunk_size: int | None = None,
+    n_threads: int | None = None,
+    return_full_history: bool = False,
     auto_build: bool = False,
 ):
-    """Simulate many seasons in bulk using the Rust backend."""
+    """Simulate many seasons in bulk using the Rust backend.
+
+    A fixed ``seed`` reproduces the whole batch regardless of thread count;
+    ``None`` draws a fresh base seed. With ``points_distribution=True`` each
+    ``position_counts`` entry becomes ``{"positions": {...}, "points": {...}}``
+    so final-points histograms come back alongside the finish counts;
+    ``return_record=True`` adds a ``"record"`` entry of ``wins``, ``draws``
+    and ``losses`` histograms in the same way, and ``return_goals=True``
+    adds ``"goals_for"`` and ``"goals_against"`` season-total histograms.
+    ``return_clean_sheets=True`` adds a ``"clean_sheets"`` histogram of the
+    simulated fixtures each team kept a clean sheet in.
+    ``antithetic=True`` plays the seasons in pairs from one seed, the second
+    drawing every score at ``1 - u``, which lowers the variance of the
+    estimates for the same ``n_sims``. ``stratified=True`` instead splits
+    each fixture's draws into ``n_sims`` equal strata, one per season.
+    With ``timeout_ms`` no new season starts once that many milliseconds have
+    passed, and the return value becomes ``(result, completed_count)``.
+    ``chunk_size`` sets how many seasons each worker task plays (default: an
+    even split across threads); a run shorter than one chunk stays on the
+    calling thread, which is quicker for a few hundred seasons.
+    ``n_threads`` runs the batch on its own pool of that many threads (``0``
+    for one per CPU) instead of the shared one; a seeded run gives the same
+    counts on any number of threads.
+    ``return_full_history=True`` adds ``"position_history"``: one list per
+    season of every team's finishing position, in base-table order, for
+    ``position_correlation_matrix``.
+    ``deductions`` maps teams to points removed before any season is played.
+    With ``checkpoint_path`` the running tally is saved there every
+    ``checkpoint_every`` seasons (and at the end) for ``simulate_bulk_resume``.
+    ``tiebreaker_stages`` ranks every season as in ``simulate_season``.
+    """
     rust_module = get_rust_module(auto_build=auto_build)
     return rust_module.simulate_bulk(
         base_table,
@@ -86,4 +380,1133 @@ def simulate_bulk(
         n_sims,
         seed,
         top_k_tables,
+        rho,
+        home_advantage,
+        max_goals,
+        points_distribution,
+        deductions,
+        checkpoint_every,
+        checkpoint_path,
+        tiebreaker_stages,
+        return_record,
+        return_goals,
+        return_clean_sheets,
+        antithetic,
+        stratified,
+        timeout_ms,
+        chunk_size,
+        n_threads,
+        return_full_history,
+    )
+
+
+def apply_results(base_table, completed_results, *, auto_build: bool = False):
+    """Return ``base_table`` with finished matches added to its columns.
+
+    ``completed_results`` is a list of ``{"home", "away", "home_goals",
+    "away_goals"}`` dicts. The table keeps its header and row order, so it can
+    be passed straight to ``simulate_season``.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.apply_results(base_table, completed_results)
+
+
+def validate_fixtures(base_table, fixtures, *, auto_build: bool = False):
+    """Return every problem in ``fixtures`` instead of failing on the first.
+
+    Each problem is an ``{"error_type", "fixture_idx", "message"}`` dict; the
+    error types are ``malformed_fixture``, ``unknown_team``,
+    ``duplicate_fixture``, ``self_play`` and ``unbalanced_venues``. An empty
+    list means the fixtures are ready to simulate.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.validate_fixtures(base_table, fixtures)
+
+
+def check_home_away_balance(fixtures, base_table, *, auto_build: bool = False):
+    """Return ``{team: {"home", "away"}}`` for teams more than one fixture off balance."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.check_home_away_balance(fixtures, base_table)
+
+
+def fix_home_away_balance(
+    fixtures, base_table, *, seed: int | None = None, auto_build: bool = False
+):
+    """Return ``fixtures`` with just enough of them reversed to balance every team.
+
+    Afterwards each team's home and away counts differ by at most one. Pairs
+    are reversed and SofaScore dicts get ``"h"``/``"a"`` exchanged; ``seed``
+    picks among equally small sets of swaps.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.fix_home_away_balance(fixtures, base_table, seed)
+
+
+def live_season(base_table, home_table, away_table, *, auto_build: bool = False):
+    """Create a Rust-side ``LiveSeason`` for in-season updates.
+
+    ``add_result(home, away, home_goals, away_goals)`` records a finished
+    match, ``standings()`` returns the table so far and
+    ``simulate_remaining(fixtures, n_sims, seed=None)`` simulates the rest of
+    the season from it, without re-sending the tables on every call.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.LiveSeason(base_table, home_table, away_table)
+
+
+def simulate_stream(
+    base_table,
+    fixtures,
+    home_table,
+    away_table,
+    n_sims,
+    *,
+    seed: int | None = None,
+    rho: float | None = None,
+    home_advantage: float | None = None,
+    max_goals: int | None = None,
+    deductions: dict[str, int] | None = None,
+    auto_build: bool = False,
+):
+    """Iterate over simulated seasons as the Rust backend finishes them.
+
+    Each item is one season's standings in the ``simulate_season`` format.
+    Seasons come back in completion order; with a fixed ``seed`` the set of
+    seasons matches the ones ``simulate_bulk`` tallies. Only a few seasons
+    per thread are simulated ahead of the reader.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_stream(
+        base_table,
+        fixtures,
+        home_table,
+        away_table,
+        n_sims,
+        seed,
+        rho,
+        home_advantage,
+        max_goals,
+        deductions,
+    )
+
+
+def simulate_bulk_qmc(
+    base_table,
+    fixtures,
+    home_table,
+    away_table,
+    n_sims,
+    *,
+    seed: int | None = None,
+    top_k_tables: int = 25,
+    rho: float | None = None,
+    home_advantage: float | None = None,
+    max_goals: int | None = None,
+    auto_build: bool = False,
+):
+    """Like ``simulate_bulk``, drawing scores from a randomly shifted Sobol sequence.
+
+    Quasi-Monte Carlo points cover the fixtures' outcomes more evenly than
+    independent draws, so the position probabilities settle with fewer
+    seasons. ``seed`` picks the shift.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_bulk_qmc(
+        base_table,
+        fixtures,
+        home_table,
+        away_table,
+        n_sims,
+        seed,
+        top_k_tables,
+        rho,
+        home_advantage,
+        max_goals,
+    )
+
+
+def simulate_season_conditional(
+    base_table,
+    fixtures,
+    fixed_results,
+    home_table,
+    away_table,
+    n_sims,
+    *,
+    seed: int | None = None,
+    top_k_tables: int = 25,
+    rho: float | None = None,
+    home_advantage: float | None = None,
+    max_goals: int | None = None,
+    auto_build: bool = False,
+):
+    """Like ``simulate_bulk``, with some remaining fixtures decided in advance.
+
+    ``fixed_results`` maps fixture indices to ``(home_goals, away_goals)``;
+    those games finish with that score in every season and only the rest are
+    simulated, e.g. ``{0: (1, 0)}`` asks what happens if the first fixture's
+    home side wins it 1-0.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_season_conditional(
+        base_table,
+        fixtures,
+        fixed_results,
+        home_table,
+        away_table,
+        n_sims,
+        seed,
+        top_k_tables,
+        rho,
+        home_advantage,
+        max_goals,
+    )
+
+
+def simulate_bulk_scenario_delta(
+    base_table,
+    fixtures,
+    scenario_a,
+    scenario_b,
+    n_sims,
+    *,
+    seed: int | None = None,
+    home_table=None,
+    away_table=None,
+    rho: float | None = None,
+    home_advantage: float | None = None,
+    max_goals: int | None = None,
+    auto_build: bool = False,
+):
+    """Return ``{team: {position: P(A) - P(B)}}`` for two scenarios of fixed results.
+
+    Each scenario maps fixture indices to ``(home_goals, away_goals)`` as for
+    ``simulate_season_conditional``. Both run from the same seed, so every
+    other fixture plays out identically and the deltas reflect the scenarios
+    rather than simulation noise.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_bulk_scenario_delta(
+        base_table,
+        fixtures,
+        scenario_a,
+        scenario_b,
+        n_sims,
+        seed,
+        home_table,
+        away_table,
+        rho,
+        home_advantage,
+        max_goals,
+    )
+
+
+def simulate_bulk_importance(
+    base_table,
+    fixtures,
+    home_table,
+    away_table,
+    n_sims,
+    target_team: str,
+    target_outcome: str,
+    proposal_lambda_scale: float,
+    *,
+    seed: int | None = None,
+    rho: float | None = None,
+    home_advantage: float | None = None,
+    max_goals: int | None = None,
+    auto_build: bool = False,
+):
+    """Estimate a long-shot probability for ``target_team`` by importance sampling.
+
+    ``target_outcome`` is ``"championship"``, ``"top4"``,
+    ``"relegation_survival"`` or ``"relegation"``. Seasons are played with the
+    team's expected goals scaled by ``proposal_lambda_scale`` (above ``1`` for
+    an unlikely title, below for unlikely relegation) and reweighted by the
+    likelihood ratio. Returns ``{"probability", "std_error",
+    "effective_sample_size"}``; a small effective sample size means the
+    proposal is badly chosen.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_bulk_importance(
+        base_table,
+        fixtures,
+        home_table,
+        away_table,
+        n_sims,
+        seed,
+        target_team,
+        target_outcome,
+        proposal_lambda_scale,
+        rho,
+        home_advantage,
+        max_goals,
+    )
+
+
+def simulate_bulk_converging(
+    base_table,
+    fixtures,
+    target_team: str,
+    target_metric: str,
+    tolerance: float,
+    max_sims: int,
+    seed: int | None = None,
+    *,
+    auto_build: bool = False,
+):
+    """Simulate until ``target_team``'s ``target_metric`` estimate settles.
+
+    Seasons are played in batches of 1000 until the running ``"championship"``,
+    ``"top4"``, ``"relegation_survival"`` or ``"relegation"`` share moves by
+    less than ``tolerance`` between batches, or ``max_sims`` is reached.
+    Returns ``(result, n_sims_used)`` with ``result`` shaped like
+    ``simulate_bulk``'s.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_bulk_converging(
+        base_table, fixtures, target_team, target_metric, tolerance, max_sims, seed
+    )
+
+
+def fixture_importance(
+    base_table, fixtures, n_sims: int, seed: int, *, auto_build: bool = False
+):
+    """Rank remaining fixtures by how far they swing the title odds.
+
+    Each fixture is simulated as a home win and as an away win from the same
+    seed; entries carry ``delta_home_title``, ``delta_away_title`` and an
+    ``importance_score``, most important first.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.fixture_importance(base_table, fixtures, n_sims, seed)
+
+
+def marginal_fixture_value(
+    base_table,
+    fixtures,
+    team: str,
+    metric: str,
+    n_sims: int,
+    seed: int,
+    *,
+    auto_build: bool = False,
+):
+    """Return ``team``'s remaining fixtures with the chance of ``metric`` after each result.
+
+    ``metric`` is ``"championship"``, ``"top4"`` or ``"relegation_survival"``;
+    each entry carries ``fixture_idx``, ``p_win``, ``p_draw``, ``p_loss`` and
+    their ``expected_value`` under the fixture's own odds.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.marginal_fixture_value(
+        base_table, fixtures, team, metric, n_sims, seed
+    )
+
+
+def simulate_bulk_resume(
+    checkpoint_path: str,
+    remaining_sims: int,
+    base_table,
+    fixtures,
+    home_table,
+    away_table,
+    *,
+    top_k_tables: int = 25,
+    rho: float | None = None,
+    home_advantage: float | None = None,
+    max_goals: int | None = None,
+    deductions: dict[str, int] | None = None,
+    checkpoint_every: int | None = None,
+    auto_build: bool = False,
+):
+    """Continue a checkpointed ``simulate_bulk`` run for ``remaining_sims`` more seasons.
+
+    Pass the same league and settings as the original call; a ``ValueError``
+    is raised if ``rho``, ``home_advantage``, ``max_goals`` or
+    ``deductions`` differ from the ones saved. The seed and tiebreaker stages
+    come from the checkpoint, so the position counts match a single run of
+    the combined length. The checkpoint is updated as the run continues.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_bulk_resume(
+        checkpoint_path,
+        remaining_sims,
+        base_table,
+        fixtures,
+        home_table,
+        away_table,
+        top_k_tables,
+        rho,
+        home_advantage,
+        max_goals,
+        deductions,
+        checkpoint_every,
+    )
+
+
+def fit_dixon_coles(
+    results,
+    rho_init: float = -0.1,
+    max_iter: int = 1000,
+    tol: float = 1e-8,
+    time_decay: float = 0.0,
+    dates=None,
+    *,
+    auto_build: bool = False,
+):
+    """Fit Dixon-Coles strengths to played results by maximum likelihood.
+
+    ``results`` lists ``{"home", "away", "home_goals", "away_goals"}`` dicts.
+    With ``time_decay`` (e.g. ``0.0065``, a half-life of about 100 days) and
+    a parallel ``dates`` list of days ago, each match is weighted by
+    ``exp(-time_decay * days_ago)``.
+    Returns ``{"attack": {team: a}, "defense": {team: d}, "rho", "mu",
+    "home_advantage", "converged"}`` with attack and defense normalised to a
+    geometric mean of ``1``, ready to pass to ``simulate_season_dc``.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.fit_dixon_coles(
+        results, rho_init, max_iter, tol, time_decay, dates
+    )
+
+
+def estimate_lambdas_poisson(results, *, auto_build: bool = False):
+    """Fit a Poisson regression with team attack and defense effects by IRLS.
+
+    ``results`` lists ``{"home", "away", "home_goals", "away_goals"}`` dicts.
+    Returns ``{team: {"attack", "defense"}, ..., "mu", "home_advantage"}``
+    with attack and defense normalised to a geometric mean of ``1``.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.estimate_lambdas_poisson(results)
+
+
+def brier_score(predictions, outcomes, *, auto_build: bool = False) -> float:
+    """Mean Brier score of ``(p_home, p_draw, p_away)`` forecasts against ``"H"``/``"D"``/``"A"``."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.brier_score(predictions, outcomes)
+
+
+def log_loss(predictions, outcomes, *, auto_build: bool = False) -> float:
+    """Mean negative log-probability given to the observed results."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.log_loss(predictions, outcomes)
+
+
+def ranked_probability_score(predictions, outcomes, *, auto_build: bool = False) -> float:
+    """Mean ranked probability score over the ordered home/draw/away outcomes."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.ranked_probability_score(predictions, outcomes)
+
+
+def rps_position(
+    predicted_probs, actual_position: int, n_positions: int, *, auto_build: bool = False
+) -> float:
+    """Ranked probability score of one team's position probabilities (positions ``1..=n``)."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.rps_position(predicted_probs, actual_position, n_positions)
+
+
+def mean_rps(predictions_list, actuals, *, auto_build: bool = False) -> float:
+    """Mean position RPS over every team's forecast and actual finishing position."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.mean_rps(predictions_list, actuals)
+
+
+def backtest(
+    historical_results,
+    initial_stats,
+    rho: float = -0.1,
+    n_sims: int = 10_000,
+    *,
+    seed: int | None = None,
+    auto_build: bool = False,
+):
+    """Replay a season match by match and score each pre-match forecast.
+
+    ``historical_results`` lists ``{"home", "away", "home_goals",
+    "away_goals"}`` dicts oldest first, optionally with a ``"matchday"``;
+    ``initial_stats`` is a standings table with a header row. Before each
+    matchday the rest of the schedule is simulated ``n_sims`` times from the
+    table so far, with the home advantage estimated from the results already
+    replayed. Each returned dict holds the ``predicted_h``/``predicted_d``/``predicted_a`` forecast,
+    the ``actual`` result and its ``brier`` and ``rps`` scores.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.backtest(historical_results, initial_stats, rho, n_sims, seed)
+
+
+def config_to_json(config, *, auto_build: bool = False) -> str:
+    """Validate a simulation config dict and serialize it to JSON.
+
+    Recognised keys are ``home_advantage``, ``rho``, ``max_goals``, ``seed``,
+    ``n_sims``, ``model``, ``dispersion`` and ``lambda_3``; missing ones take
+    the ``simulate_bulk`` defaults.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.config_to_json(config)
+
+
+def config_from_json(json: str, *, auto_build: bool = False):
+    """Parse JSON from ``config_to_json`` back into a config dict."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.config_from_json(json)
+
+
+def simulate_bulk_from_config(
+    base_table,
+    fixtures,
+    home_table,
+    away_table,
+    config,
+    *,
+    top_k_tables: int = 25,
+    auto_build: bool = False,
+):
+    """Run ``simulate_bulk`` with the settings of a saved config dict."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_bulk_from_config(
+        base_table, fixtures, home_table, away_table, config, top_k_tables
+    )
+
+
+def simulation_progress(*, auto_build: bool = False) -> int:
+    """Return how many seasons the running or latest ``simulate_bulk`` call has finished.
+
+    ``simulate_bulk`` releases the GIL, so a background thread can poll this
+    to report progress on long runs.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulation_progress()
+
+
+def simulate_bulk_array(
+    base_table,
+    fixtures,
+    home_table,
+    away_table,
+    n_sims,
+    *,
+    seed: int | None = None,
+    rho: float | None = None,
+    home_advantage: float | None = None,
+    max_goals: int | None = None,
+    deductions: dict[str, int] | None = None,
+    auto_build: bool = False,
+):
+    """Return ``(counts, teams)`` position counts as a NumPy array.
+
+    ``counts[i, j]`` is how often ``teams[i]`` finished in position ``j + 1``;
+    rows follow the alphabetical ``teams`` list, so each row sums to ``n_sims``.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_bulk_array(
+        base_table,
+        fixtures,
+        home_table,
+        away_table,
+        n_sims,
+        seed,
+        rho,
+        home_advantage,
+        max_goals,
+        deductions,
+    )
+
+
+def zone_probabilities(
+    counts,
+    n_teams: int,
+    relegation_zone: int,
+    promotion_zone: int,
+    n_sims: int,
+    *,
+    auto_build: bool = False,
+):
+    """Map each team to ``{"relegation", "promotion", "mid_table"}`` probabilities.
+
+    ``counts`` is the ``simulate_bulk`` result or its ``position_counts`` dict.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.zone_probabilities(
+        counts, n_teams, relegation_zone, promotion_zone, n_sims
+    )
+
+
+def competitiveness_index(position_counts, n_sims: int, *, auto_build: bool = False) -> float:
+    """Return the mean normalized entropy of finishing positions, from 0 (settled) to 1."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.competitiveness_index(position_counts, n_sims)
+
+
+def position_summary_stats(position_counts, n_sims: int, *, auto_build: bool = False):
+    """Return ``{team: {"mean", "median", "p25", "p75", "std"}}`` finishing positions.
+
+    Computed from ``simulate_bulk`` position counts; the quantiles are places
+    the team can actually finish in, the first whose cumulative share reaches
+    the level.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.position_summary_stats(position_counts, n_sims)
+
+
+def position_correlation_matrix(position_history, n_sims: int, *, auto_build: bool = False):
+    """Return the team-by-team correlation of finishing positions as an array.
+
+    ``position_history`` is the ``simulate_bulk(..., return_full_history=True)``
+    entry of the same name; rows and columns follow its team order. Teams that
+    always finish in the same place have NaN correlations.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return np.asarray(rust_module.position_correlation_matrix(position_history, n_sims))
+
+
+def joint_top_n_probability(
+    position_counts_full, teams, n: int, n_sims: int, *, auto_build: bool = False
+) -> float:
+    """Return the chance that all of ``teams`` finish in the top ``n``, in any order.
+
+    ``position_counts_full`` is the ``position_history`` of a
+    ``simulate_bulk(..., return_full_history=True)`` run, and ``teams`` are
+    zero-based base-table rows, the columns of that history.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.joint_top_n_probability(position_counts_full, teams, n, n_sims)
+
+
+def league_gini(position_counts, n_sims: int, *, auto_build: bool = False) -> float:
+    """Return the Gini coefficient of title chances, 0 (wide open) to 1 (foregone)."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.league_gini(position_counts, n_sims)
+
+
+def per_position_gini(position_counts, n_sims: int, *, auto_build: bool = False) -> list[float]:
+    """Return the Gini coefficient of how the teams share each position, champion first."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.per_position_gini(position_counts, n_sims)
+
+
+def bootstrap_ci(
+    position_counts,
+    n_sims: int,
+    team: str,
+    position: int,
+    n_bootstrap: int = 1000,
+    confidence: float = 0.95,
+    *,
+    auto_build: bool = False,
+) -> tuple[float, float]:
+    """Return a bootstrap ``(low, high)`` interval on ``team`` finishing ``position``.
+
+    ``position_counts`` is the ``simulate_bulk`` result or its
+    ``position_counts`` dict; the interval narrows as ``n_sims`` grows.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.bootstrap_ci(
+        position_counts, n_sims, team, position, n_bootstrap, confidence
+    )
+
+
+def can_finish_above(
+    base_table, fixtures, team: str, target_position: int, *, auto_build: bool = False
+) -> bool:
+    """Return ``False`` once ``team`` cannot finish ``target_position`` or higher."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.can_finish_above(base_table, fixtures, team, target_position)
+
+
+def already_qualified(
+    base_table, fixtures, team: str, zone_size: int, *, auto_build: bool = False
+) -> bool:
+    """Return ``True`` once ``team`` cannot drop out of the top ``zone_size``."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.already_qualified(base_table, fixtures, team, zone_size)
+
+
+def best_worst_case(
+    base_table, fixtures, team: str, *, auto_build: bool = False
+) -> tuple[int, int]:
+    """Return ``team``'s ``(best_position, worst_position)`` in the two extreme scenarios.
+
+    Best case: it wins every remaining match 1-0 and the fixtures between its
+    rivals keep as many of them below it as they can; worst case: it loses
+    every match 0-1 and every rival wins. Goal difference and goals scored
+    break ties as in the default table order.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.best_worst_case(base_table, fixtures, team)
+
+
+def points_to_win_league(base_table, fixtures, *, auto_build: bool = False) -> int:
+    """Return the points total that makes the current leader's title safe on points.
+
+    No other team can finish above it given the remaining fixtures; when
+    nobody can catch the leader any more it equals the leader's points.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.points_to_win_league(base_table, fixtures)
+
+
+def simulate_group_stage(
+    teams,
+    team_stats,
+    n_sims: int,
+    *,
+    seed: int | None = None,
+    double_round_robin: bool = True,
+    auto_build: bool = False,
+):
+    """Return ``{team: {position: count}}`` for a round-robin group.
+
+    ``team_stats`` maps each team to ``(lambda_attack, lambda_defense)``, its
+    goals scored and conceded per match. Every pair meets home and away, or
+    once at a neutral venue when ``double_round_robin`` is false.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_group_stage(
+        teams, team_stats, n_sims, seed, double_round_robin
+    )
+
+
+def simulate_playoff(
+    bracket,
+    team_stats,
+    n_sims: int,
+    *,
+    seed: int | None = None,
+    penalty_rate: float = 0.75,
+    auto_build: bool = False,
+):
+    """Return ``{team: win_probability}`` for a knockout bracket.
+
+    ``bracket`` lists the first-round ``(home, away)`` ties and ``team_stats``
+    maps each team to its ``(gf, ga, m)`` record.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_playoff(bracket, team_stats, n_sims, seed, penalty_rate)
+
+
+def simulate_two_leg_tie(
+    lambda_h1: float,
+    lambda_a1: float,
+    lambda_h2: float,
+    lambda_a2: float,
+    rho: float = -0.1,
+    max_goals: int = 10,
+    away_goals_rule: bool = False,
+    n_sims: int = 10_000,
+    *,
+    seed: int | None = None,
+    penalty_rate: float = 0.75,
+    auto_build: bool = False,
+):
+    """Return ``{"team1_advance", "team2_advance", "penalties"}`` for a two-legged tie.
+
+    Team 1 hosts the first leg (``lambda_h1``), team 2 the second (``lambda_h2``).
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_two_leg_tie(
+        lambda_h1,
+        lambda_a1,
+        lambda_h2,
+        lambda_a2,
+        rho,
+        max_goals,
+        away_goals_rule,
+        n_sims,
+        seed,
+        penalty_rate,
+    )
+
+
+def simulate_championship_playoff(
+    team_ranks,
+    team_stats,
+    n_sims: int,
+    *,
+    seed: int | None = None,
+    penalty_rate: float = 0.75,
+    auto_build: bool = False,
+):
+    """Return ``{team: promotion_probability}`` for a Championship-style playoff.
+
+    ``team_ranks`` maps the four entrants to their league positions; 3rd meets
+    6th and 4th meets 5th over two legs (away goals, then penalties) with the
+    higher-placed side at home second, and the final is a single match at a
+    neutral venue. ``team_stats`` maps each team to
+    ``{"lambda_attack": goals_scored_per_match, "lambda_defense": goals_conceded_per_match}``.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_championship_playoff(
+        team_ranks, team_stats, n_sims, seed, penalty_rate
+    )
+
+
+def simulate_champions_league(
+    groups,
+    team_stats,
+    n_sims: int,
+    *,
+    seed: int | None = None,
+    penalty_rate: float = 0.75,
+    auto_build: bool = False,
+):
+    """Simulate a Champions League style group stage and knockout bracket.
+
+    ``groups`` lists each group's teams (the number of groups must be a power
+    of two) and ``team_stats`` maps every team to ``(lambda_attack,
+    lambda_defense)``. Groups are double round-robins; winners are drawn
+    against runners-up, who host the first leg, and later rounds are drawn
+    openly. Ties are two-legged without away goals and the final is a single
+    match at a neutral venue. Returns ``{team: {stage: probability}}`` for
+    ``round_of_16``, ``quarter_final``, ``semi_final``, ``final`` and
+    ``winner`` (named after the teams left for other group counts).
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_champions_league(
+        groups, team_stats, n_sims, seed, penalty_rate
+    )
+
+
+def simulate_mls_season(
+    east_base,
+    west_base,
+    east_fixtures,
+    west_fixtures,
+    inter_fixtures,
+    n_sims: int = 1000,
+    *,
+    seed: int | None = None,
+    rho: float | None = None,
+    home_advantage: float | None = None,
+    max_goals: int | None = None,
+    auto_build: bool = False,
+):
+    """Simulate an MLS regular season and playoff.
+
+    Both conference tables use the ``simulate_season`` standings format, and
+    ``inter_fixtures`` holds the games between the conferences. The top seven
+    of each conference qualify; the top seed has a first-round bye, the higher
+    seed hosts every single-match round through the conference final, and the
+    champion with the better overall record hosts MLS Cup. Returns
+    ``{team: {"cup_win", "playoff", "conference_final"}}`` probabilities.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_mls_season(
+        east_base,
+        west_base,
+        east_fixtures,
+        west_fixtures,
+        inter_fixtures,
+        n_sims,
+        seed,
+        rho,
+        home_advantage,
+        max_goals,
+    )
+
+
+def simulate_swiss(
+    teams,
+    team_stats,
+    n_rounds: int,
+    n_sims: int,
+    *,
+    seed: int | None = None,
+    auto_build: bool = False,
+):
+    """Simulate a Swiss-system league phase like the Champions League's.
+
+    Each round pairs every team with the nearest team in the current
+    standings it has not met yet; ``teams`` must be even in number and
+    ``n_rounds`` at most half of it, so a pairing without rematches always
+    exists. ``team_stats`` maps teams to ``(lambda_attack, lambda_defense)``.
+    Returns ``{team: {"qualified_top_8", "qualified_top_16", "eliminated"}}``
+    probabilities; the top 16 includes the top 8 and eliminated teams
+    finished below 16th.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_swiss(teams, team_stats, n_rounds, n_sims, seed)
+
+
+def simulate_penalty_shootout(
+    p_team1: float,
+    p_team2: float,
+    *,
+    seed: int | None = None,
+    auto_build: bool = False,
+) -> str:
+    """Return ``"team1"`` or ``"team2"`` for one shootout; team 1 kicks first."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_penalty_shootout(p_team1, p_team2, seed)
+
+
+def simulate_penalty_shootout_bulk(
+    p_team1: float,
+    p_team2: float,
+    n_sims: int,
+    *,
+    seed: int | None = None,
+    auto_build: bool = False,
+) -> tuple[int, int]:
+    """Return ``(team1_wins, team2_wins)`` over ``n_sims`` shootouts."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.simulate_penalty_shootout_bulk(p_team1, p_team2, n_sims, seed)
+
+
+def match_probabilities(
+    lambda_h: float,
+    lambda_a: float,
+    rho: float = -0.1,
+    max_goals: int = 10,
+    *,
+    auto_build: bool = False,
+):
+    """Return ``{"home_win", "draw", "away_win"}`` probabilities for one fixture."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.match_probabilities(lambda_h, lambda_a, rho, max_goals)
+
+
+def scoreline_probabilities(
+    lambda_h: float,
+    lambda_a: float,
+    rho: float = -0.1,
+    max_goals: int = 10,
+    *,
+    auto_build: bool = False,
+):
+    """Return ``{(home_goals, away_goals): probability}`` for one fixture."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.scoreline_probabilities(lambda_h, lambda_a, rho, max_goals)
+
+
+def head_to_head_matrix(
+    team_a: str,
+    team_b: str,
+    team_stats,
+    rho: float = -0.1,
+    max_goals: int = 10,
+    *,
+    auto_build: bool = False,
+):
+    """Return the scoreline grid of ``team_a`` hosting ``team_b``.
+
+    ``team_stats`` holds fitted parameters as returned by ``fit_dixon_coles``.
+    The result is ``{"matrix", "team_a_goals", "team_b_goals"}``: ``matrix[i][j]``
+    is the chance of an ``i``-``j`` score, the other two its row and column sums.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.head_to_head_matrix(team_a, team_b, team_stats, rho, max_goals)
+
+
+def over_under_probability(
+    lambda_h: float,
+    lambda_a: float,
+    rho: float = -0.1,
+    line: float = 2.5,
+    max_goals: int = 10,
+    *,
+    auto_build: bool = False,
+):
+    """Return ``(p_over, p_under, p_push)`` for total goals against ``line``."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.over_under_probability(
+        lambda_h, lambda_a, rho, line, max_goals
+    )
+
+
+def asian_handicap_probability(
+    lambda_h: float,
+    lambda_a: float,
+    rho: float = -0.1,
+    handicap: float = 0.0,
+    max_goals: int = 10,
+    *,
+    auto_build: bool = False,
+):
+    """Return ``(p_team1, p_team2)`` with ``handicap`` goals given to the home side."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.asian_handicap_probability(
+        lambda_h, lambda_a, rho, handicap, max_goals
+    )
+
+
+def expected_points(
+    lambda_h: float,
+    lambda_a: float,
+    rho: float = -0.1,
+    max_goals: int = 10,
+    *,
+    auto_build: bool = False,
+) -> tuple[float, float]:
+    """Return ``(xpts_home, xpts_away)``, i.e. ``3 * P(win) + P(draw)`` per side."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.expected_points(lambda_h, lambda_a, rho, max_goals)
+
+
+def btts_probability(
+    lambda_h: float,
+    lambda_a: float,
+    rho: float = -0.1,
+    max_goals: int = 10,
+    *,
+    auto_build: bool = False,
+):
+    """Return ``(p_yes, p_no)`` that both teams score."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.btts_probability(lambda_h, lambda_a, rho, max_goals)
+
+
+def clean_sheet_probability(
+    lambda_h: float,
+    lambda_a: float,
+    rho: float = -0.1,
+    max_goals: int = 10,
+    *,
+    auto_build: bool = False,
+):
+    """Return ``(p_home_cs, p_away_cs)``, the chance each side concedes nothing."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.clean_sheet_probability(lambda_h, lambda_a, rho, max_goals)
+
+
+def halftime_probabilities(
+    lambda_h: float,
+    lambda_a: float,
+    rho: float = -0.1,
+    half_scale: float = 0.45,
+    max_goals: int = 10,
+    *,
+    auto_build: bool = False,
+):
+    """Return first-half ``H``/``D``/``A`` and full-time ``full_H``/``full_D``/``full_A`` odds."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.halftime_probabilities(
+        lambda_h, lambda_a, rho, half_scale, max_goals
+    )
+
+
+def bulk_match_probabilities(
+    fixtures,
+    rho: float = -0.1,
+    max_goals: int = 10,
+    *,
+    auto_build: bool = False,
+):
+    """Return ``(home_win, draw, away_win)`` for each ``(lambda_h, lambda_a)`` pair."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.bulk_match_probabilities(fixtures, rho, max_goals)
+
+
+def lambdas_from_elo(
+    elo_home: float,
+    elo_away: float,
+    home_advantage_elo: float = 0.0,
+    mu: float = 1.0,
+    *,
+    auto_build: bool = False,
+) -> tuple[float, float]:
+    """Convert Elo ratings to ``(lambda_h, lambda_a)`` around ``mu`` goals per side."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.lambdas_from_elo(elo_home, elo_away, home_advantage_elo, mu)
+
+
+def update_elo(
+    elo_home: float,
+    elo_away: float,
+    home_goals: int,
+    away_goals: int,
+    k: float = 20.0,
+    *,
+    auto_build: bool = False,
+) -> tuple[float, float]:
+    """Return the ``(elo_home, elo_away)`` ratings after one result."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.update_elo(elo_home, elo_away, home_goals, away_goals, k)
+
+
+def format_standings(standings, locale: str = "en", *, auto_build: bool = False):
+    """Translate simulated standings into locale-specific column names."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.format_standings(standings, locale)
+
+
+def match_prob_cache(
+    capacity: int = 1024, max_goals: int = 10, *, auto_build: bool = False
+):
+    """Create a Rust-side LRU cache of win/draw/loss probabilities."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.MatchProbCache(capacity, max_goals)
+
+
+def flush_probability_cache(*, auto_build: bool = False):
+    """Discard cached score matrices, e.g. after re-estimating team strengths."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    rust_module.flush_probability_cache()
+
+
+def probability_cache_size(*, auto_build: bool = False) -> int:
+    """Return how many score matrices are currently cached."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.probability_cache_size()
+
+
+def cache_stats(*, auto_build: bool = False) -> tuple[int, int]:
+    """Return ``(hits, misses)`` of the score-matrix cache."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.cache_stats()
+
+
+def reset_cache_stats(*, auto_build: bool = False):
+    """Zero the score-matrix cache hit/miss counters."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    rust_module.reset_cache_stats()
+
+
+def warm_cache(
+    base_table,
+    fixtures,
+    home_table,
+    away_table,
+    *,
+    rho: float | None = None,
+    home_advantage: float | None = None,
+    max_goals: int | None = None,
+    auto_build: bool = False,
+) -> int:
+    """Pre-compute the score matrices ``simulate_bulk`` will need.
+
+    Pass the same tables and model settings as the later ``simulate_bulk``
+    call; returns how many matrices were added to the cache.
+    """
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.warm_cache(
+        base_table, fixtures, home_table, away_table, rho, home_advantage, max_goals
+    )
+
+
+def warm_cache_parallel(
+    base_table,
+    fixtures,
+    home_table,
+    away_table,
+    *,
+    rho: float | None = None,
+    home_advantage: float | None = None,
+    max_goals: int | None = None,
+    n_threads: int | None = None,
+    auto_build: bool = False,
+) -> int:
+    """Like ``warm_cache``, computing the matrices on ``n_threads`` threads."""
+    rust_module = get_rust_module(auto_build=auto_build)
+    return rust_module.warm_cache_parallel(
+        base_table,
+        fixtures,
+        home_table,
+        away_table,
+        rho,
+        home_advantage,
+        max_goals,
+        n_threads,
     )
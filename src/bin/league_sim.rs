@@ -0,0 +1,153 @@
+//! `league-sim`: bulk-simulates a snapshot JSON file (or stdin) and prints each team's
+//! finishing-position probabilities as JSON or CSV.
+
+use clap::{Parser, ValueEnum};
+use league_outcome_simulator_rust::json::{self, table_rows, write_string};
+use league_outcome_simulator_rust::sim::{
+    run_bulk_range, BulkOptions, BulkTally, League, ModelConfig,
+};
+use std::io::Read;
+use std::process::ExitCode;
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum Format {
+    Json,
+    Csv,
+}
+
+/// Reads a snapshot JSON object with "base_table", "fixtures" and optional
+/// "home_table"/"away_table" and prints finishing-position probabilities.
+#[derive(Debug, Parser)]
+#[command(name = "league-sim")]
+struct Args {
+    /// Snapshot file; stdin when omitted or "-"
+    #[arg(value_name = "SNAPSHOT")]
+    snapshot: Option<String>,
+    /// Seasons to simulate
+    #[arg(long, default_value_t = 10_000, value_parser = at_least_one)]
+    n_sims: usize,
+    /// Base seed for a reproducible run [default: random]
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Dixon-Coles low-score correction
+    #[arg(long, allow_negative_numbers = true)]
+    rho: Option<f64>,
+    /// Fixed home multiplier instead of the table estimate
+    #[arg(long)]
+    home_advantage: Option<f64>,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = Format::Json)]
+    format: Format,
+    /// Report progress to stderr every 10%
+    #[arg(long)]
+    progress: bool,
+}
+
+fn at_least_one(text: &str) -> Result<usize, String> {
+    match text.parse() {
+        Ok(0) => Err("must be at least 1".to_string()),
+        Ok(count) => Ok(count),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn read_snapshot(path: Option<&str>) -> Result<String, String> {
+    match path.filter(|&path| path != "-") {
+        Some(path) => {
+            std::fs::read_to_string(path).map_err(|err| format!("could not read {path}: {err}"))
+        }
+        None => {
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|err| format!("could not read stdin: {err}"))?;
+            Ok(text)
+        }
+    }
+}
+
+/// Simulates in tenths when reporting progress; the counts match a single run either way.
+fn run(args: &Args, snapshot: &str) -> Result<(Vec<String>, BulkTally), String> {
+    let snapshot = json::parse(snapshot)?;
+    let section = |key: &str| {
+        snapshot
+            .get(key)
+            .ok_or_else(|| format!("snapshot has no {key:?}"))
+    };
+    let venue = |key: &str| snapshot.get(key).map_or(Ok(Vec::new()), table_rows);
+    let config = ModelConfig::validated(args.rho, args.home_advantage, None)?;
+    let league = League::new(
+        table_rows(section("base_table")?)?,
+        &venue("home_table")?,
+        &venue("away_table")?,
+        &config,
+    )?;
+    let fixtures = json::fixtures(section("fixtures")?)?
+        .iter()
+        .map(|spec| spec.resolve(&league.team_to_idx))
+        .collect::<Result<Vec<_>, _>>()?;
+    let input = league.simulation_input(&fixtures, &config);
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let options = BulkOptions::default();
+    let steps = if args.progress { 10 } else { 1 };
+    let mut tally = BulkTally::new(input.teams.len(), &options);
+    let mut done = 0;
+    for step in 1..=steps {
+        let end = args.n_sims * step / steps;
+        tally = tally.merge(run_bulk_range(&input, done..end, seed, &options), &options);
+        done = end;
+        if args.progress {
+            eprintln!("progress: {}% ({done}/{})", step * 10, args.n_sims);
+        }
+    }
+    Ok((input.teams, tally))
+}
+
+fn render(teams: &[String], tally: &BulkTally, n_sims: usize, format: Format) -> String {
+    let share = |count: &u64| *count as f64 / n_sims as f64;
+    let mut out = String::new();
+    match format {
+        Format::Json => {
+            out.push('{');
+            for (idx, (team, counts)) in teams.iter().zip(&tally.position_counts).enumerate() {
+                if idx > 0 {
+                    out.push_str(", ");
+                }
+                write_string(&mut out, team);
+                let shares: Vec<String> = counts.iter().map(|c| share(c).to_string()).collect();
+                out.push_str(&format!(": [{}]", shares.join(", ")));
+            }
+            out.push_str("}\n");
+        }
+        Format::Csv => {
+            let positions: Vec<String> = (1..=teams.len()).map(|pos| pos.to_string()).collect();
+            out.push_str(&format!("team,{}\n", positions.join(",")));
+            for (team, counts) in teams.iter().zip(&tally.position_counts) {
+                let shares: Vec<String> = counts.iter().map(|c| share(c).to_string()).collect();
+                let team = if team.contains([',', '"']) {
+                    format!("\"{}\"", team.replace('"', "\"\""))
+                } else {
+                    team.clone()
+                };
+                out.push_str(&format!("{team},{}\n", shares.join(",")));
+            }
+        }
+    }
+    out
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let result = read_snapshot(args.snapshot.as_deref()).and_then(|text| run(&args, &text));
+    match result {
+        Ok((teams, tally)) => {
+            print!("{}", render(&teams, &tally, args.n_sims, args.format));
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("league-sim: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
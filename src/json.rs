@@ -0,0 +1,101 @@
+//! Reads snapshot-shaped JSON with `serde_json`: standings tables as arrays of rows and fixtures
+//! in the shapes the Python entry points take. Used by the C ABI and the `league-sim` binary.
+
+use crate::sim::{FixtureSpec, Stats};
+use serde_json::Value;
+
+/// Parses `text`, rejecting anything that is not strict JSON (`NaN` and `Infinity` included).
+pub fn parse(text: &str) -> Result<Value, String> {
+    serde_json::from_str(text).map_err(|err| format!("Invalid JSON: {err}"))
+}
+
+/// Appends `text` as a quoted JSON string.
+pub fn write_string(out: &mut String, text: &str) {
+    out.push_str(&Value::from(text).to_string());
+}
+
+/// `(team, record)` rows of a standings table in the Python `base_table` layout, header first.
+/// Record columns must be whole numbers.
+pub fn table_rows(json: &Value) -> Result<Vec<(String, Stats)>, String> {
+    let Some(rows) = json.as_array() else {
+        return Err("Table JSON must be an array of rows".to_string());
+    };
+    rows.iter()
+        .skip(1)
+        .map(|row| {
+            let Some(cells) = row.as_array() else {
+                return Err("Table rows must be arrays".to_string());
+            };
+            let Some(team) = cells.first().and_then(Value::as_str) else {
+                return Err("Missing team name".to_string());
+            };
+            let stat = |index: usize, name: &str| match cells.get(index) {
+                Some(value) => value.as_i64().ok_or_else(|| {
+                    format!(
+                        "{name} at column {index} for {team} must be a whole number, got {value}"
+                    )
+                }),
+                None => Err(format!("Missing {name} at column {index} for {team}")),
+            };
+            let stats = Stats {
+                matches: stat(1, "matches")?,
+                wins: stat(2, "wins")?,
+                draws: stat(3, "draws")?,
+                losses: stat(4, "losses")?,
+                goals_for: stat(5, "goals for")?,
+                goals_against: stat(6, "goals against")?,
+                points: stat(7, "points")?,
+            };
+            Ok((team.to_string(), stats))
+        })
+        .collect()
+}
+
+/// Fixtures in the SofaScore `{"h": {"title"}, "a": {"title"}}` shape, with optional
+/// `"neutral": true` and `"result": [home_goals, away_goals]`, or as `[home, away]` pairs.
+pub fn fixtures(json: &Value) -> Result<Vec<FixtureSpec>, String> {
+    let Some(fixtures) = json.as_array() else {
+        return Err("Fixtures JSON must be an array".to_string());
+    };
+    fixtures.iter().map(fixture).collect()
+}
+
+fn fixture(item: &Value) -> Result<FixtureSpec, String> {
+    if let Some(names) = item.as_array() {
+        let [Value::String(home), Value::String(away)] = &names[..] else {
+            return Err("Fixture pair must be (home_team, away_team)".to_string());
+        };
+        return Ok(FixtureSpec::new(home, away));
+    }
+    let title = |side: &str| {
+        let team = item
+            .get(side)
+            .ok_or_else(|| format!("Fixture missing '{side}' object"))?;
+        team.get("title")
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("Fixture '{side}' object missing title"))
+    };
+    let mut spec = FixtureSpec::new(title("h")?, title("a")?);
+    if let Some(flag) = item.get("neutral") {
+        spec.neutral = flag.as_bool().ok_or_else(|| {
+            format!(
+                "Fixture {} vs {} neutral must be true or false",
+                spec.home, spec.away
+            )
+        })?;
+    }
+    match item.get("result") {
+        Some(Value::Null) | None => {}
+        Some(score) => {
+            let goals: Option<Vec<i64>> = score
+                .as_array()
+                .map(|goals| goals.iter().map(Value::as_i64).collect())
+                .unwrap_or_default();
+            let Some([home_goals, away_goals]) = goals.as_deref() else {
+                return Err(spec.result_shape_error());
+            };
+            spec.result = Some((*home_goals, *away_goals));
+        }
+    }
+    Ok(spec)
+}
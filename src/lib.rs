@@ -1,150 +1,130 @@
-use num_cpus;
-use pyo3::exceptions::PyValueError;
+use pyo3::exceptions::{PyIOError, PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList};
+use pyo3::types::{PyDict, PyList, PyTuple};
+use rand::seq::SliceRandom;
 use rand::Rng;
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
 use rayon::prelude::*;
 use rayon::ThreadPoolBuilder;
-use std::cmp::Ordering;
-use std::collections::hash_map::Entry;
-use std::collections::HashMap;
-use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::num::NonZeroUsize;
+use std::sync::atomic::Ordering as AtomicOrdering;
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
 use std::sync::Once;
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone)]
-struct ProbabilityDistribution {
-    cdf: Vec<f64>,
-    dim: usize,
-}
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod json;
+pub mod optimizer;
+pub mod qmc;
+pub mod sim;
+
+use sim::*;
 
 #[macro_use]
 extern crate lazy_static;
 
-const HOME_ADVANTAGE: f64 = 1.25;
-const DEFAULT_LAMBDA: f64 = 1.0;
-const DEFAULT_RHO: f64 = -0.1;
-const MAX_GOALS: usize = 10;
-
-lazy_static! {
-    static ref PROBABILITY_CACHE: Mutex<HashMap<(u64, u64, u64), ProbabilityDistribution>> =
-        Mutex::new(HashMap::new());
-}
+const DEFAULT_HALF_SCALE: f64 = 0.45;
+const DEFAULT_PENALTY_RATE: f64 = 0.75;
+const DEFAULT_FORM_WINDOW: usize = 5;
+const DEFAULT_FORM_DECAY: f64 = 0.8;
+const DEFAULT_FATIGUE_WINDOW: usize = 4;
+const DEFAULT_FATIGUE_FACTOR: f64 = 0.9;
+const DEFAULT_RED_CARD_RATE: f64 = 0.03;
+const DEFAULT_RED_CARD_PENALTY: f64 = 0.3;
+const DEFAULT_FIT_ITERATIONS: usize = 1000;
+const DEFAULT_FIT_TOLERANCE: f64 = 1e-8;
+const DEFAULT_ELO_K: f64 = 20.0;
+const DEFAULT_CONFIG_SIMULATIONS: usize = 10_000;
+const DEFAULT_TOP_K_TABLES: usize = 25;
+const DEFAULT_RELEGATION_ZONE: usize = 3;
 
 static INIT_RAYON: Once = Once::new();
 
-#[derive(Debug, Clone)]
-struct FixtureSimulation {
-    home_idx: usize,
-    away_idx: usize,
-    distribution: ProbabilityDistribution,
+impl ScoreModel {
+    fn from_args(
+        model: Option<&str>,
+        dispersion: Option<f64>,
+        lambda_3: Option<f64>,
+    ) -> PyResult<Self> {
+        Self::parse(model, dispersion, lambda_3).map_err(PyValueError::new_err)
+    }
 }
 
-#[derive(Debug, Clone)]
-struct SimulationInput {
-    teams: Vec<String>,
-    initial_stats: Vec<[i64; 4]>,
-    fixtures: Vec<FixtureSimulation>,
+impl ModelConfig {
+    fn from_args(
+        rho: Option<f64>,
+        home_advantage: Option<f64>,
+        max_goals: Option<usize>,
+    ) -> PyResult<Self> {
+        Self::validated(rho, home_advantage, max_goals).map_err(PyValueError::new_err)
+    }
 }
 
-#[derive(Clone)]
-struct SeasonResult {
-    order: Vec<usize>,
-    final_stats: Vec<[i64; 4]>,
+/// Small LRU cache of win/draw/loss triples keyed by `(lambda_h, lambda_a, rho)`.
+///
+/// Unlike `PROBABILITY_CACHE` it keeps only three floats per entry, so callers
+/// that repeatedly price the same pairings avoid rebuilding and cloning CDFs.
+#[pyclass]
+struct MatchProbCache {
+    cache: BoundedCache<(u64, u64, u64), (f64, f64, f64)>,
+    max_goals: usize,
 }
 
-struct DixonColes {}
-
-impl DixonColes {
-    fn correction_factor(x: i64, y: i64, lambda_x: f64, lambda_y: f64, rho: f64) -> f64 {
-        match (x, y) {
-            (0, 0) => 1.0 - lambda_x * lambda_y * rho,
-            (0, 1) => 1.0 + lambda_x * rho,
-            (1, 0) => 1.0 + lambda_y * rho,
-            (1, 1) => 1.0 - rho,
-            _ => 1.0,
+impl MatchProbCache {
+    fn with_capacity(capacity: NonZeroUsize, max_goals: usize) -> Self {
+        MatchProbCache {
+            cache: BoundedCache::new(capacity),
+            max_goals,
         }
     }
 
-    fn poisson_pmf(k: i64, lambda: f64) -> f64 {
-        if lambda <= 0.0 || k < 0 {
-            return 0.0;
-        }
-        let k_float = k as f64;
-        let log_lambda = lambda.ln();
-        let log_k_factorial = (1..=k).map(|i| (i as f64).ln()).sum::<f64>();
-        (-lambda + k_float * log_lambda - log_k_factorial).exp()
+    fn key(lambda_h: f64, lambda_a: f64, rho: f64) -> (u64, u64, u64) {
+        (lambda_h.to_bits(), lambda_a.to_bits(), rho.to_bits())
+    }
+}
+
+#[pymethods]
+impl MatchProbCache {
+    #[new]
+    #[args(capacity = "1024", max_goals = "MAX_GOALS")]
+    fn new(capacity: usize, max_goals: usize) -> PyResult<Self> {
+        let capacity = NonZeroUsize::new(capacity)
+            .ok_or_else(|| PyValueError::new_err("capacity must be at least 1"))?;
+        Ok(Self::with_capacity(capacity, max_goals))
     }
 
-    fn result_probability(x: i64, y: i64, lambda_x: f64, lambda_y: f64, rho: f64) -> f64 {
-        let p_x = Self::poisson_pmf(x, lambda_x);
-        let p_y = Self::poisson_pmf(y, lambda_y);
-        let tau = Self::correction_factor(x, y, lambda_x, lambda_y, rho);
-        p_x * p_y * tau
+    /// Cached `(home_win, draw, away_win)` for the pairing, or `None` on a miss.
+    fn get(&self, lambda_h: f64, lambda_a: f64, rho: f64) -> Option<(f64, f64, f64)> {
+        self.cache.get(&Self::key(lambda_h, lambda_a, rho))
     }
 
-    fn precompute_probability_matrix(
-        lambda_h: f64,
-        lambda_a: f64,
-        rho: f64,
-        max_goals: usize,
-    ) -> ProbabilityDistribution {
-        let mut flat_probs = Vec::with_capacity((max_goals + 1) * (max_goals + 1));
-        let mut total = 0.0;
-        for h in 0..=max_goals {
-            for a in 0..=max_goals {
-                let p = Self::result_probability(h as i64, a as i64, lambda_h, lambda_a, rho);
-                flat_probs.push(p);
-                total += p;
+    /// Compute and store outcome probabilities for every `(lambda_h, lambda_a)` pair.
+    fn precompute(&self, lambda_pairs: Vec<(f64, f64)>, rho: f64) {
+        for (lambda_h, lambda_a) in lambda_pairs {
+            let key = Self::key(lambda_h, lambda_a, rho);
+            if self.cache.get(&key).is_some() {
+                continue;
             }
+            self.cache.insert(
+                key,
+                match_outcome_probs(lambda_h, lambda_a, rho, self.max_goals),
+            );
         }
+    }
 
-        let mut cdf = Vec::with_capacity(flat_probs.len());
-        let mut acc = 0.0;
-        for prob in flat_probs.iter_mut() {
-            *prob /= total.max(f64::EPSILON);
-            acc += *prob;
-            cdf.push(acc);
-        }
-        if let Some(last) = cdf.last_mut() {
-            *last = 1.0;
-        }
-        ProbabilityDistribution {
-            cdf,
-            dim: max_goals + 1,
-        }
+    #[getter]
+    fn capacity(&self) -> usize {
+        self.cache.capacity()
     }
 
-    fn get_probability_matrix(
-        lambda_h: f64,
-        lambda_a: f64,
-        rho: f64,
-        max_goals: usize,
-    ) -> ProbabilityDistribution {
-        let key = (lambda_h.to_bits(), lambda_a.to_bits(), rho.to_bits());
-        let mut cache = PROBABILITY_CACHE
-            .lock()
-            .unwrap_or_else(|poison| poison.into_inner());
-        match cache.entry(key) {
-            Entry::Occupied(entry) => entry.get().clone(),
-            Entry::Vacant(entry) => entry
-                .insert(Self::precompute_probability_matrix(
-                    lambda_h, lambda_a, rho, max_goals,
-                ))
-                .clone(),
-        }
-    }
-
-    fn simulate_from_distribution<R: Rng>(rng: &mut R, pd: &ProbabilityDistribution) -> (i64, i64) {
-        let u: f64 = rng.gen();
-        let idx = match pd
-            .cdf
-            .binary_search_by(|value| value.partial_cmp(&u).unwrap_or(Ordering::Greater))
-        {
-            Ok(index) | Err(index) => index.min(pd.cdf.len().saturating_sub(1)),
-        };
-        ((idx / pd.dim) as i64, (idx % pd.dim) as i64)
+    fn __len__(&self) -> usize {
+        self.cache.len()
     }
 }
 
@@ -162,351 +142,9473 @@ fn extract_team_name(row_list: &PyList) -> PyResult<String> {
         .extract()
 }
 
+/// Team names, current records and a name -> index map from a standings table (header row first).
+fn parse_base_table(base: &PyList) -> PyResult<(Vec<String>, Vec<Stats>, TeamIndex)> {
+    let mut teams: Vec<String> = Vec::new();
+    let mut initial_stats: Vec<Stats> = Vec::new();
+    let mut team_to_idx: TeamIndex = HashMap::new();
+
+    for row in base.iter().skip(1) {
+        let row_list: &PyList = row.extract()?;
+        let team = extract_team_name(row_list)?;
+        let stats = Stats {
+            matches: extract_row_stat(row_list, 1, "matches")?,
+            wins: extract_row_stat(row_list, 2, "wins")?,
+            draws: extract_row_stat(row_list, 3, "draws")?,
+            losses: extract_row_stat(row_list, 4, "losses")?,
+            goals_for: extract_row_stat(row_list, 5, "goals for")?,
+            goals_against: extract_row_stat(row_list, 6, "goals against")?,
+            points: extract_row_stat(row_list, 7, "points")?,
+        };
+        let index = teams.len();
+        teams.push(team.clone());
+        team_to_idx.insert(team, index);
+        initial_stats.push(stats);
+    }
+    Ok((teams, initial_stats, team_to_idx))
+}
+
+/// `(home, away)` titles of a SofaScore fixture dict.
+fn sofascore_team_names(dict: &PyDict) -> PyResult<(String, String)> {
+    let home_obj = dict
+        .get_item("h")
+        .ok_or_else(|| PyValueError::new_err("Fixture missing 'h' object"))?;
+    let away_obj = dict
+        .get_item("a")
+        .ok_or_else(|| PyValueError::new_err("Fixture missing 'a' object"))?;
+    let home_dict: &PyDict = home_obj
+        .downcast()
+        .map_err(|_| PyValueError::new_err("Fixture 'h' is not a dict"))?;
+    let away_dict: &PyDict = away_obj
+        .downcast()
+        .map_err(|_| PyValueError::new_err("Fixture 'a' is not a dict"))?;
+    let home_name: String = home_dict
+        .get_item("title")
+        .ok_or_else(|| PyValueError::new_err("Fixture home object missing title"))?
+        .extract()?;
+    let away_name: String = away_dict
+        .get_item("title")
+        .ok_or_else(|| PyValueError::new_err("Fixture away object missing title"))?
+        .extract()?;
+    Ok((home_name, away_name))
+}
+
+/// `(home, away)` names of one fixture, plus its dict when given in SofaScore shape.
+fn fixture_team_names(item: &PyAny) -> PyResult<(String, String, Option<&PyDict>)> {
+    if item.is_instance_of::<PyList>()? || item.is_instance_of::<PyTuple>()? {
+        let names: Vec<String> = item.extract()?;
+        let [home_name, away_name] = <[String; 2]>::try_from(names)
+            .map_err(|_| PyValueError::new_err("Fixture pair must be (home_team, away_team)"))?;
+        return Ok((home_name, away_name, None));
+    }
+    let dict: &PyDict = item.extract()?;
+    let (home_name, away_name) = sofascore_team_names(dict)?;
+    Ok((home_name, away_name, Some(dict)))
+}
+
+/// Remaining fixtures in SofaScore `{"h": .., "a": ..}` shape, with an optional `"neutral": true`,
+/// or as plain `(home_team, away_team)` lists or tuples.
+fn parse_fixture_pairs(
+    fixtures_list: &PyList,
+    team_to_idx: &TeamIndex,
+) -> PyResult<Vec<ParsedFixture>> {
+    let mut pairs = Vec::with_capacity(fixtures_list.len());
+    for item in fixtures_list.iter() {
+        let (home_name, away_name, dict) = fixture_team_names(item)?;
+        let mut spec = FixtureSpec::new(&home_name, &away_name);
+        if let Some(flag) = dict.and_then(|dict| dict.get_item("neutral")) {
+            spec.neutral = flag.extract()?;
+        }
+        match dict.and_then(|dict| dict.get_item("result")) {
+            Some(score) if !score.is_none() => {
+                let score: Vec<i64> = score.extract().unwrap_or_default();
+                let [home_goals, away_goals] = score[..] else {
+                    return Err(PyValueError::new_err(spec.result_shape_error()));
+                };
+                spec.result = Some((home_goals, away_goals));
+            }
+            _ => {}
+        }
+        pairs.push(spec.resolve(team_to_idx).map_err(PyValueError::new_err)?);
+    }
+    Ok(pairs)
+}
+
+impl League {
+    fn into_input(
+        self,
+        py: Python,
+        fixtures: PyObject,
+        config: &ModelConfig,
+    ) -> PyResult<SimulationInput> {
+        let fixtures_list: &PyList = fixtures.extract(py)?;
+        let fixtures = parse_fixture_pairs(fixtures_list, &self.team_to_idx)?;
+        self.check_score_model(&fixtures, config)
+            .map_err(PyValueError::new_err)?;
+        Ok(self.simulation_input(&fixtures, config))
+    }
+}
+
 fn parse_simulation_input(
     py: Python,
     base_table: PyObject,
     fixtures: PyObject,
     home_table: PyObject,
     away_table: PyObject,
+    config: &ModelConfig,
 ) -> PyResult<SimulationInput> {
+    parse_league(py, base_table, home_table, away_table, config)?.into_input(py, fixtures, config)
+}
+
+/// A league without venue splits: both venues are rated from the overall record.
+fn parse_base_league(py: Python, base_table: PyObject, config: &ModelConfig) -> PyResult<League> {
+    let base: &PyList = base_table.extract(py)?;
+    let (teams, stats, _) = parse_base_table(base)?;
+    let rows: Vec<(String, Stats)> = teams.into_iter().zip(stats).collect();
+    League::new(rows.clone(), &rows, &rows, config).map_err(PyValueError::new_err)
+}
+
+fn parse_league(
+    py: Python,
+    base_table: PyObject,
+    home_table: PyObject,
+    away_table: PyObject,
+    config: &ModelConfig,
+) -> PyResult<League> {
     let base: &PyList = base_table.extract(py)?;
-    let fixtures_list: &PyList = fixtures.extract(py)?;
     let home_list: &PyList = home_table.extract(py)?;
     let away_list: &PyList = away_table.extract(py)?;
 
-    let mut teams: Vec<String> = Vec::new();
-    let mut initial_stats: Vec<[i64; 4]> = Vec::new();
-    let mut team_to_idx: HashMap<String, usize> = HashMap::new();
+    let (teams, initial_stats, _) = parse_base_table(base)?;
+    let table: Vec<(String, Stats)> = teams.into_iter().zip(initial_stats).collect();
+    let home = parse_venue_table(home_list, "home")?;
+    let away = parse_venue_table(away_list, "away")?;
+    League::new(table, &home, &away, config).map_err(PyValueError::new_err)
+}
 
-    for row in base.iter().skip(1) {
+/// `(team, record)` rows of a home or away table; only matches and goals are read.
+fn parse_venue_table(venue_list: &PyList, venue: &str) -> PyResult<Vec<(String, Stats)>> {
+    let mut rows = Vec::with_capacity(venue_list.len());
+    for row in venue_list.iter().skip(1) {
         let row_list: &PyList = row.extract()?;
-        let team = extract_team_name(row_list)?;
-        let m = extract_row_stat(row_list, 1, "matches")?;
-        let gf = extract_row_stat(row_list, 5, "goals for")?;
-        let ga = extract_row_stat(row_list, 6, "goals against")?;
-        let pts = extract_row_stat(row_list, 7, "points")?;
-        let index = teams.len();
-        teams.push(team.clone());
-        team_to_idx.insert(team, index);
-        initial_stats.push([pts, gf, ga, m]);
+        let stats = Stats {
+            matches: extract_row_stat(row_list, 1, &format!("{venue} matches"))?,
+            goals_for: extract_row_stat(row_list, 5, &format!("{venue} goals for"))?,
+            goals_against: extract_row_stat(row_list, 6, &format!("{venue} goals against"))?,
+            ..Stats::default()
+        };
+        rows.push((extract_team_name(row_list)?, stats));
     }
+    Ok(rows)
+}
 
-    let num_teams = teams.len();
-    let mut home_stats: Vec<(i64, i64, i64)> = vec![(0, 0, 0); num_teams];
-    let mut away_stats: Vec<(i64, i64, i64)> = vec![(0, 0, 0); num_teams];
+/// Played results as `{"home", "away", "home_goals", "away_goals"}` dicts, oldest first.
+fn parse_played_results(results: &PyList, team_to_idx: &TeamIndex) -> PyResult<Vec<MatchResult>> {
+    let team = |dict: &PyDict, key: &str| -> PyResult<usize> {
+        let name: String = dict
+            .get_item(key)
+            .ok_or_else(|| PyValueError::new_err(format!("Result missing '{key}'")))?
+            .extract()?;
+        team_to_idx
+            .get(&name)
+            .copied()
+            .ok_or_else(|| PyValueError::new_err(format!("Team {name} not found in standings")))
+    };
+    let goals = |dict: &PyDict, key: &str| -> PyResult<i64> {
+        let value: i64 = dict
+            .get_item(key)
+            .ok_or_else(|| PyValueError::new_err(format!("Result missing '{key}'")))?
+            .extract()?;
+        if value < 0 {
+            return Err(PyValueError::new_err(format!(
+                "Result {key} cannot be negative"
+            )));
+        }
+        Ok(value)
+    };
+    results
+        .iter()
+        .map(|item| {
+            let dict: &PyDict = item.extract()?;
+            Ok(MatchResult {
+                home_idx: team(dict, "home")?,
+                away_idx: team(dict, "away")?,
+                home_goals: goals(dict, "home_goals")?,
+                away_goals: goals(dict, "away_goals")?,
+            })
+        })
+        .collect()
+}
 
-    for row in home_list.iter().skip(1) {
-        let row_list: &PyList = row.extract()?;
-        let team = extract_team_name(row_list)?;
-        let idx = *team_to_idx.get(&team).ok_or_else(|| {
-            PyValueError::new_err(format!(
-                "Team {team} found in home table but not base table"
-            ))
-        })?;
-        home_stats[idx] = (
-            extract_row_stat(row_list, 5, "home goals for")?,
-            extract_row_stat(row_list, 6, "home goals against")?,
-            extract_row_stat(row_list, 1, "home matches")?,
-        );
+/// Per-team `(scored, conceded)` per-match rates over each team's last `window` results, weighting
+/// the match `k` games back by `decay^k`. Teams without recent results get `None`.
+fn form_scoring_rates(
+    results: &[MatchResult],
+    num_teams: usize,
+    window: usize,
+    decay: f64,
+) -> Vec<Option<(f64, f64)>> {
+    let mut recent: Vec<Vec<(i64, i64)>> = vec![Vec::new(); num_teams];
+    for result in results.iter().rev() {
+        for (team, scored, conceded) in [
+            (result.home_idx, result.home_goals, result.away_goals),
+            (result.away_idx, result.away_goals, result.home_goals),
+        ] {
+            if recent[team].len() < window {
+                recent[team].push((scored, conceded));
+            }
+        }
     }
+    recent
+        .iter()
+        .map(|matches| {
+            if matches.is_empty() {
+                return None;
+            }
+            let mut weight = 1.0;
+            let (mut total_weight, mut scored, mut conceded) = (0.0, 0.0, 0.0);
+            for &(gf, ga) in matches {
+                total_weight += weight;
+                scored += weight * gf as f64;
+                conceded += weight * ga as f64;
+                weight *= decay;
+            }
+            Some((scored / total_weight, conceded / total_weight))
+        })
+        .collect()
+}
 
-    for row in away_list.iter().skip(1) {
-        let row_list: &PyList = row.extract()?;
-        let team = extract_team_name(row_list)?;
-        let idx = *team_to_idx.get(&team).ok_or_else(|| {
-            PyValueError::new_err(format!(
-                "Team {team} found in away table but not base table"
-            ))
-        })?;
-        away_stats[idx] = (
-            extract_row_stat(row_list, 5, "away goals for")?,
-            extract_row_stat(row_list, 6, "away goals against")?,
-            extract_row_stat(row_list, 1, "away matches")?,
-        );
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    English,
+    German,
+    Spanish,
+    French,
+}
+
+impl Language {
+    fn from_code(code: &str) -> Option<Self> {
+        match code.to_ascii_lowercase().as_str() {
+            "en" | "english" => Some(Language::English),
+            "de" | "german" => Some(Language::German),
+            "es" | "spanish" => Some(Language::Spanish),
+            "fr" | "french" => Some(Language::French),
+            _ => None,
+        }
     }
 
-    let total_gf: i64 = initial_stats.iter().map(|stats| stats[1]).sum();
-    let total_matches: i64 = initial_stats.iter().map(|stats| stats[3]).sum();
-    let avg_league_goals = if total_matches > 0 {
-        total_gf as f64 / total_matches as f64
-    } else {
-        DEFAULT_LAMBDA
-    };
+    fn column_label(self, column: &str) -> Option<&'static str> {
+        let labels: [&'static str; 10] = match self {
+            Language::English => ["Pos", "Team", "M", "W", "D", "L", "GF", "GA", "GD", "PTS"],
+            Language::German => [
+                "Pl.",
+                "Verein",
+                "Sp.",
+                "S",
+                "U",
+                "N",
+                "Tore",
+                "Gegentore",
+                "Diff.",
+                "Pkt.",
+            ],
+            Language::Spanish => [
+                "Pos", "Equipo", "PJ", "PG", "PE", "PP", "GF", "GC", "DG", "Pts",
+            ],
+            Language::French => [
+                "Pos", "Équipe", "J", "G", "N", "P", "BP", "BC", "Diff", "Pts",
+            ],
+        };
+        STANDINGS_COLUMNS
+            .iter()
+            .position(|&key| key == column)
+            .map(|index| labels[index])
+    }
+}
 
-    let home_total_gf: i64 = home_stats.iter().map(|(gf, _, _)| gf).sum();
-    let away_total_gf: i64 = away_stats.iter().map(|(gf, _, _)| gf).sum();
-    let home_advantage = if away_total_gf > 0 {
-        (home_total_gf as f64 / away_total_gf as f64).clamp(1.0, 1.5)
-    } else {
-        HOME_ADVANTAGE
-    };
+/// Canonical column keys understood by the locale formatter, in display order.
+const STANDINGS_COLUMNS: [&str; 10] = ["Pos", "Team", "M", "W", "D", "L", "GF", "GA", "GD", "PTS"];
 
-    let mut home_attack = vec![1.0; num_teams];
-    let mut home_defense = vec![1.0; num_teams];
-    let mut away_attack = vec![1.0; num_teams];
-    let mut away_defense = vec![1.0; num_teams];
+#[derive(Debug, Clone)]
+struct StandingsLocale {
+    lang: Language,
+    column_order: Vec<String>,
+}
 
-    for idx in 0..num_teams {
-        let (hgf, hga, hm) = home_stats[idx];
-        if hm > 0 && avg_league_goals > 0.0 {
-            home_attack[idx] = (hgf as f64 / hm as f64) / avg_league_goals;
-            home_defense[idx] = (hga as f64 / hm as f64) / avg_league_goals;
-        }
-        let (agf, aga, am) = away_stats[idx];
-        if am > 0 && avg_league_goals > 0.0 {
-            away_attack[idx] = (agf as f64 / am as f64) / avg_league_goals;
-            away_defense[idx] = (aga as f64 / am as f64) / avg_league_goals;
+impl StandingsLocale {
+    fn new(lang: Language) -> Self {
+        StandingsLocale {
+            lang,
+            column_order: STANDINGS_COLUMNS
+                .iter()
+                .map(|key| key.to_string())
+                .collect(),
         }
     }
+}
 
-    let mut fixtures: Vec<FixtureSimulation> = Vec::new();
-    for item in fixtures_list.iter() {
-        let dict: &PyDict = item.extract()?;
-        let home_obj = dict
-            .get_item("h")
-            .ok_or_else(|| PyValueError::new_err("Fixture missing 'h' object"))?;
-        let away_obj = dict
-            .get_item("a")
-            .ok_or_else(|| PyValueError::new_err("Fixture missing 'a' object"))?;
-        let home_dict: &PyDict = home_obj
-            .downcast()
-            .map_err(|_| PyValueError::new_err("Fixture 'h' is not a dict"))?;
-        let away_dict: &PyDict = away_obj
+fn standings_header(locale: &StandingsLocale) -> Vec<String> {
+    locale
+        .column_order
+        .iter()
+        .map(|column| {
+            locale
+                .lang
+                .column_label(column)
+                .map_or_else(|| column.clone(), str::to_string)
+        })
+        .collect()
+}
+
+fn format_standings_locale(
+    standings: &[(String, Stats)],
+    locale: &StandingsLocale,
+) -> Vec<HashMap<String, String>> {
+    let header = standings_header(locale);
+    standings
+        .iter()
+        .enumerate()
+        .map(|(index, (team, stats))| {
+            locale
+                .column_order
+                .iter()
+                .zip(header.iter())
+                .map(|(column, label)| {
+                    let value = match column.as_str() {
+                        "Pos" => (index + 1).to_string(),
+                        "Team" => team.clone(),
+                        "M" => stats.matches.to_string(),
+                        "W" => stats.wins.to_string(),
+                        "D" => stats.draws.to_string(),
+                        "L" => stats.losses.to_string(),
+                        "GF" => stats.goals_for.to_string(),
+                        "GA" => stats.goals_against.to_string(),
+                        "GD" => stats.goal_difference().to_string(),
+                        "PTS" => stats.points.to_string(),
+                        _ => String::new(),
+                    };
+                    (label.clone(), value)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn extract_standings_stat(dict: &PyDict, key: &str) -> PyResult<i64> {
+    dict.get_item(key)
+        .ok_or_else(|| PyValueError::new_err(format!("Standings entry missing '{key}'")))?
+        .extract()
+}
+
+fn parse_tiebreaker_stages(stages: &PyList) -> PyResult<Vec<TiebreakerStage>> {
+    stages
+        .iter()
+        .map(|stage| {
+            if let Ok(coefficients) = stage.downcast::<PyDict>() {
+                let table = coefficients
+                    .get_item("uefa_coefficient")
+                    .ok_or_else(|| {
+                        PyValueError::new_err(
+                            "Tiebreaker dict stages must have a 'uefa_coefficient' key",
+                        )
+                    })?
+                    .extract()?;
+                return Ok(TiebreakerStage::UefaCoefficient(table));
+            }
+            let name: String = stage.extract()?;
+            TiebreakerStage::from_name(&name).map_err(PyValueError::new_err)
+        })
+        .collect()
+}
+
+fn validate_lambda(name: &str, value: f64) -> PyResult<f64> {
+    if value >= 0.0 && value.is_finite() {
+        Ok(value)
+    } else {
+        Err(PyValueError::new_err(format!(
+            "{name} must be a finite non-negative expected goal count, got {value}"
+        )))
+    }
+}
+
+/// Home win, draw and away win probabilities for one fixture, straight from the score matrix.
+#[pyfunction(rho = "DEFAULT_RHO", max_goals = "MAX_GOALS")]
+fn match_probabilities(
+    py: Python,
+    lambda_h: f64,
+    lambda_a: f64,
+    rho: f64,
+    max_goals: usize,
+) -> PyResult<PyObject> {
+    let lambda_h = validate_lambda("lambda_h", lambda_h)?;
+    let lambda_a = validate_lambda("lambda_a", lambda_a)?;
+    let config = ModelConfig::from_args(Some(rho), None, Some(max_goals))?;
+    let (home_win, draw, away_win) =
+        DixonColes::precompute_probability_matrix(lambda_h, lambda_a, config.rho, config.max_goals)
+            .outcome_probabilities();
+    let result = PyDict::new(py);
+    result.set_item("home_win", home_win)?;
+    result.set_item("draw", draw)?;
+    result.set_item("away_win", away_win)?;
+    Ok(result.into())
+}
+
+/// Correct-score probabilities keyed by `(home_goals, away_goals)`.
+#[pyfunction(rho = "DEFAULT_RHO", max_goals = "MAX_GOALS")]
+fn scoreline_probabilities(
+    py: Python,
+    lambda_h: f64,
+    lambda_a: f64,
+    rho: f64,
+    max_goals: usize,
+) -> PyResult<PyObject> {
+    let lambda_h = validate_lambda("lambda_h", lambda_h)?;
+    let lambda_a = validate_lambda("lambda_a", lambda_a)?;
+    let config = ModelConfig::from_args(Some(rho), None, Some(max_goals))?;
+    let distribution =
+        DixonColes::precompute_probability_matrix(lambda_h, lambda_a, config.rho, config.max_goals);
+    let result = PyDict::new(py);
+    for (score, probability) in distribution.scorelines() {
+        result.set_item(score, probability)?;
+    }
+    Ok(result.into())
+}
+
+/// Expected goals of `home` hosting `away` under fitted `{"attack", "defense", "mu",
+/// "home_advantage"}` parameters, the shape `fit_dixon_coles` returns. A missing
+/// `home_advantage` falls back to the default.
+fn fitted_lambdas(params: &PyDict, home: &str, away: &str) -> PyResult<(f64, f64)> {
+    let field = |key: &str| {
+        params
+            .get_item(key)
+            .ok_or_else(|| PyValueError::new_err(format!("team_stats missing '{key}'")))
+    };
+    let rating = |key: &str, team: &str| -> PyResult<f64> {
+        let ratings: &PyDict = field(key)?
             .downcast()
-            .map_err(|_| PyValueError::new_err("Fixture 'a' is not a dict"))?;
-        let home_name: String = home_dict
-            .get_item("title")
-            .ok_or_else(|| PyValueError::new_err("Fixture home object missing title"))?
-            .extract()?;
-        let away_name: String = away_dict
-            .get_item("title")
-            .ok_or_else(|| PyValueError::new_err("Fixture away object missing title"))?
+            .map_err(|_| PyValueError::new_err(format!("team_stats '{key}' must be a dict")))?;
+        let value: f64 = ratings
+            .get_item(team)
+            .ok_or_else(|| PyValueError::new_err(format!("team_stats has no {key} for {team}")))?
             .extract()?;
+        if !value.is_finite() || value < 0.0 {
+            return Err(PyValueError::new_err(format!(
+                "{team} {key} must be a non-negative number"
+            )));
+        }
+        Ok(value)
+    };
+    let mu: f64 = field("mu")?.extract()?;
+    let home_advantage: f64 = match params.get_item("home_advantage") {
+        Some(value) => value.extract()?,
+        None => HOME_ADVANTAGE,
+    };
+    let lambda_home = mu * home_advantage * rating("attack", home)? * rating("defense", away)?;
+    let lambda_away = mu * rating("attack", away)? * rating("defense", home)?;
+    Ok((
+        validate_lambda("home lambda", lambda_home)?,
+        validate_lambda("away lambda", lambda_away)?,
+    ))
+}
+
+/// Every scoreline of `team_a` hosting `team_b`: `{"matrix", "team_a_goals", "team_b_goals"}`,
+/// where `matrix[i][j]` is the chance of an `i`-`j` result and the other two are its row and
+/// column sums. `team_stats` holds fitted parameters as returned by `fit_dixon_coles`.
+#[pyfunction(rho = "DEFAULT_RHO", max_goals = "MAX_GOALS")]
+fn head_to_head_matrix(
+    py: Python,
+    team_a: String,
+    team_b: String,
+    team_stats: PyObject,
+    rho: f64,
+    max_goals: usize,
+) -> PyResult<PyObject> {
+    let config = ModelConfig::from_args(Some(rho), None, Some(max_goals))?;
+    let team_stats: &PyDict = team_stats.extract(py)?;
+    let (lambda_a, lambda_b) = fitted_lambdas(team_stats, &team_a, &team_b)?;
+    let distribution =
+        DixonColes::precompute_probability_matrix(lambda_a, lambda_b, config.rho, config.max_goals);
+    let probabilities: Vec<f64> = distribution.probabilities().collect();
+    let matrix: Vec<&[f64]> = probabilities.chunks(config.max_goals + 1).collect();
+    let (team_a_goals, team_b_goals) = distribution.goal_marginals();
 
-        let home_idx = *team_to_idx.get(&home_name).ok_or_else(|| {
-            PyValueError::new_err(format!("Team {home_name} not found in standings"))
-        })?;
-        let away_idx = *team_to_idx.get(&away_name).ok_or_else(|| {
-            PyValueError::new_err(format!("Team {away_name} not found in standings"))
-        })?;
+    let result = PyDict::new(py);
+    result.set_item("matrix", matrix)?;
+    result.set_item("team_a_goals", team_a_goals)?;
+    result.set_item("team_b_goals", team_b_goals)?;
+    Ok(result.into())
+}
 
-        let lambda_h =
-            avg_league_goals * home_attack[home_idx] * away_defense[away_idx] * home_advantage;
-        let lambda_a = avg_league_goals * away_attack[away_idx] * home_defense[home_idx];
-        let distribution =
-            DixonColes::get_probability_matrix(lambda_h, lambda_a, DEFAULT_RHO, MAX_GOALS);
-        fixtures.push(FixtureSimulation {
-            home_idx,
-            away_idx,
-            distribution,
-        });
+/// `(p_over, p_under, p_push)` for total goals against `line`, e.g. `2.5`.
+#[pyfunction(rho = "DEFAULT_RHO", line = "2.5", max_goals = "MAX_GOALS")]
+fn over_under_probability(
+    lambda_h: f64,
+    lambda_a: f64,
+    rho: f64,
+    line: f64,
+    max_goals: usize,
+) -> PyResult<(f64, f64, f64)> {
+    let lambda_h = validate_lambda("lambda_h", lambda_h)?;
+    let lambda_a = validate_lambda("lambda_a", lambda_a)?;
+    if !line.is_finite() {
+        return Err(PyValueError::new_err(format!(
+            "line must be finite, got {line}"
+        )));
     }
+    let config = ModelConfig::from_args(Some(rho), None, Some(max_goals))?;
+    Ok(
+        DixonColes::get_probability_matrix(lambda_h, lambda_a, config.rho, config.max_goals)
+            .total_goals_split(line),
+    )
+}
 
-    Ok(SimulationInput {
-        teams,
-        initial_stats,
-        fixtures,
-    })
+/// `(p_team1, p_team2)` on an Asian handicap line given to the home side, e.g. `-0.5`.
+#[pyfunction(rho = "DEFAULT_RHO", handicap = "0.0", max_goals = "MAX_GOALS")]
+fn asian_handicap_probability(
+    lambda_h: f64,
+    lambda_a: f64,
+    rho: f64,
+    handicap: f64,
+    max_goals: usize,
+) -> PyResult<(f64, f64)> {
+    let lambda_h = validate_lambda("lambda_h", lambda_h)?;
+    let lambda_a = validate_lambda("lambda_a", lambda_a)?;
+    if !handicap.is_finite() || (handicap * 4.0).fract() != 0.0 {
+        return Err(PyValueError::new_err(format!(
+            "handicap must be a multiple of 0.25, got {handicap}"
+        )));
+    }
+    let config = ModelConfig::from_args(Some(rho), None, Some(max_goals))?;
+    Ok(
+        DixonColes::get_probability_matrix(lambda_h, lambda_a, config.rho, config.max_goals)
+            .asian_handicap_split(handicap),
+    )
 }
 
-fn simulate_single_season<R: Rng>(input: &SimulationInput, rng: &mut R) -> SeasonResult {
-    let num_teams = input.teams.len();
-    let mut standings = input.initial_stats.clone();
+/// `(xpts_home, xpts_away)` for one fixture.
+#[pyfunction(rho = "DEFAULT_RHO", max_goals = "MAX_GOALS")]
+fn expected_points(
+    lambda_h: f64,
+    lambda_a: f64,
+    rho: f64,
+    max_goals: usize,
+) -> PyResult<(f64, f64)> {
+    let lambda_h = validate_lambda("lambda_h", lambda_h)?;
+    let lambda_a = validate_lambda("lambda_a", lambda_a)?;
+    let config = ModelConfig::from_args(Some(rho), None, Some(max_goals))?;
+    Ok(
+        DixonColes::get_probability_matrix(lambda_h, lambda_a, config.rho, config.max_goals)
+            .expected_points(),
+    )
+}
 
-    for fixture in &input.fixtures {
-        let home_idx = fixture.home_idx;
-        let away_idx = fixture.away_idx;
-        let (gh, ga) = DixonColes::simulate_from_distribution(rng, &fixture.distribution);
+/// `(p_yes, p_no)` that both teams score at least once.
+#[pyfunction(rho = "DEFAULT_RHO", max_goals = "MAX_GOALS")]
+fn btts_probability(
+    lambda_h: f64,
+    lambda_a: f64,
+    rho: f64,
+    max_goals: usize,
+) -> PyResult<(f64, f64)> {
+    let lambda_h = validate_lambda("lambda_h", lambda_h)?;
+    let lambda_a = validate_lambda("lambda_a", lambda_a)?;
+    let config = ModelConfig::from_args(Some(rho), None, Some(max_goals))?;
+    let p_yes =
+        DixonColes::get_probability_matrix(lambda_h, lambda_a, config.rho, config.max_goals)
+            .both_teams_score();
+    Ok((p_yes, 1.0 - p_yes))
+}
 
-        standings[home_idx][1] += gh;
-        standings[home_idx][2] += ga;
-        standings[home_idx][3] += 1;
-        if gh > ga {
-            standings[home_idx][0] += 3;
-        } else if gh == ga {
-            standings[home_idx][0] += 1;
-        }
+/// `(p_home_cs, p_away_cs)`: the chance each side concedes nothing.
+#[pyfunction(rho = "DEFAULT_RHO", max_goals = "MAX_GOALS")]
+fn clean_sheet_probability(
+    lambda_h: f64,
+    lambda_a: f64,
+    rho: f64,
+    max_goals: usize,
+) -> PyResult<(f64, f64)> {
+    let lambda_h = validate_lambda("lambda_h", lambda_h)?;
+    let lambda_a = validate_lambda("lambda_a", lambda_a)?;
+    let config = ModelConfig::from_args(Some(rho), None, Some(max_goals))?;
+    Ok(
+        DixonColes::get_probability_matrix(lambda_h, lambda_a, config.rho, config.max_goals)
+            .clean_sheets(),
+    )
+}
 
-        standings[away_idx][1] += ga;
-        standings[away_idx][2] += gh;
-        standings[away_idx][3] += 1;
-        if ga > gh {
-            standings[away_idx][0] += 3;
-        } else if gh == ga {
-            standings[away_idx][0] += 1;
-        }
+/// First-half and full-time outcome triples; the first half scores at `half_scale` of the match rate.
+fn halftime_outcome_probs(
+    lambda_h: f64,
+    lambda_a: f64,
+    rho: f64,
+    half_scale: f64,
+    max_goals: usize,
+) -> ((f64, f64, f64), (f64, f64, f64)) {
+    let first_half =
+        match_outcome_probs(lambda_h * half_scale, lambda_a * half_scale, rho, max_goals);
+    let full_time = match_outcome_probs(lambda_h, lambda_a, rho, max_goals);
+    (first_half, full_time)
+}
+
+#[pyfunction(
+    rho = "DEFAULT_RHO",
+    half_scale = "DEFAULT_HALF_SCALE",
+    max_goals = "MAX_GOALS"
+)]
+fn halftime_probabilities(
+    py: Python,
+    lambda_h: f64,
+    lambda_a: f64,
+    rho: f64,
+    half_scale: f64,
+    max_goals: usize,
+) -> PyResult<PyObject> {
+    let lambda_h = validate_lambda("lambda_h", lambda_h)?;
+    let lambda_a = validate_lambda("lambda_a", lambda_a)?;
+    if !(half_scale > 0.0 && half_scale <= 1.0) {
+        return Err(PyValueError::new_err(format!(
+            "half_scale must be in (0.0, 1.0], got {half_scale}"
+        )));
     }
+    let config = ModelConfig::from_args(Some(rho), None, Some(max_goals))?;
+    let (first_half, full_time) =
+        halftime_outcome_probs(lambda_h, lambda_a, config.rho, half_scale, config.max_goals);
+    let result = PyDict::new(py);
+    result.set_item("H", first_half.0)?;
+    result.set_item("D", first_half.1)?;
+    result.set_item("A", first_half.2)?;
+    result.set_item("full_H", full_time.0)?;
+    result.set_item("full_D", full_time.1)?;
+    result.set_item("full_A", full_time.2)?;
+    Ok(result.into())
+}
 
-    let mut order: Vec<usize> = (0..num_teams).collect();
-    order.sort_by(|&left, &right| {
-        let a = &standings[left];
-        let b = &standings[right];
-        b[0].cmp(&a[0])
-            .then((b[1] - b[2]).cmp(&(a[1] - a[2])))
-            .then(b[1].cmp(&a[1]))
-            .then(input.teams[left].cmp(&input.teams[right]))
-    });
+/// Outcome triples for every `(lambda_h, lambda_a)` pair; each distinct pair is computed once.
+fn bulk_outcome_probs(pairs: &[(f64, f64)], rho: f64, max_goals: usize) -> Vec<(f64, f64, f64)> {
+    let mut unique: HashMap<(u64, u64), usize> = HashMap::new();
+    let mut distinct: Vec<(f64, f64)> = Vec::new();
+    let slots: Vec<usize> = pairs
+        .iter()
+        .map(|&(lambda_h, lambda_a)| {
+            *unique
+                .entry((lambda_h.to_bits(), lambda_a.to_bits()))
+                .or_insert_with(|| {
+                    distinct.push((lambda_h, lambda_a));
+                    distinct.len() - 1
+                })
+        })
+        .collect();
+    let outcomes: Vec<(f64, f64, f64)> = distinct
+        .par_iter()
+        .map(|&(lambda_h, lambda_a)| match_outcome_probs(lambda_h, lambda_a, rho, max_goals))
+        .collect();
+    slots.into_iter().map(|slot| outcomes[slot]).collect()
+}
+
+#[pyfunction(rho = "DEFAULT_RHO", max_goals = "MAX_GOALS")]
+fn bulk_match_probabilities(
+    py: Python,
+    fixtures: PyObject,
+    rho: f64,
+    max_goals: usize,
+) -> PyResult<PyObject> {
+    let pairs: Vec<(f64, f64)> = fixtures.extract(py)?;
+    for &(lambda_h, lambda_a) in &pairs {
+        validate_lambda("lambda_h", lambda_h)?;
+        validate_lambda("lambda_a", lambda_a)?;
+    }
+    let config = ModelConfig::from_args(Some(rho), None, Some(max_goals))?;
+    let outcomes = py.allow_threads(|| bulk_outcome_probs(&pairs, config.rho, config.max_goals));
+    Ok(outcomes.into_py(py))
+}
+
+/// Expected home share of the result under the Elo logistic curve.
+fn elo_expected_share(elo_home: f64, elo_away: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((elo_away - elo_home) / 400.0))
+}
+
+/// Splits `2 * mu` expected goals by Elo share, then applies the home scoring multiplier.
+fn elo_lambdas(elo_home: f64, elo_away: f64, home_advantage_elo: f64, mu: f64) -> (f64, f64) {
+    let share = elo_expected_share(elo_home + home_advantage_elo, elo_away);
+    (mu * 2.0 * share * HOME_ADVANTAGE, mu * 2.0 * (1.0 - share))
+}
+
+/// Zero-sum Elo update: the home side gains `k * (actual - expected)`, the away side loses it.
+fn elo_update(
+    elo_home: f64,
+    elo_away: f64,
+    home_goals: i64,
+    away_goals: i64,
+    k: f64,
+) -> (f64, f64) {
+    let actual = match home_goals.cmp(&away_goals) {
+        Ordering::Greater => 1.0,
+        Ordering::Equal => 0.5,
+        Ordering::Less => 0.0,
+    };
+    let change = k * (actual - elo_expected_share(elo_home, elo_away));
+    (elo_home + change, elo_away - change)
+}
+
+fn validate_elo(name: &str, value: f64) -> PyResult<f64> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(PyValueError::new_err(format!(
+            "{name} must be finite, got {value}"
+        )))
+    }
+}
+
+/// `(lambda_h, lambda_a)` from Elo ratings; `home_advantage_elo` is added to the home rating.
+#[pyfunction(home_advantage_elo = "0.0", mu = "DEFAULT_LAMBDA")]
+fn lambdas_from_elo(
+    elo_home: f64,
+    elo_away: f64,
+    home_advantage_elo: f64,
+    mu: f64,
+) -> PyResult<(f64, f64)> {
+    validate_elo("elo_home", elo_home)?;
+    validate_elo("elo_away", elo_away)?;
+    validate_elo("home_advantage_elo", home_advantage_elo)?;
+    validate_lambda("mu", mu)?;
+    Ok(elo_lambdas(elo_home, elo_away, home_advantage_elo, mu))
+}
 
-    SeasonResult {
-        order,
-        final_stats: standings,
+/// Post-match `(elo_home, elo_away)` after one result.
+#[pyfunction(k = "DEFAULT_ELO_K")]
+fn update_elo(
+    elo_home: f64,
+    elo_away: f64,
+    home_goals: i64,
+    away_goals: i64,
+    k: f64,
+) -> PyResult<(f64, f64)> {
+    validate_elo("elo_home", elo_home)?;
+    validate_elo("elo_away", elo_away)?;
+    if home_goals < 0 || away_goals < 0 {
+        return Err(PyValueError::new_err("Goals cannot be negative"));
+    }
+    if !(k.is_finite() && k >= 0.0) {
+        return Err(PyValueError::new_err("k must be a non-negative number"));
     }
+    Ok(elo_update(elo_home, elo_away, home_goals, away_goals, k))
 }
 
+/// Drop every cached score matrix, e.g. after re-estimating team strengths.
 #[pyfunction]
-fn simulate_season(
+fn flush_probability_cache() {
+    PROBABILITY_CACHE.clear();
+}
+
+/// Number of score matrices currently cached.
+#[pyfunction]
+fn probability_cache_size() -> usize {
+    PROBABILITY_CACHE.len()
+}
+
+/// `(hits, misses)` of the score-matrix cache since load or the last `reset_cache_stats()`.
+#[pyfunction]
+fn cache_stats() -> (u64, u64) {
+    (
+        CACHE_HITS.load(AtomicOrdering::Relaxed),
+        CACHE_MISSES.load(AtomicOrdering::Relaxed),
+    )
+}
+
+#[pyfunction]
+fn reset_cache_stats() {
+    CACHE_HITS.store(0, AtomicOrdering::Relaxed);
+    CACHE_MISSES.store(0, AtomicOrdering::Relaxed);
+}
+
+/// Fills the score-matrix cache for the unplayed `fixtures`, rated from the tables exactly as
+/// `simulate_bulk` rates them with the same `rho`, `home_advantage` and `max_goals`. Returns the
+/// number of matrices added.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn warm_cache(
     py: Python,
     base_table: PyObject,
     fixtures: PyObject,
     home_table: PyObject,
     away_table: PyObject,
-    seed: Option<u64>,
-) -> PyResult<PyObject> {
-    let input = parse_simulation_input(py, base_table, fixtures, home_table, away_table)?;
-    let mut rng = match seed {
-        Some(value) => ChaCha8Rng::seed_from_u64(value),
-        None => ChaCha8Rng::from_entropy(),
-    };
-    let result = simulate_single_season(&input, &mut rng);
+    rho: Option<f64>,
+    home_advantage: Option<f64>,
+    max_goals: Option<usize>,
+) -> PyResult<usize> {
+    let config = ModelConfig::from_args(rho, home_advantage, max_goals)?;
+    let league = parse_league(py, base_table, home_table, away_table, &config)?;
+    let fixtures_list: &PyList = fixtures.extract(py)?;
+    let fixtures = parse_fixture_pairs(fixtures_list, &league.team_to_idx)?;
+    let pairs: Vec<(f64, f64)> = fixtures
+        .iter()
+        .filter(|fixture| fixture.result.is_none())
+        .map(|fixture| league.ratings.lambdas(fixture))
+        .collect();
+    Ok(py.allow_threads(|| DixonColes::warm_cache(pairs, config.rho, config.max_goals)))
+}
+
+/// `warm_cache` with the matrices computed in parallel, on `n_threads` threads as in
+/// `simulate_bulk`.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn warm_cache_parallel(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    home_table: PyObject,
+    away_table: PyObject,
+    rho: Option<f64>,
+    home_advantage: Option<f64>,
+    max_goals: Option<usize>,
+    n_threads: Option<usize>,
+) -> PyResult<usize> {
+    let config = ModelConfig::from_args(rho, home_advantage, max_goals)?;
+    let league = parse_league(py, base_table, home_table, away_table, &config)?;
+    let fixtures_list: &PyList = fixtures.extract(py)?;
+    let fixtures = parse_fixture_pairs(fixtures_list, &league.team_to_idx)?;
+    let pairs: Vec<(f64, f64)> = fixtures
+        .iter()
+        .filter(|fixture| fixture.result.is_none())
+        .map(|fixture| league.ratings.lambdas(fixture))
+        .collect();
+    py.allow_threads(|| {
+        with_thread_count(n_threads, || {
+            DixonColes::warm_cache_parallel(pairs, config.rho, config.max_goals)
+        })
+    })
+    .map_err(PyRuntimeError::new_err)
+}
 
+/// Final table as `(team, {"PTS", "GF", "GA", "M", "W", "D", "L"})` rows in finishing order.
+fn standings_list<'py>(
+    py: Python<'py>,
+    input: &SimulationInput,
+    result: &SeasonResult,
+    expected_points: Option<&[f64]>,
+) -> PyResult<&'py PyList> {
     let standings = PyList::empty(py);
     for &team_idx in &result.order {
         let stats = &result.final_stats[team_idx];
         let team = &input.teams[team_idx];
         let dict = PyDict::new(py);
-        dict.set_item("PTS", stats[0])?;
-        dict.set_item("GF", stats[1])?;
-        dict.set_item("GA", stats[2])?;
-        dict.set_item("M", stats[3])?;
+        dict.set_item("PTS", stats.points)?;
+        dict.set_item("GF", stats.goals_for)?;
+        dict.set_item("GA", stats.goals_against)?;
+        dict.set_item("M", stats.matches)?;
+        dict.set_item("W", stats.wins)?;
+        dict.set_item("D", stats.draws)?;
+        dict.set_item("L", stats.losses)?;
+        if let Some(expected_points) = expected_points {
+            dict.set_item("xPts", expected_points[team_idx])?;
+        }
         standings.append((team, dict))?;
     }
-    Ok(standings.into())
+    Ok(standings)
 }
 
-#[pyfunction]
-fn simulate_bulk(
+/// `(home_goals, away_goals)` -> how often that score came up among `results`.
+fn scoreline_frequencies(results: &[MatchResult]) -> HashMap<(i64, i64), u64> {
+    let mut counts = HashMap::new();
+    for result in results {
+        *counts
+            .entry((result.home_goals, result.away_goals))
+            .or_insert(0) += 1;
+    }
+    counts
+}
+
+#[pyfunction(
+    return_fixtures = false,
+    return_xpts = false,
+    fixture_dates = "None",
+    fatigue_window = "DEFAULT_FATIGUE_WINDOW",
+    fatigue_factor = "DEFAULT_FATIGUE_FACTOR",
+    red_card_rate = "DEFAULT_RED_CARD_RATE",
+    red_card_penalty = "DEFAULT_RED_CARD_PENALTY",
+    return_scorelines = false
+)]
+#[allow(clippy::too_many_arguments)]
+fn simulate_season(
     py: Python,
     base_table: PyObject,
     fixtures: PyObject,
     home_table: PyObject,
     away_table: PyObject,
-    n_sims: usize,
     seed: Option<u64>,
-    top_k_tables: usize,
+    tiebreaker_stages: Option<&PyList>,
+    rho: Option<f64>,
+    home_advantage: Option<f64>,
+    max_goals: Option<usize>,
+    return_fixtures: bool,
+    deductions: Option<HashMap<String, i64>>,
+    model: Option<&str>,
+    dispersion: Option<f64>,
+    lambda_3: Option<f64>,
+    return_xpts: bool,
+    fixture_dates: Option<PyObject>,
+    fatigue_window: usize,
+    fatigue_factor: f64,
+    red_card_rate: f64,
+    red_card_penalty: f64,
+    return_scorelines: bool,
 ) -> PyResult<PyObject> {
-    let input = parse_simulation_input(py, base_table, fixtures, home_table, away_table)?;
-    let num_teams = input.teams.len();
-    let base_seed = seed.unwrap_or(42);
+    let mut config = ModelConfig::from_args(rho, home_advantage, max_goals)?;
+    config.score_model = ScoreModel::from_args(model, dispersion, lambda_3)?;
+    config.red_cards =
+        RedCards::validated(red_card_rate, red_card_penalty).map_err(PyValueError::new_err)?;
+    let mut input = match fixture_dates {
+        None => parse_simulation_input(py, base_table, fixtures, home_table, away_table, &config)?,
+        Some(dates) => {
+            let dates: Vec<i64> = dates.extract(py)?;
+            if !(fatigue_factor.is_finite() && fatigue_factor > 0.0) {
+                return Err(PyValueError::new_err(format!(
+                    "fatigue_factor must be positive, got {fatigue_factor}"
+                )));
+            }
+            let fatigue = Fatigue {
+                window: fatigue_window,
+                factor: fatigue_factor,
+            };
+            let league = parse_league(py, base_table, home_table, away_table, &config)?;
+            let fixtures_list: &PyList = fixtures.extract(py)?;
+            let fixtures = parse_fixture_pairs(fixtures_list, &league.team_to_idx)?;
+            league
+                .fatigued_simulation_input(&fixtures, &dates, &fatigue, &config)
+                .map_err(PyValueError::new_err)?
+        }
+    };
+    if let Some(deductions) = &deductions {
+        input
+            .apply_deductions(deductions)
+            .map_err(PyValueError::new_err)?;
+    }
+    if let Some(stages) = tiebreaker_stages {
+        input.tiebreakers = parse_tiebreaker_stages(stages)?;
+    }
+    let mut rng = season_rng(seed);
+    let result = simulate_season_recording(&input, &mut rng, return_fixtures || return_scorelines);
 
-    let (counts, tables) = (0..n_sims)
-        .into_par_iter()
-        .map(|sim_index| {
-            let mut rng = ChaCha8Rng::seed_from_u64(base_seed.wrapping_add(sim_index as u64));
-            let result = simulate_single_season(&input, &mut rng);
-            let table_key: Vec<usize> = result.order.clone();
-            (result.order, table_key)
-        })
-        .fold(
-            || {
-                (
-                    vec![vec![0u64; num_teams]; num_teams],
-                    HashMap::<Vec<usize>, u64>::new(),
-                )
-            },
-            |(mut acc_counts, mut acc_tables), (order, table_key)| {
-                for (pos_idx, &team_idx) in order.iter().enumerate() {
-                    acc_counts[team_idx][pos_idx] += 1;
-                }
-                *acc_tables.entry(table_key).or_insert(0) += 1;
-                if top_k_tables > 0 && acc_tables.len() > top_k_tables * 4 {
-                    let mut tables: Vec<(Vec<usize>, u64)> = acc_tables.into_iter().collect();
-                    tables.sort_by(|a, b| b.1.cmp(&a.1));
-                    tables.truncate(top_k_tables * 2);
-                    acc_tables = tables.into_iter().collect();
-                }
-                (acc_counts, acc_tables)
-            },
-        )
-        .reduce(
-            || {
-                (
-                    vec![vec![0u64; num_teams]; num_teams],
-                    HashMap::<Vec<usize>, u64>::new(),
-                )
-            },
-            |(mut left_counts, mut left_tables), (right_counts, right_tables)| {
-                for team_idx in 0..num_teams {
-                    for pos_idx in 0..num_teams {
-                        left_counts[team_idx][pos_idx] += right_counts[team_idx][pos_idx];
-                    }
-                }
-                for (table, count) in right_tables {
-                    *left_tables.entry(table).or_insert(0) += count;
-                }
-                if top_k_tables > 0 && left_tables.len() > top_k_tables * 4 {
-                    let mut tables: Vec<(Vec<usize>, u64)> = left_tables.into_iter().collect();
-                    tables.sort_by(|a, b| b.1.cmp(&a.1));
-                    tables.truncate(top_k_tables * 2);
-                    left_tables = tables.into_iter().collect();
-                }
-                (left_counts, left_tables)
-            },
-        );
+    let expected_points = return_xpts.then(|| input.expected_points());
+    let standings = standings_list(py, &input, &result, expected_points.as_deref())?;
+    if !return_fixtures && !return_scorelines {
+        return Ok(standings.into());
+    }
 
-    let result = PyDict::new(py);
-    let position_counts = PyDict::new(py);
-    for (team_idx, team_name) in input.teams.iter().enumerate() {
-        let inner = PyDict::new(py);
-        for (pos_idx, &count) in counts[team_idx].iter().enumerate() {
-            inner.set_item(pos_idx + 1, count)?;
+    let mut items: Vec<PyObject> = vec![standings.into()];
+    if return_fixtures {
+        let fixture_results = PyList::empty(py);
+        for result in &result.results {
+            let dict = PyDict::new(py);
+            dict.set_item("home", &input.teams[result.home_idx])?;
+            dict.set_item("away", &input.teams[result.away_idx])?;
+            dict.set_item("home_goals", result.home_goals)?;
+            dict.set_item("away_goals", result.away_goals)?;
+            fixture_results.append(dict)?;
         }
-        position_counts.set_item(team_name, inner)?;
+        items.push(fixture_results.into());
     }
+    if return_scorelines {
+        let scoreline_freq = PyDict::new(py);
+        for ((home_goals, away_goals), count) in scoreline_frequencies(&result.results) {
+            scoreline_freq.set_item(format!("{home_goals}-{away_goals}"), count)?;
+        }
+        let extras = PyDict::new(py);
+        extras.set_item("scoreline_freq", scoreline_freq)?;
+        items.push(extras.into());
+    }
+    Ok(PyTuple::new(py, items).into())
+}
 
-    let mut top_tables: Vec<(Vec<usize>, u64)> = tables.into_iter().collect();
-    top_tables.sort_by(|a, b| b.1.cmp(&a.1));
-    if top_k_tables > 0 {
-        top_tables.truncate(top_k_tables);
+/// Like `simulate_season`, but each team's scoring and conceding rates come from its recent form.
+#[pyfunction(form_window = "DEFAULT_FORM_WINDOW", form_decay = "DEFAULT_FORM_DECAY")]
+#[allow(clippy::too_many_arguments)]
+fn simulate_season_form(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    home_table: PyObject,
+    away_table: PyObject,
+    recent_fixtures: PyObject,
+    form_window: usize,
+    form_decay: f64,
+    seed: Option<u64>,
+    rho: Option<f64>,
+    home_advantage: Option<f64>,
+    max_goals: Option<usize>,
+) -> PyResult<PyObject> {
+    if form_window == 0 {
+        return Err(PyValueError::new_err("form_window must be at least 1"));
     }
-    let top_tables_py = PyList::empty(py);
-    for (table, count) in top_tables {
-        let entry = PyDict::new(py);
-        let ordered_names: Vec<&String> = table.iter().map(|&idx| &input.teams[idx]).collect();
-        entry.set_item("table", ordered_names)?;
-        entry.set_item("count", count)?;
-        top_tables_py.append(entry)?;
+    if !(form_decay > 0.0 && form_decay <= 1.0) {
+        return Err(PyValueError::new_err("form_decay must lie in (0, 1]"));
     }
+    let config = ModelConfig::from_args(rho, home_advantage, max_goals)?;
+    let mut league = parse_league(py, base_table, home_table, away_table, &config)?;
+    let recent_list: &PyList = recent_fixtures.extract(py)?;
+    let recent = parse_played_results(recent_list, &league.team_to_idx)?;
+    let form_rates = form_scoring_rates(&recent, league.teams.len(), form_window, form_decay);
+    league.ratings.apply_form(&form_rates);
+    let input = league.into_input(py, fixtures, &config)?;
 
-    result.set_item("position_counts", position_counts)?;
-    result.set_item("top_tables", top_tables_py)?;
+    let mut rng = season_rng(seed);
+    let result = simulate_single_season(&input, &mut rng);
+    Ok(standings_list(py, &input, &result, None)?.into())
+}
+
+/// `(attack, defense)` per team from a `{team: {"attack", "defense"}}` dict; unlisted teams are average.
+fn parse_team_strengths(
+    team_params: &PyDict,
+    team_to_idx: &TeamIndex,
+) -> PyResult<Vec<(f64, f64)>> {
+    let mut strengths = vec![(1.0, 1.0); team_to_idx.len()];
+    for (team, params) in team_params.iter() {
+        let team: String = team.extract()?;
+        let idx = *team_to_idx
+            .get(&team)
+            .ok_or_else(|| PyValueError::new_err(format!("Team {team} not found in standings")))?;
+        let params: &PyDict = params
+            .downcast()
+            .map_err(|_| PyValueError::new_err(format!("Parameters for {team} must be a dict")))?;
+        let strength = |key: &str| -> PyResult<f64> {
+            let value: f64 = params
+                .get_item(key)
+                .ok_or_else(|| {
+                    PyValueError::new_err(format!("Parameters for {team} missing '{key}'"))
+                })?
+                .extract()?;
+            if !value.is_finite() || value < 0.0 {
+                return Err(PyValueError::new_err(format!(
+                    "{team} {key} must be a non-negative number"
+                )));
+            }
+            Ok(value)
+        };
+        strengths[idx] = (strength("attack")?, strength("defense")?);
+    }
+    Ok(strengths)
+}
+
+/// Like `simulate_season`, but a `zero_inflation` share of every fixture's mass sits on 0-0.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn simulate_season_zip(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    home_table: PyObject,
+    away_table: PyObject,
+    zero_inflation: f64,
+    seed: Option<u64>,
+    rho: Option<f64>,
+    home_advantage: Option<f64>,
+    max_goals: Option<usize>,
+) -> PyResult<PyObject> {
+    if !(0.0..1.0).contains(&zero_inflation) {
+        return Err(PyValueError::new_err(format!(
+            "zero_inflation must be in [0.0, 1.0), got {zero_inflation}"
+        )));
+    }
+    let mut config = ModelConfig::from_args(rho, home_advantage, max_goals)?;
+    config.score_model = ScoreModel::ZeroInflated { zero_inflation };
+    let input = parse_simulation_input(py, base_table, fixtures, home_table, away_table, &config)?;
+
+    let mut rng = season_rng(seed);
+    let result = simulate_single_season(&input, &mut rng);
+    Ok(standings_list(py, &input, &result, None)?.into())
+}
+
+/// Like `simulate_season`, for a league that splits into a top `top_n` and a bottom half after
+/// the first `split_after` fixtures; any fixtures listed past that point are replaced by the
+/// post-split round in which each team meets every other team of its half once more.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn simulate_season_split(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    home_table: PyObject,
+    away_table: PyObject,
+    split_after: usize,
+    top_n: usize,
+    seed: Option<u64>,
+    rho: Option<f64>,
+    home_advantage: Option<f64>,
+    max_goals: Option<usize>,
+) -> PyResult<PyObject> {
+    let config = ModelConfig::from_args(rho, home_advantage, max_goals)?;
+    let league = parse_league(py, base_table, home_table, away_table, &config)?;
+    if top_n == 0 || top_n >= league.teams.len() {
+        return Err(PyValueError::new_err(format!(
+            "top_n must be between 1 and {}, got {top_n}",
+            league.teams.len().saturating_sub(1)
+        )));
+    }
+    let fixtures_list: &PyList = fixtures.extract(py)?;
+    let parsed = parse_fixture_pairs(fixtures_list, &league.team_to_idx)?;
+    if split_after > parsed.len() {
+        return Err(PyValueError::new_err(format!(
+            "split_after is {split_after} but only {} fixtures were given",
+            parsed.len()
+        )));
+    }
+    let ratings = league.ratings.clone();
+    let input = league.simulation_input(&parsed[..split_after], &config);
+
+    let mut rng = season_rng(seed);
+    let result = simulate_split_season(&input, &ratings, &config, top_n, &mut rng);
+    Ok(standings_list(py, &input, &result, None)?.into())
+}
+
+/// Simulates `n_years` seasons of a two-division pyramid with promotion and relegation and
+/// counts, per team, how many of those seasons it spends in the top division.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn simulate_multi_season(
+    py: Python,
+    top_league: PyObject,
+    lower_league: PyObject,
+    top_fixtures: PyObject,
+    lower_fixtures: PyObject,
+    relegation_spots: usize,
+    promotion_spots: usize,
+    n_years: usize,
+    n_sims: usize,
+    seed: Option<u64>,
+    rho: Option<f64>,
+    home_advantage: Option<f64>,
+    max_goals: Option<usize>,
+) -> PyResult<PyObject> {
+    if n_years == 0 {
+        return Err(PyValueError::new_err("n_years must be at least 1"));
+    }
+    // Only overall tables are given, so home advantage cannot be estimated from venue splits.
+    let config = ModelConfig::from_args(
+        rho,
+        Some(home_advantage.unwrap_or(HOME_ADVANTAGE)),
+        max_goals,
+    )?;
+    let division =
+        |table: PyObject, fixtures: PyObject| -> PyResult<(League, Vec<ParsedFixture>)> {
+            let base: &PyList = table.extract(py)?;
+            let (teams, stats, _) = parse_base_table(base)?;
+            let rows: Vec<(String, Stats)> = teams.into_iter().zip(stats).collect();
+            let league =
+                League::new(rows.clone(), &rows, &rows, &config).map_err(PyValueError::new_err)?;
+            let fixtures_list: &PyList = fixtures.extract(py)?;
+            let fixtures = parse_fixture_pairs(fixtures_list, &league.team_to_idx)?;
+            Ok((league, fixtures))
+        };
+    let (top, top_fixtures) = division(top_league, top_fixtures)?;
+    let (lower, lower_fixtures) = division(lower_league, lower_fixtures)?;
+    let pyramid = Pyramid::new(
+        [(top, &top_fixtures), (lower, &lower_fixtures)],
+        &config,
+        relegation_spots,
+        promotion_spots,
+        n_years,
+    )
+    .map_err(PyValueError::new_err)?;
+
+    let base_seed = seed.unwrap_or_else(rand::random);
+    let counts = py.allow_threads(|| simulate_pyramid(&pyramid, n_years, n_sims, base_seed));
+    let result = PyDict::new(py);
+    for (team, counts) in pyramid.teams.iter().zip(counts) {
+        let seasons = PyDict::new(py);
+        for (years, count) in counts.into_iter().enumerate() {
+            seasons.set_item(years, count)?;
+        }
+        result.set_item(team, seasons)?;
+    }
     Ok(result.into())
 }
 
-#[pymodule]
-fn league_outcome_simulator_rust(_py: Python, m: &PyModule) -> PyResult<()> {
-    INIT_RAYON.call_once(|| {
-        let _ = ThreadPoolBuilder::new()
-            .num_threads(num_cpus::get())
-            .build_global();
+/// Like `simulate_season`, but lambdas come from explicit Dixon-Coles attack/defense strengths.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn simulate_season_dc(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    team_params: PyObject,
+    seed: Option<u64>,
+    rho: Option<f64>,
+    home_advantage: Option<f64>,
+    max_goals: Option<usize>,
+) -> PyResult<PyObject> {
+    let config = ModelConfig::from_args(rho, home_advantage, max_goals)?;
+    let base: &PyList = base_table.extract(py)?;
+    let team_params: &PyDict = team_params.extract(py)?;
+    let (teams, initial_stats, team_to_idx) = parse_base_table(base)?;
+    let strengths = parse_team_strengths(team_params, &team_to_idx)?;
+
+    let avg_league_goals = league_goal_rate(&initial_stats);
+    let league = League {
+        teams,
+        initial_stats,
+        team_to_idx,
+        ratings: LeagueRatings::from_strengths(
+            avg_league_goals,
+            config.home_advantage.unwrap_or(HOME_ADVANTAGE),
+            &strengths,
+        ),
+    };
+    let input = league.into_input(py, fixtures, &config)?;
+
+    let mut rng = season_rng(seed);
+    let result = simulate_single_season(&input, &mut rng);
+    Ok(standings_list(py, &input, &result, None)?.into())
+}
+
+/// Maximum-likelihood Dixon-Coles parameters; attack and defense have geometric mean 1.
+struct DixonColesFit {
+    attack: Vec<f64>,
+    defense: Vec<f64>,
+    mu: f64,
+    home_advantage: f64,
+    rho: f64,
+    converged: bool,
+}
+
+/// A distinct `(home, away, home_goals, away_goals)` result with its summed weight.
+type Observation = ((usize, usize, i64, i64), f64);
+
+fn weighted_observations(results: &[MatchResult], weights: &[f64]) -> Vec<Observation> {
+    let mut totals: HashMap<(usize, usize, i64, i64), f64> = HashMap::new();
+    for (result, &weight) in results.iter().zip(weights) {
+        let key = (
+            result.home_idx,
+            result.away_idx,
+            result.home_goals,
+            result.away_goals,
+        );
+        *totals.entry(key).or_insert(0.0) += weight;
+    }
+    let mut observations: Vec<Observation> = totals.into_iter().collect();
+    observations.sort_by_key(|&(key, _)| key);
+    observations
+}
+
+/// Mean log-likelihood per unit weight. `params` holds log attack, log defense, log mu,
+/// log home advantage and rho, in that order.
+fn dixon_coles_log_likelihood(
+    params: &[f64],
+    observations: &[Observation],
+    num_teams: usize,
+) -> f64 {
+    let rho = params[2 * num_teams + 2];
+    if rho.abs() >= 1.0 {
+        return f64::NEG_INFINITY;
+    }
+    let log_mu = params[2 * num_teams];
+    let log_home = params[2 * num_teams + 1];
+    let (mut total, mut total_weight) = (0.0, 0.0);
+    for &((home, away, home_goals, away_goals), weight) in observations {
+        let lambda_h = (log_mu + log_home + params[home] + params[num_teams + away]).exp();
+        let lambda_a = (log_mu + params[away] + params[num_teams + home]).exp();
+        let probability =
+            DixonColes::result_probability(home_goals, away_goals, lambda_h, lambda_a, rho);
+        if probability <= 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        total += weight * probability.ln();
+        total_weight += weight;
+    }
+    total / total_weight
+}
+
+/// Re-centres log attack and log defense on zero, folding the offsets into log mu.
+fn normalize_strengths(params: &mut [f64], num_teams: usize) {
+    for block in 0..2 {
+        let range = block * num_teams..(block + 1) * num_teams;
+        let mean = params[range.clone()].iter().sum::<f64>() / num_teams as f64;
+        params[range].iter_mut().for_each(|value| *value -= mean);
+        params[2 * num_teams] += mean;
+    }
+}
+
+/// Gradient ascent on the Dixon-Coles likelihood with forward-difference gradients and a
+/// backtracking step. Converges once an accepted step improves the mean log-likelihood by
+/// less than `tol` or no improving step remains.
+fn fit_dixon_coles_params(
+    results: &[MatchResult],
+    weights: &[f64],
+    num_teams: usize,
+    rho_init: f64,
+    max_iter: usize,
+    tol: f64,
+) -> DixonColesFit {
+    const GRADIENT_STEP: f64 = 1e-6;
+    const MIN_STEP: f64 = 1e-12;
+
+    let observations = weighted_observations(results, weights);
+    let total_weight: f64 = observations.iter().map(|(_, weight)| weight).sum();
+    let goals: f64 = observations
+        .iter()
+        .map(|&((_, _, home_goals, away_goals), weight)| weight * (home_goals + away_goals) as f64)
+        .sum();
+    let mean_goals = (goals / (2.0 * total_weight)).max(0.1);
+
+    let mut params = vec![0.0; 2 * num_teams + 3];
+    params[2 * num_teams] = mean_goals.ln();
+    params[2 * num_teams + 2] = rho_init;
+    let mut likelihood = dixon_coles_log_likelihood(&params, &observations, num_teams);
+    let mut step = 1.0;
+    let mut converged = false;
+
+    for _ in 0..max_iter {
+        let gradient: Vec<f64> = (0..params.len())
+            .map(|idx| {
+                let mut shifted = params.clone();
+                shifted[idx] += GRADIENT_STEP;
+                (dixon_coles_log_likelihood(&shifted, &observations, num_teams) - likelihood)
+                    / GRADIENT_STEP
+            })
+            .collect();
+
+        let accepted = loop {
+            let mut candidate: Vec<f64> = params
+                .iter()
+                .zip(&gradient)
+                .map(|(value, slope)| value + step * slope)
+                .collect();
+            normalize_strengths(&mut candidate, num_teams);
+            let candidate_likelihood =
+                dixon_coles_log_likelihood(&candidate, &observations, num_teams);
+            if candidate_likelihood > likelihood {
+                break Some((candidate, candidate_likelihood));
+            }
+            step *= 0.5;
+            if step < MIN_STEP {
+                break None;
+            }
+        };
+        let Some((candidate, candidate_likelihood)) = accepted else {
+            converged = true;
+            break;
+        };
+        let improvement = candidate_likelihood - likelihood;
+        params = candidate;
+        likelihood = candidate_likelihood;
+        if improvement < tol {
+            converged = true;
+            break;
+        }
+        step *= 2.0;
+    }
+
+    DixonColesFit {
+        attack: params[..num_teams]
+            .iter()
+            .map(|value| value.exp())
+            .collect(),
+        defense: params[num_teams..2 * num_teams]
+            .iter()
+            .map(|value| value.exp())
+            .collect(),
+        mu: params[2 * num_teams].exp(),
+        home_advantage: params[2 * num_teams + 1].exp(),
+        rho: params[2 * num_teams + 2],
+        converged,
+    }
+}
+
+/// Per-match likelihood weights `exp(-time_decay * days_ago)`, so recent results count most.
+fn time_decay_weights(days_ago: &[i64], time_decay: f64) -> Vec<f64> {
+    days_ago
+        .iter()
+        .map(|&days| (-time_decay * days as f64).exp())
+        .collect()
+}
+
+/// Team names in order of first appearance across `{"home", "away", ...}` result dicts.
+fn index_result_teams(results: &PyList) -> PyResult<(Vec<String>, TeamIndex)> {
+    let mut teams = Vec::new();
+    let mut team_to_idx = TeamIndex::new();
+    for item in results.iter() {
+        let dict: &PyDict = item.extract()?;
+        for key in ["home", "away"] {
+            let name: String = dict
+                .get_item(key)
+                .ok_or_else(|| PyValueError::new_err(format!("Result missing '{key}'")))?
+                .extract()?;
+            if !team_to_idx.contains_key(&name) {
+                team_to_idx.insert(name.clone(), teams.len());
+                teams.push(name);
+            }
+        }
+    }
+    Ok((teams, team_to_idx))
+}
+
+#[pyfunction(
+    rho_init = "DEFAULT_RHO",
+    max_iter = "DEFAULT_FIT_ITERATIONS",
+    tol = "DEFAULT_FIT_TOLERANCE",
+    time_decay = "0.0"
+)]
+fn fit_dixon_coles(
+    py: Python,
+    results: PyObject,
+    rho_init: f64,
+    max_iter: usize,
+    tol: f64,
+    time_decay: f64,
+    dates: Option<Vec<i64>>,
+) -> PyResult<PyObject> {
+    if !(rho_init > -1.0 && rho_init < 1.0) {
+        return Err(PyValueError::new_err("rho_init must lie in (-1, 1)"));
+    }
+    if !(tol.is_finite() && tol > 0.0) {
+        return Err(PyValueError::new_err("tol must be a positive number"));
+    }
+    if !(time_decay.is_finite() && time_decay >= 0.0) {
+        return Err(PyValueError::new_err(
+            "time_decay must be a non-negative number",
+        ));
+    }
+    let results_list: &PyList = results.extract(py)?;
+    if results_list.is_empty() {
+        return Err(PyValueError::new_err("results must not be empty"));
+    }
+    let (teams, team_to_idx) = index_result_teams(results_list)?;
+    let played = parse_played_results(results_list, &team_to_idx)?;
+    let weights = match dates {
+        Some(days_ago) => {
+            if days_ago.len() != played.len() {
+                return Err(PyValueError::new_err(format!(
+                    "dates has {} entries but results has {}",
+                    days_ago.len(),
+                    played.len()
+                )));
+            }
+            if days_ago.iter().any(|&days| days < 0) {
+                return Err(PyValueError::new_err("dates must be non-negative days ago"));
+            }
+            time_decay_weights(&days_ago, time_decay)
+        }
+        None if time_decay > 0.0 => {
+            return Err(PyValueError::new_err("time_decay requires dates"));
+        }
+        None => vec![1.0; played.len()],
+    };
+    let fit = py.allow_threads(|| {
+        fit_dixon_coles_params(&played, &weights, teams.len(), rho_init, max_iter, tol)
     });
 
-    m.add_function(wrap_pyfunction!(simulate_season, m)?)?;
-    m.add_function(wrap_pyfunction!(simulate_bulk, m)?)?;
-    Ok(())
+    let attack = PyDict::new(py);
+    let defense = PyDict::new(py);
+    for (idx, team) in teams.iter().enumerate() {
+        attack.set_item(team, fit.attack[idx])?;
+        defense.set_item(team, fit.defense[idx])?;
+    }
+    let output = PyDict::new(py);
+    output.set_item("attack", attack)?;
+    output.set_item("defense", defense)?;
+    output.set_item("rho", fit.rho)?;
+    output.set_item("mu", fit.mu)?;
+    output.set_item("home_advantage", fit.home_advantage)?;
+    output.set_item("converged", fit.converged)?;
+    Ok(output.into())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Poisson regression estimates; attack and defense have geometric mean 1.
+#[derive(Debug)]
+struct PoissonFit {
+    attack: Vec<f64>,
+    defense: Vec<f64>,
+    mu: f64,
+    home_advantage: f64,
+}
 
-    #[test]
-    fn dixon_coles_match_returns_reasonable_scores() {
-        let mut rng = ChaCha8Rng::seed_from_u64(123);
-        let pd = DixonColes::get_probability_matrix(1.4, 0.9, DEFAULT_RHO, MAX_GOALS);
-        let (home, away) = DixonColes::simulate_from_distribution(&mut rng, &pd);
-        assert!(home >= 0);
-        assert!(away >= 0);
-        assert!(home <= MAX_GOALS as i64);
-        assert!(away <= MAX_GOALS as i64);
+/// Solves `matrix * x = rhs` in place by Gaussian elimination with partial pivoting.
+fn solve_linear_system(mut matrix: Vec<Vec<f64>>, mut rhs: Vec<f64>) -> Result<Vec<f64>, String> {
+    let n = rhs.len();
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&a, &b| matrix[a][col].abs().total_cmp(&matrix[b][col].abs()))
+            .unwrap_or(col);
+        if matrix[pivot][col].abs() < 1e-12 {
+            return Err("design matrix is singular; every team needs results".to_string());
+        }
+        matrix.swap(col, pivot);
+        rhs.swap(col, pivot);
+        for row in col + 1..n {
+            let factor = matrix[row][col] / matrix[col][col];
+            if factor != 0.0 {
+                let (upper, lower) = matrix.split_at_mut(row);
+                for (value, pivot_value) in lower[0][col..].iter_mut().zip(&upper[col][col..]) {
+                    *value -= factor * pivot_value;
+                }
+                rhs[row] -= factor * rhs[col];
+            }
+        }
+    }
+    let mut solution = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = (row + 1..n)
+            .map(|col| matrix[row][col] * solution[col])
+            .sum();
+        solution[row] = (rhs[row] - tail) / matrix[row][row];
+    }
+    Ok(solution)
+}
+
+/// Fits `log lambda = log mu + [home] log home_advantage + attack + defense` to every side's
+/// goals by iteratively reweighted least squares. The first team's attack and defense are
+/// pinned to zero while fitting and everything is re-centred afterwards.
+fn fit_poisson_irls(results: &[MatchResult], num_teams: usize) -> Result<PoissonFit, String> {
+    const MAX_ITERATIONS: usize = 100;
+    const TOLERANCE: f64 = 1e-10;
+    if num_teams < 2 {
+        return Err("results must involve at least two teams".to_string());
+    }
+    // Columns: attack 1..n, defense 1..n, log mu, log home advantage.
+    let dim = 2 * num_teams;
+    let columns = |scorer: usize, opponent: usize, at_home: bool| {
+        let mut columns = vec![dim - 2];
+        if scorer > 0 {
+            columns.push(scorer - 1);
+        }
+        if opponent > 0 {
+            columns.push(num_teams - 1 + opponent - 1);
+        }
+        if at_home {
+            columns.push(dim - 1);
+        }
+        columns
+    };
+    let rows: Vec<(Vec<usize>, f64)> = results
+        .iter()
+        .flat_map(|result| {
+            [
+                (
+                    columns(result.home_idx, result.away_idx, true),
+                    result.home_goals as f64,
+                ),
+                (
+                    columns(result.away_idx, result.home_idx, false),
+                    result.away_goals as f64,
+                ),
+            ]
+        })
+        .collect();
+    let mean_goals = rows.iter().map(|(_, goals)| goals).sum::<f64>() / rows.len() as f64;
+
+    let mut coefficients = vec![0.0; dim];
+    coefficients[dim - 2] = mean_goals.max(0.1).ln();
+    let mut converged = false;
+    for _ in 0..MAX_ITERATIONS {
+        // Newton step for the log link: (X'WX) delta = X'(y - lambda) with W = lambda.
+        let mut information = vec![vec![0.0; dim]; dim];
+        let mut score = vec![0.0; dim];
+        for (columns, goals) in &rows {
+            let lambda = columns
+                .iter()
+                .map(|&col| coefficients[col])
+                .sum::<f64>()
+                .exp();
+            for &row in columns {
+                score[row] += goals - lambda;
+                for &col in columns {
+                    information[row][col] += lambda;
+                }
+            }
+        }
+        let step = solve_linear_system(information, score)?;
+        coefficients
+            .iter_mut()
+            .zip(&step)
+            .for_each(|(value, delta)| *value += delta);
+        if step.iter().all(|delta| delta.abs() < TOLERANCE) {
+            converged = true;
+            break;
+        }
+    }
+    if !converged || coefficients.iter().any(|value| !value.is_finite()) {
+        return Err("Poisson regression did not converge; every team needs goals".to_string());
+    }
+
+    let mut params = vec![0.0; 2 * num_teams + 2];
+    params[1..num_teams].copy_from_slice(&coefficients[..num_teams - 1]);
+    params[num_teams + 1..2 * num_teams].copy_from_slice(&coefficients[num_teams - 1..dim - 2]);
+    params[2 * num_teams] = coefficients[dim - 2];
+    params[2 * num_teams + 1] = coefficients[dim - 1];
+    normalize_strengths(&mut params, num_teams);
+    Ok(PoissonFit {
+        attack: params[..num_teams]
+            .iter()
+            .map(|value| value.exp())
+            .collect(),
+        defense: params[num_teams..2 * num_teams]
+            .iter()
+            .map(|value| value.exp())
+            .collect(),
+        mu: params[2 * num_teams].exp(),
+        home_advantage: params[2 * num_teams + 1].exp(),
+    })
+}
+
+/// Poisson regression of `{"home", "away", "home_goals", "away_goals"}` results on team attack
+/// and defense dummies, fitted by IRLS. Each team maps to `{"attack", "defense"}`, alongside
+/// `"mu"` and `"home_advantage"` entries.
+#[pyfunction]
+fn estimate_lambdas_poisson(py: Python, results: PyObject) -> PyResult<PyObject> {
+    let results_list: &PyList = results.extract(py)?;
+    if results_list.is_empty() {
+        return Err(PyValueError::new_err("results must not be empty"));
+    }
+    let (teams, team_to_idx) = index_result_teams(results_list)?;
+    if let Some(team) = teams
+        .iter()
+        .find(|team| ["mu", "home_advantage"].contains(&team.as_str()))
+    {
+        return Err(PyValueError::new_err(format!(
+            "Team name {team:?} clashes with an output key"
+        )));
+    }
+    let played = parse_played_results(results_list, &team_to_idx)?;
+    let fit = py
+        .allow_threads(|| fit_poisson_irls(&played, teams.len()))
+        .map_err(PyValueError::new_err)?;
+
+    let output = PyDict::new(py);
+    for (idx, team) in teams.iter().enumerate() {
+        let strengths = PyDict::new(py);
+        strengths.set_item("attack", fit.attack[idx])?;
+        strengths.set_item("defense", fit.defense[idx])?;
+        output.set_item(team, strengths)?;
+    }
+    output.set_item("mu", fit.mu)?;
+    output.set_item("home_advantage", fit.home_advantage)?;
+    Ok(output.into())
+}
+
+/// `(p_home, p_draw, p_away)` forecasts paired with the index of the observed outcome.
+type ScoredForecast = ([f64; 3], usize);
+
+fn parse_forecasts(
+    predictions: Vec<(f64, f64, f64)>,
+    outcomes: Vec<String>,
+) -> PyResult<Vec<ScoredForecast>> {
+    if predictions.len() != outcomes.len() {
+        return Err(PyValueError::new_err(format!(
+            "predictions has {} entries but outcomes has {}",
+            predictions.len(),
+            outcomes.len()
+        )));
+    }
+    if predictions.is_empty() {
+        return Err(PyValueError::new_err("predictions must not be empty"));
+    }
+    predictions
+        .into_iter()
+        .zip(outcomes)
+        .map(|((home, draw, away), outcome)| {
+            let probabilities = [home, draw, away];
+            if probabilities
+                .iter()
+                .any(|p| !p.is_finite() || !(0.0..=1.0).contains(p))
+            {
+                return Err(PyValueError::new_err(format!(
+                    "Probabilities must lie in [0, 1], got {probabilities:?}"
+                )));
+            }
+            let actual = match outcome.as_str() {
+                "H" => 0,
+                "D" => 1,
+                "A" => 2,
+                other => {
+                    return Err(PyValueError::new_err(format!(
+                        "Outcome must be \"H\", \"D\" or \"A\", got {other:?}"
+                    )))
+                }
+            };
+            Ok((probabilities, actual))
+        })
+        .collect()
+}
+
+/// Squared error of the forecast against the one-hot outcome, summed over classes.
+fn brier(probabilities: &[f64], actual: usize) -> f64 {
+    probabilities
+        .iter()
+        .enumerate()
+        .map(|(idx, p)| (p - if idx == actual { 1.0 } else { 0.0 }).powi(2))
+        .sum()
+}
+
+/// Negative log of the probability given to the observed outcome, floored to stay finite.
+fn log_loss_term(probabilities: &[f64], actual: usize) -> f64 {
+    -probabilities[actual].max(1e-15).ln()
+}
+
+/// Ranked probability score of an ordered forecast: the mean squared gap between the
+/// cumulative forecast and the cumulative outcome over the first `n - 1` categories.
+fn ranked_probability(probabilities: &[f64], actual: usize) -> f64 {
+    if probabilities.len() < 2 {
+        return 0.0;
+    }
+    let mut cumulative = 0.0;
+    let total: f64 = probabilities[..probabilities.len() - 1]
+        .iter()
+        .enumerate()
+        .map(|(idx, p)| {
+            cumulative += p;
+            let observed = if idx >= actual { 1.0 } else { 0.0 };
+            (cumulative - observed).powi(2)
+        })
+        .sum();
+    total / (probabilities.len() - 1) as f64
+}
+
+fn mean_score(forecasts: &[ScoredForecast], score: impl Fn(&[f64], usize) -> f64) -> f64 {
+    forecasts
+        .iter()
+        .map(|(probabilities, actual)| score(probabilities, *actual))
+        .sum::<f64>()
+        / forecasts.len() as f64
+}
+
+/// Mean Brier score of `(p_home, p_draw, p_away)` forecasts against `"H"`/`"D"`/`"A"` results.
+#[pyfunction]
+fn brier_score(predictions: Vec<(f64, f64, f64)>, outcomes: Vec<String>) -> PyResult<f64> {
+    Ok(mean_score(&parse_forecasts(predictions, outcomes)?, brier))
+}
+
+/// Mean negative log-likelihood of the observed results.
+#[pyfunction]
+fn log_loss(predictions: Vec<(f64, f64, f64)>, outcomes: Vec<String>) -> PyResult<f64> {
+    Ok(mean_score(
+        &parse_forecasts(predictions, outcomes)?,
+        log_loss_term,
+    ))
+}
+
+/// Mean ranked probability score, treating home win, draw and away win as ordered.
+#[pyfunction]
+fn ranked_probability_score(
+    predictions: Vec<(f64, f64, f64)>,
+    outcomes: Vec<String>,
+) -> PyResult<f64> {
+    Ok(mean_score(
+        &parse_forecasts(predictions, outcomes)?,
+        ranked_probability,
+    ))
+}
+
+/// Ranked probability score of a finishing-position forecast; `actual_position` is 1-based.
+fn position_rps(predicted_probs: &[f64], actual_position: usize) -> Result<f64, String> {
+    let n_positions = predicted_probs.len();
+    if n_positions < 2 {
+        return Err(format!("Need at least two positions, got {n_positions}"));
+    }
+    if !(1..=n_positions).contains(&actual_position) {
+        return Err(format!(
+            "actual_position must be between 1 and {n_positions}, got {actual_position}"
+        ));
+    }
+    if predicted_probs
+        .iter()
+        .any(|p| !p.is_finite() || !(0.0..=1.0).contains(p))
+    {
+        return Err("Position probabilities must lie in [0, 1]".to_string());
+    }
+    Ok(ranked_probability(predicted_probs, actual_position - 1))
+}
+
+/// RPS of one team's position probabilities, listed for positions `1..=n_positions`.
+#[pyfunction]
+fn rps_position(
+    predicted_probs: Vec<f64>,
+    actual_position: usize,
+    n_positions: usize,
+) -> PyResult<f64> {
+    if predicted_probs.len() != n_positions {
+        return Err(PyValueError::new_err(format!(
+            "predicted_probs has {} entries but n_positions is {n_positions}",
+            predicted_probs.len()
+        )));
+    }
+    position_rps(&predicted_probs, actual_position).map_err(PyValueError::new_err)
+}
+
+/// Mean position RPS over a league's worth of teams.
+#[pyfunction]
+fn mean_rps(predictions_list: Vec<Vec<f64>>, actuals: Vec<usize>) -> PyResult<f64> {
+    if predictions_list.len() != actuals.len() {
+        return Err(PyValueError::new_err(format!(
+            "predictions_list has {} entries but actuals has {}",
+            predictions_list.len(),
+            actuals.len()
+        )));
+    }
+    if predictions_list.is_empty() {
+        return Err(PyValueError::new_err("predictions_list must not be empty"));
+    }
+    let mut total = 0.0;
+    for (predicted_probs, &actual_position) in predictions_list.iter().zip(&actuals) {
+        total += position_rps(predicted_probs, actual_position).map_err(PyValueError::new_err)?;
+    }
+    Ok(total / actuals.len() as f64)
+}
+
+/// One scored pre-match forecast from a historical replay.
+struct BacktestEntry {
+    matchday: i64,
+    result: MatchResult,
+    predicted: [f64; 3],
+    actual: usize,
+    brier: f64,
+    rps: f64,
+}
+
+/// Walks `results` in order, forecasting each matchday by simulating the rest of the schedule
+/// `n_sims` times from the table as it stood before it, and scoring each match's outcome
+/// shares against what happened. Ratings are each team's goals-per-match relative to the
+/// league rate, with the home advantage estimated from the results replayed so far.
+fn replay_forecasts(
+    teams: &[String],
+    mut stats: Vec<Stats>,
+    results: &[(i64, MatchResult)],
+    config: &ModelConfig,
+    n_sims: usize,
+    base_seed: u64,
+) -> Vec<BacktestEntry> {
+    let mut entries = Vec::with_capacity(results.len());
+    let (mut home_goals, mut away_goals) = (0, 0);
+    let mut start = 0;
+    while start < results.len() {
+        let matchday = results[start].0;
+        let end = start
+            + results[start..]
+                .iter()
+                .take_while(|(day, _)| *day == matchday)
+                .count();
+
+        let avg_league_goals = league_goal_rate(&stats);
+        let relative: Vec<(f64, f64)> = stats
+            .iter()
+            .map(|team| {
+                if team.matches > 0 && avg_league_goals > 0.0 {
+                    let matches = team.matches as f64;
+                    (
+                        team.goals_for as f64 / matches / avg_league_goals,
+                        team.goals_against as f64 / matches / avg_league_goals,
+                    )
+                } else {
+                    (1.0, 1.0)
+                }
+            })
+            .collect();
+        let ratings = LeagueRatings::from_strengths(
+            avg_league_goals,
+            estimated_home_advantage(home_goals, away_goals),
+            &relative,
+        );
+        let input = SimulationInput {
+            teams: teams.to_vec(),
+            initial_stats: stats.clone(),
+            fixtures: results[start..]
+                .iter()
+                .map(|(_, result)| {
+                    let fixture = ParsedFixture {
+                        home_idx: result.home_idx,
+                        away_idx: result.away_idx,
+                        neutral: false,
+                        result: None,
+                    };
+                    ratings.fixture_simulation(&fixture, config)
+                })
+                .collect(),
+            tiebreakers: Vec::new(),
+        };
+
+        let mut rng = ChaCha8Rng::seed_from_u64(simulation_seed(base_seed, start));
+        let mut counts = vec![[0u64; 3]; end - start];
+        for _ in 0..n_sims {
+            let season = simulate_season_recording(&input, &mut rng, true);
+            for (count, simulated) in counts.iter_mut().zip(&season.results) {
+                count[outcome_index(simulated.home_goals, simulated.away_goals)] += 1;
+            }
+        }
+
+        for (&(matchday, result), count) in results[start..end].iter().zip(counts) {
+            let predicted = count.map(|count| count as f64 / n_sims as f64);
+            let actual = outcome_index(result.home_goals, result.away_goals);
+            entries.push(BacktestEntry {
+                matchday,
+                result,
+                predicted,
+                actual,
+                brier: brier(&predicted, actual),
+                rps: ranked_probability(&predicted, actual),
+            });
+            stats[result.home_idx].record_result(result.home_goals, result.away_goals);
+            stats[result.away_idx].record_result(result.away_goals, result.home_goals);
+            home_goals += result.home_goals;
+            away_goals += result.away_goals;
+        }
+        start = end;
+    }
+    entries
+}
+
+/// 0 for a home win, 1 for a draw, 2 for an away win.
+fn outcome_index(home_goals: i64, away_goals: i64) -> usize {
+    match home_goals.cmp(&away_goals) {
+        Ordering::Greater => 0,
+        Ordering::Equal => 1,
+        Ordering::Less => 2,
+    }
+}
+
+/// Replays `historical_results` (oldest first) from the `initial_stats` table, forecasting
+/// each match before it is applied. Results may carry a `"matchday"`; matches sharing one
+/// are all forecast from the table before that matchday.
+#[pyfunction(rho = "DEFAULT_RHO", n_sims = "10_000")]
+fn backtest(
+    py: Python,
+    historical_results: PyObject,
+    initial_stats: PyObject,
+    rho: f64,
+    n_sims: usize,
+    seed: Option<u64>,
+) -> PyResult<PyObject> {
+    let config = ModelConfig::from_args(Some(rho), None, None)?;
+    if n_sims == 0 {
+        return Err(PyValueError::new_err("n_sims must be at least 1"));
+    }
+    let base: &PyList = initial_stats.extract(py)?;
+    let results_list: &PyList = historical_results.extract(py)?;
+    let (teams, stats, team_to_idx) = parse_base_table(base)?;
+    let played = parse_played_results(results_list, &team_to_idx)?;
+    let mut results = Vec::with_capacity(played.len());
+    for (idx, (item, result)) in results_list.iter().zip(played).enumerate() {
+        let dict: &PyDict = item.extract()?;
+        let matchday = match dict.get_item("matchday") {
+            Some(value) => value.extract()?,
+            None => idx as i64 + 1,
+        };
+        results.push((matchday, result));
+    }
+
+    let base_seed = seed.unwrap_or_else(rand::random);
+    let entries =
+        py.allow_threads(|| replay_forecasts(&teams, stats, &results, &config, n_sims, base_seed));
+
+    let output = PyList::empty(py);
+    for entry in &entries {
+        let dict = PyDict::new(py);
+        dict.set_item("matchday", entry.matchday)?;
+        dict.set_item("home", &teams[entry.result.home_idx])?;
+        dict.set_item("away", &teams[entry.result.away_idx])?;
+        dict.set_item("predicted_h", entry.predicted[0])?;
+        dict.set_item("predicted_d", entry.predicted[1])?;
+        dict.set_item("predicted_a", entry.predicted[2])?;
+        dict.set_item("actual", ["H", "D", "A"][entry.actual])?;
+        dict.set_item("brier", entry.brier)?;
+        dict.set_item("rps", entry.rps)?;
+        output.append(dict)?;
+    }
+    Ok(output.into())
+}
+
+/// `(relegation, promotion, mid_table)` shares from one team's `[position]` finish counts.
+fn zone_shares(
+    position_counts: &[u64],
+    relegation_zone: usize,
+    promotion_zone: usize,
+    n_sims: usize,
+) -> (f64, f64, f64) {
+    let n_teams = position_counts.len();
+    let seasons = n_sims as f64;
+    let promotion: u64 = position_counts.iter().take(promotion_zone).sum();
+    let relegation: u64 = position_counts
+        .iter()
+        .skip(n_teams.saturating_sub(relegation_zone))
+        .sum();
+    let mid_table: u64 = position_counts.iter().sum::<u64>() - promotion - relegation;
+    (
+        relegation as f64 / seasons,
+        promotion as f64 / seasons,
+        mid_table as f64 / seasons,
+    )
+}
+
+/// `(team, [position] count)` rows from `simulate_bulk` output or its `position_counts` entry.
+/// Without `n_teams`, the number of teams listed sets the number of positions.
+fn parse_position_counts(
+    py: Python,
+    counts: PyObject,
+    n_teams: Option<usize>,
+) -> PyResult<Vec<(PyObject, Vec<u64>)>> {
+    let mut counts: &PyDict = counts.extract(py)?;
+    if let Some(position_counts) = counts.get_item("position_counts") {
+        counts = position_counts.downcast()?;
+    }
+    let n_teams = n_teams.unwrap_or(counts.len());
+    let mut rows = Vec::with_capacity(counts.len());
+    for (team, entry) in counts.iter() {
+        let mut entry: &PyDict = entry.downcast()?;
+        if let Some(positions) = entry.get_item("positions") {
+            entry = positions.downcast()?;
+        }
+        let mut position_counts = vec![0u64; n_teams];
+        for (position, count) in entry.iter() {
+            let position: usize = position.extract()?;
+            if position == 0 || position > n_teams {
+                return Err(PyValueError::new_err(format!(
+                    "Position {position} is outside 1..={n_teams}"
+                )));
+            }
+            position_counts[position - 1] = count.extract()?;
+        }
+        rows.push((team.into(), position_counts));
+    }
+    Ok(rows)
+}
+
+/// Mean normalized entropy of each team's finishing position, in `[0, 1]`: `1` when every team
+/// is equally likely to finish anywhere, `0` when every place is already settled.
+fn competitiveness(position_counts: &[Vec<u64>], n_sims: usize) -> f64 {
+    let n_teams = position_counts.len();
+    if n_teams < 2 {
+        return 0.0;
+    }
+    let entropy = |counts: &Vec<u64>| -> f64 {
+        counts
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let share = count as f64 / n_sims as f64;
+                -share * share.ln()
+            })
+            .sum()
+    };
+    let mean_entropy = position_counts.iter().map(entropy).sum::<f64>() / n_teams as f64;
+    mean_entropy / (n_teams as f64).ln()
+}
+
+/// Entropy-based competitiveness of a league from `simulate_bulk` position counts.
+#[pyfunction]
+fn competitiveness_index(py: Python, position_counts: PyObject, n_sims: usize) -> PyResult<f64> {
+    if n_sims == 0 {
+        return Err(PyValueError::new_err("n_sims must be positive"));
+    }
+    let rows = parse_position_counts(py, position_counts, None)?;
+    let counts: Vec<Vec<u64>> = rows.into_iter().map(|(_, counts)| counts).collect();
+    Ok(competitiveness(&counts, n_sims))
+}
+
+/// `[team][team]` Pearson correlation of finishing positions over `[season][team]` positions.
+/// A team that always finishes in the same place has no defined correlation, reported as NaN.
+fn position_correlations(history: &[Vec<usize>]) -> Vec<Vec<f64>> {
+    let n_teams = history.first().map_or(0, Vec::len);
+    let n_sims = history.len() as f64;
+    let means: Vec<f64> = (0..n_teams)
+        .map(|team| {
+            history
+                .iter()
+                .map(|season| season[team] as f64)
+                .sum::<f64>()
+                / n_sims
+        })
+        .collect();
+    let mut covariance = vec![vec![0.0; n_teams]; n_teams];
+    for season in history {
+        let deviations: Vec<f64> = season
+            .iter()
+            .zip(&means)
+            .map(|(&position, mean)| position as f64 - mean)
+            .collect();
+        for (row, deviation) in covariance.iter_mut().zip(&deviations) {
+            for (cell, other) in row.iter_mut().zip(&deviations) {
+                *cell += deviation * other;
+            }
+        }
+    }
+    let spreads: Vec<f64> = (0..n_teams)
+        .map(|team| covariance[team][team].sqrt())
+        .collect();
+    covariance
+        .iter()
+        .enumerate()
+        .map(|(team, row)| {
+            row.iter()
+                .zip(&spreads)
+                .enumerate()
+                .map(|(other, (cell, spread))| {
+                    if spreads[team] > 0.0 && team == other {
+                        1.0
+                    } else if spreads[team] > 0.0 && *spread > 0.0 {
+                        (cell / (spreads[team] * spread)).clamp(-1.0, 1.0)
+                    } else {
+                        f64::NAN
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// `[team][team]` correlation of finishing positions, from the `position_history` that
+/// `simulate_bulk(..., return_full_history=True)` returns: one list of every team's position
+/// per season. Teams keep the column order of the history.
+#[pyfunction]
+fn position_correlation_matrix(
+    py: Python,
+    position_history: PyObject,
+    n_sims: usize,
+) -> PyResult<Vec<Vec<f64>>> {
+    let history: Vec<Vec<usize>> = position_history.extract(py)?;
+    if n_sims < 2 {
+        return Err(PyValueError::new_err("n_sims must be at least 2"));
+    }
+    if history.len() != n_sims {
+        return Err(PyValueError::new_err(format!(
+            "position_history has {} seasons but n_sims is {n_sims}",
+            history.len()
+        )));
+    }
+    let n_teams = history[0].len();
+    if history.iter().any(|season| season.len() != n_teams) {
+        return Err(PyValueError::new_err(
+            "every season in position_history must list the same teams",
+        ));
+    }
+    Ok(py.allow_threads(|| position_correlations(&history)))
+}
+
+/// Gini coefficient of `shares`, rescaled by `n / (n - 1)` so that `1` means one entry holds
+/// everything and `0` that all are equal.
+fn normalized_gini(shares: &[f64]) -> f64 {
+    let n = shares.len();
+    let total: f64 = shares.iter().sum();
+    if n < 2 || total <= 0.0 {
+        return 0.0;
+    }
+    let mut sorted = shares.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    // Sum of |x_i - x_j| over all pairs, from the sorted order.
+    let differences: f64 = sorted
+        .iter()
+        .enumerate()
+        .map(|(rank, share)| (2.0 * rank as f64 + 1.0 - n as f64) * share)
+        .sum();
+    (differences / ((n - 1) as f64 * total)).clamp(0.0, 1.0)
+}
+
+/// `[position]` Gini coefficient of how the teams share that finishing position.
+fn position_ginis(position_counts: &[Vec<u64>], n_sims: usize) -> Vec<f64> {
+    (0..position_counts.len())
+        .map(|pos_idx| {
+            let shares: Vec<f64> = position_counts
+                .iter()
+                .map(|counts| counts[pos_idx] as f64 / n_sims as f64)
+                .collect();
+            normalized_gini(&shares)
+        })
+        .collect()
+}
+
+/// Gini coefficient of the teams' title chances from `simulate_bulk` position counts: `0` when
+/// every team is equally likely to be champion, `1` when one team always is.
+#[pyfunction]
+fn league_gini(py: Python, position_counts: PyObject, n_sims: usize) -> PyResult<f64> {
+    let ginis = per_position_gini(py, position_counts, n_sims)?;
+    Ok(ginis.first().copied().unwrap_or(0.0))
+}
+
+/// `league_gini` for every finishing position in turn, champion first.
+#[pyfunction]
+fn per_position_gini(py: Python, position_counts: PyObject, n_sims: usize) -> PyResult<Vec<f64>> {
+    if n_sims == 0 {
+        return Err(PyValueError::new_err("n_sims must be positive"));
+    }
+    let rows = parse_position_counts(py, position_counts, None)?;
+    let counts: Vec<Vec<u64>> = rows.into_iter().map(|(_, counts)| counts).collect();
+    Ok(position_ginis(&counts, n_sims))
+}
+
+/// Share of `[season][team]` zero-based position seasons in which every one of `teams` finishes
+/// inside the top `n`.
+fn joint_top_n_share(history: &[Vec<usize>], teams: &[usize], n: usize) -> f64 {
+    let hits = history
+        .iter()
+        .filter(|season| teams.iter().all(|&team| season[team] < n))
+        .count();
+    hits as f64 / history.len() as f64
+}
+
+/// Probability that all of `teams` finish in positions `1..=n`, in any order, from the
+/// `position_history` of `simulate_bulk(..., return_full_history=True)`. Teams are given by
+/// their column in the history, i.e. their zero-based row in the base table.
+#[pyfunction]
+fn joint_top_n_probability(
+    py: Python,
+    position_counts_full: PyObject,
+    teams: PyObject,
+    n: usize,
+    n_sims: usize,
+) -> PyResult<f64> {
+    let history: Vec<Vec<usize>> = position_counts_full.extract(py)?;
+    let teams: Vec<usize> = teams.extract(py)?;
+    if n_sims == 0 {
+        return Err(PyValueError::new_err("n_sims must be positive"));
+    }
+    if history.len() != n_sims {
+        return Err(PyValueError::new_err(format!(
+            "position history has {} seasons but n_sims is {n_sims}",
+            history.len()
+        )));
+    }
+    let n_teams = history[0].len();
+    if n == 0 || n > n_teams {
+        return Err(PyValueError::new_err(format!(
+            "n must be between 1 and {n_teams}"
+        )));
+    }
+    if let Some(&team) = teams.iter().find(|&&team| team >= n_teams) {
+        return Err(PyValueError::new_err(format!(
+            "team {team} is not a column of the position history"
+        )));
+    }
+    let zero_based = history
+        .into_iter()
+        .map(|season| {
+            if season.len() != n_teams {
+                return Err(PyValueError::new_err(
+                    "every season in the position history must list the same teams",
+                ));
+            }
+            season
+                .into_iter()
+                .map(|position| {
+                    position
+                        .checked_sub(1)
+                        .ok_or_else(|| PyValueError::new_err("positions in the history start at 1"))
+                })
+                .collect()
+        })
+        .collect::<PyResult<Vec<Vec<usize>>>>()?;
+    Ok(joint_top_n_share(&zero_based, &teams, n))
+}
+
+/// Spread of one team's finishing position, in 1-based places.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PositionSummary {
+    mean: f64,
+    median: f64,
+    p25: f64,
+    p75: f64,
+    std: f64,
+}
+
+/// Summary of a `[position]` count histogram over `n_sims` seasons. Quantiles are the first
+/// place whose cumulative share reaches the level, so they are always a place the team can
+/// actually finish in.
+fn position_summary(counts: &[u64], n_sims: usize) -> PositionSummary {
+    let shares: Vec<f64> = counts
+        .iter()
+        .map(|&count| count as f64 / n_sims as f64)
+        .collect();
+    let place = |idx: usize| (idx + 1) as f64;
+    let mean: f64 = shares
+        .iter()
+        .enumerate()
+        .map(|(idx, share)| place(idx) * share)
+        .sum();
+    let variance: f64 = shares
+        .iter()
+        .enumerate()
+        .map(|(idx, share)| (place(idx) - mean).powi(2) * share)
+        .sum();
+    let quantile = |level: f64| {
+        let mut cumulative = 0.0;
+        let idx = shares
+            .iter()
+            .position(|share| {
+                cumulative += share;
+                // Tolerates the rounding of shares that should add up to exactly `level`.
+                cumulative >= level - 1e-12
+            })
+            .unwrap_or(shares.len().saturating_sub(1));
+        place(idx)
+    };
+    PositionSummary {
+        mean,
+        median: quantile(0.5),
+        p25: quantile(0.25),
+        p75: quantile(0.75),
+        std: variance.sqrt(),
+    }
+}
+
+/// `{team: {"mean", "median", "p25", "p75", "std"}}` of finishing positions from `simulate_bulk`
+/// position counts, which already hold each team's whole distribution.
+#[pyfunction]
+fn position_summary_stats(
+    py: Python,
+    position_counts: PyObject,
+    n_sims: usize,
+) -> PyResult<PyObject> {
+    if n_sims == 0 {
+        return Err(PyValueError::new_err("n_sims must be positive"));
+    }
+    let result = PyDict::new(py);
+    for (team, counts) in parse_position_counts(py, position_counts, None)? {
+        let seasons: u64 = counts.iter().sum();
+        if seasons != n_sims as u64 {
+            return Err(PyValueError::new_err(format!(
+                "{team} has {seasons} counted seasons but n_sims is {n_sims}"
+            )));
+        }
+        let summary = position_summary(&counts, n_sims);
+        let stats = PyDict::new(py);
+        stats.set_item("mean", summary.mean)?;
+        stats.set_item("median", summary.median)?;
+        stats.set_item("p25", summary.p25)?;
+        stats.set_item("p75", summary.p75)?;
+        stats.set_item("std", summary.std)?;
+        result.set_item(team, stats)?;
+    }
+    Ok(result.into())
+}
+
+/// Percentile bootstrap interval for a probability observed `count` times in `n_sims` seasons.
+/// Resampling the seasons multinomially leaves the one position binomial, so each replicate is
+/// a `Binomial(n_sims, count / n_sims)` draw.
+fn bootstrap_interval<R: Rng>(
+    rng: &mut R,
+    count: u64,
+    n_sims: usize,
+    n_bootstrap: usize,
+    confidence: f64,
+) -> Result<(f64, f64), String> {
+    if n_sims == 0 || n_bootstrap == 0 {
+        return Err("n_sims and n_bootstrap must be positive".to_string());
+    }
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err(format!("confidence must lie in (0, 1), got {confidence}"));
+    }
+    if count > n_sims as u64 {
+        return Err(format!("count {count} exceeds n_sims {n_sims}"));
+    }
+    let replicate = rand_distr::Binomial::new(n_sims as u64, count as f64 / n_sims as f64)
+        .map_err(|err| err.to_string())?;
+    let mut shares: Vec<f64> = (0..n_bootstrap)
+        .map(|_| rng.sample(replicate) as f64 / n_sims as f64)
+        .collect();
+    shares.sort_by(f64::total_cmp);
+    let quantile = |q: f64| shares[((n_bootstrap - 1) as f64 * q).round() as usize];
+    Ok((
+        quantile((1.0 - confidence) / 2.0),
+        quantile((1.0 + confidence) / 2.0),
+    ))
+}
+
+/// Bootstrap `confidence` interval on `team`'s probability of finishing in `position`, from
+/// `simulate_bulk` position counts over `n_sims` seasons.
+#[pyfunction]
+fn bootstrap_ci(
+    py: Python,
+    position_counts: PyObject,
+    n_sims: usize,
+    team: String,
+    position: usize,
+    n_bootstrap: usize,
+    confidence: f64,
+) -> PyResult<(f64, f64)> {
+    let rows = parse_position_counts(py, position_counts, None)?;
+    let mut counts = None;
+    for (name, row) in rows {
+        if name.extract::<String>(py)? == team {
+            counts = Some(row);
+        }
+    }
+    let counts =
+        counts.ok_or_else(|| PyValueError::new_err(format!("Team {team} not found in counts")))?;
+    if position == 0 || position > counts.len() {
+        return Err(PyValueError::new_err(format!(
+            "Position {position} is outside 1..={}",
+            counts.len()
+        )));
+    }
+    let mut rng = season_rng(None);
+    bootstrap_interval(
+        &mut rng,
+        counts[position - 1],
+        n_sims,
+        n_bootstrap,
+        confidence,
+    )
+    .map_err(PyValueError::new_err)
+}
+
+/// Turn `simulate_bulk` position counts into relegation / promotion / mid-table probabilities.
+#[pyfunction]
+fn zone_probabilities(
+    py: Python,
+    counts: PyObject,
+    n_teams: usize,
+    relegation_zone: usize,
+    promotion_zone: usize,
+    n_sims: usize,
+) -> PyResult<PyObject> {
+    if n_sims == 0 {
+        return Err(PyValueError::new_err("n_sims must be positive"));
+    }
+    if relegation_zone + promotion_zone > n_teams {
+        return Err(PyValueError::new_err(format!(
+            "relegation_zone + promotion_zone must not exceed n_teams ({n_teams})"
+        )));
+    }
+    let result = PyDict::new(py);
+    for (team, position_counts) in parse_position_counts(py, counts, Some(n_teams))? {
+        let (relegation, promotion, mid_table) =
+            zone_shares(&position_counts, relegation_zone, promotion_zone, n_sims);
+        let zones = PyDict::new(py);
+        zones.set_item("relegation", relegation)?;
+        zones.set_item("promotion", promotion)?;
+        zones.set_item("mid_table", mid_table)?;
+        result.set_item(team, zones)?;
+    }
+    Ok(result.into())
+}
+
+/// Current records plus the fixtures still to play, for what-is-still-possible checks.
+struct RemainingSeason {
+    teams: Vec<String>,
+    stats: Vec<Stats>,
+    fixtures: Vec<(usize, usize)>,
+}
+
+impl RemainingSeason {
+    fn from_python(py: Python, base_table: PyObject, fixtures: PyObject) -> PyResult<Self> {
+        let base: &PyList = base_table.extract(py)?;
+        let fixtures_list: &PyList = fixtures.extract(py)?;
+        let (teams, mut stats, team_to_idx) = parse_base_table(base)?;
+        let mut fixtures = Vec::new();
+        for fixture in parse_fixture_pairs(fixtures_list, &team_to_idx)? {
+            match fixture.result {
+                // Awarded results are already settled, so they count as played.
+                Some((home_goals, away_goals)) => {
+                    let (home_goals, away_goals) = (home_goals as i64, away_goals as i64);
+                    stats[fixture.home_idx].record_result(home_goals, away_goals);
+                    stats[fixture.away_idx].record_result(away_goals, home_goals);
+                }
+                None => fixtures.push((fixture.home_idx, fixture.away_idx)),
+            }
+        }
+        Ok(RemainingSeason {
+            teams,
+            stats,
+            fixtures,
+        })
+    }
+
+    fn team_index(&self, team: &str) -> PyResult<usize> {
+        self.teams
+            .iter()
+            .position(|name| name == team)
+            .ok_or_else(|| PyValueError::new_err(format!("Team {team} not found in standings")))
+    }
+
+    fn remaining_matches(&self) -> Vec<i64> {
+        let mut remaining = vec![0; self.teams.len()];
+        for &(home_idx, away_idx) in &self.fixtures {
+            remaining[home_idx] += 1;
+            remaining[away_idx] += 1;
+        }
+        remaining
+    }
+
+    /// Points the current leader needs for the title to be safe on points: no rival can
+    /// finish above this total, and the leader already has at least its current points.
+    fn title_threshold(&self) -> i64 {
+        let Some(leader) = (0..self.teams.len()).reduce(|leader, team| {
+            if self.finishes_above(team, &self.stats[team], leader, &self.stats[leader]) {
+                team
+            } else {
+                leader
+            }
+        }) else {
+            return 0;
+        };
+        self.stats
+            .iter()
+            .zip(self.remaining_matches())
+            .enumerate()
+            .filter(|&(team, _)| team != leader)
+            .map(|(_, (stats, remaining))| stats.points + 3 * remaining)
+            .fold(self.stats[leader].points, i64::max)
+    }
+
+    /// Ranking key of a record under the default order: points, goal difference, goals for.
+    fn record_key(stats: &Stats) -> (i64, i64, i64) {
+        (stats.points, stats.goal_difference(), stats.goals_for)
+    }
+
+    /// Whether `ahead` stays above `behind` when they finish on `ahead_record` and `behind_record`.
+    fn finishes_above(
+        &self,
+        ahead: usize,
+        ahead_record: &Stats,
+        behind: usize,
+        behind_record: &Stats,
+    ) -> bool {
+        match Self::record_key(ahead_record).cmp(&Self::record_key(behind_record)) {
+            Ordering::Greater => true,
+            Ordering::Less => false,
+            Ordering::Equal => self.teams[ahead] < self.teams[behind],
+        }
+    }
+
+    /// Records once `team` has won every one of its remaining fixtures by `margin` goals (or
+    /// lost them, for a negative `margin`), and the fixtures left between its rivals.
+    fn after_team_fixtures(&self, team: usize, margin: i64) -> (Vec<Stats>, Vec<(usize, usize)>) {
+        let mut records = self.stats.clone();
+        let mut rival_fixtures = Vec::new();
+        let (scored, conceded) = (margin.max(0), (-margin).max(0));
+        for &(home, away) in &self.fixtures {
+            if home == team || away == team {
+                let rival = if home == team { away } else { home };
+                records[team].record_result(scored, conceded);
+                records[rival].record_result(conceded, scored);
+            } else {
+                rival_fixtures.push((home, away));
+            }
+        }
+        (records, rival_fixtures)
+    }
+
+    /// `records` after the rival fixtures, each `winners` entry winning by `margin` and the
+    /// fixtures without one drawn 0-0.
+    fn settle(
+        records: &[Stats],
+        rival_fixtures: &[(usize, usize)],
+        winners: &[Option<usize>],
+        margin: i64,
+    ) -> Vec<Stats> {
+        let mut records = records.to_vec();
+        for (&(home, away), winner) in rival_fixtures.iter().zip(winners) {
+            let (home_goals, away_goals) = match winner {
+                Some(winner) if *winner == home => (margin, 0),
+                Some(_) => (0, margin),
+                None => (0, 0),
+            };
+            records[home].record_result(home_goals, away_goals);
+            records[away].record_result(away_goals, home_goals);
+        }
+        records
+    }
+
+    /// Best finish `team` can still reach: it wins every remaining match by `MAX_GOALS` while
+    /// the fixtures between its rivals are settled to keep as many of them below it as
+    /// possible, so past this position the team is mathematically out of reach.
+    fn best_possible_position(&self, team: usize) -> usize {
+        self.best_case_position(team, MAX_GOALS as i64)
+    }
+
+    /// Finish of `team` when it wins every remaining match by `margin` goals and every rival
+    /// loses to it by the same score. Fixtures between rivals are won 1-0 by a max-flow
+    /// assignment that keeps each rival's wins under what would lift it above `team`, and
+    /// drawn where neither side can take the win. When those draws lift a rival above `team`,
+    /// the one with the most points is let through and the fixtures are assigned again. The
+    /// position comes from results that can really happen; with draws in play the exact
+    /// question is NP-hard, so rare combinations may be missed.
+    fn best_case_position(&self, team: usize, margin: i64) -> usize {
+        let (records, rival_fixtures) = self.after_team_fixtures(team, margin);
+        let best = records[team];
+        let below = |rival: usize, record: &Stats| {
+            rival == team || !self.finishes_above(rival, record, team, &best)
+        };
+        let mut games = vec![0; self.teams.len()];
+        for &(home, away) in &rival_fixtures {
+            games[home] += 1;
+            games[away] += 1;
+        }
+        let mut let_through: Vec<bool> = (0..self.teams.len())
+            .map(|rival| !below(rival, &records[rival]))
+            .collect();
+        loop {
+            let caps: Vec<usize> = (0..self.teams.len())
+                .map(|rival| {
+                    if let_through[rival] {
+                        return usize::MAX;
+                    }
+                    let mut record = records[rival];
+                    (0..games[rival])
+                        .take_while(|_| {
+                            record.record_result(1, 0);
+                            below(rival, &record)
+                        })
+                        .count()
+                })
+                .collect();
+            let winners = assign_wins(&rival_fixtures, &caps);
+            let settled = Self::settle(&records, &rival_fixtures, &winners, 1);
+            // Fixtures left without a winner were drawn; a rival those draws lift above `team`
+            // is let through, and the assignment is redone around it.
+            let lifted = rival_fixtures
+                .iter()
+                .zip(&winners)
+                .filter(|(_, winner)| winner.is_none())
+                .flat_map(|(&(home, away), _)| [home, away])
+                .filter(|&rival| !let_through[rival] && !below(rival, &settled[rival]))
+                .max_by_key(|&rival| (records[rival].points, Reverse(rival)));
+            match lifted {
+                Some(rival) => let_through[rival] = true,
+                None => {
+                    break (0..self.teams.len())
+                        .filter(|&rival| !below(rival, &settled[rival]))
+                        .count()
+                        + 1
+                }
+            }
+        }
+    }
+
+    /// Worst finish `team` can still drop to: it loses every remaining match by `MAX_GOALS`
+    /// while the fixtures between its rivals lift as many of them above it as possible.
+    fn worst_possible_position(&self, team: usize) -> usize {
+        self.worst_case_position(team, MAX_GOALS as i64)
+    }
+
+    /// Finish of `team` when it loses every remaining match by `margin` goals and every rival
+    /// beats it by the same score. Rivals are then taken fewest wins first, each given the
+    /// wins by `margin` it needs to pass `team` whenever a max-flow assignment of the fixtures
+    /// between rivals still finds them alongside the earlier ones; the other fixtures are
+    /// drawn. That is tried with no fixtures drawn up front, with those drawn where a point
+    /// lifts both sides, and with all of them drawn, keeping the worst. Like
+    /// `best_case_position`, the finish is one some set of results really gives.
+    fn worst_case_position(&self, team: usize, margin: i64) -> usize {
+        let (records, rival_fixtures) = self.after_team_fixtures(team, -margin);
+        let worst = records[team];
+        let above = |rival: usize, record: &Stats| {
+            rival != team && !self.finishes_above(team, &worst, rival, record)
+        };
+        // Rivals above `team` once the `drawn` fixtures end 0-0 and the others are assigned.
+        let lifted = |drawn: &[bool]| {
+            let (drawn_fixtures, open): (Vec<_>, Vec<_>) = rival_fixtures
+                .iter()
+                .zip(drawn)
+                .partition(|(_, &drawn)| drawn);
+            let drawn_fixtures: Vec<(usize, usize)> = drawn_fixtures
+                .into_iter()
+                .map(|(&fixture, _)| fixture)
+                .collect();
+            let open: Vec<(usize, usize)> = open.into_iter().map(|(&fixture, _)| fixture).collect();
+            let records = Self::settle(
+                &records,
+                &drawn_fixtures,
+                &vec![None; drawn_fixtures.len()],
+                0,
+            );
+            let mut games = vec![0; self.teams.len()];
+            for &(home, away) in &open {
+                games[home] += 1;
+                games[away] += 1;
+            }
+            let mut needs: Vec<(usize, usize)> = (0..self.teams.len())
+                .filter(|&rival| rival != team && !above(rival, &records[rival]))
+                .filter_map(|rival| {
+                    let mut record = records[rival];
+                    (1..=games[rival])
+                        .find(|_| {
+                            record.record_result(margin, 0);
+                            above(rival, &record)
+                        })
+                        .map(|wins| (wins, rival))
+                })
+                .collect();
+            needs.sort_unstable();
+            let mut demands = vec![0; self.teams.len()];
+            for (wins, rival) in needs {
+                demands[rival] = wins;
+                let assigned = assign_wins(&open, &demands)
+                    .iter()
+                    .filter(|winner| winner.is_some())
+                    .count();
+                if assigned < demands.iter().sum() {
+                    demands[rival] = 0;
+                }
+            }
+            let winners = assign_wins(&open, &demands);
+            let settled = Self::settle(&records, &open, &winners, margin);
+            (0..self.teams.len())
+                .filter(|&rival| above(rival, &settled[rival]))
+                .count()
+        };
+        // A draw lifts both sides at once, so also try drawing the fixtures where one point
+        // is enough for both, and drawing every fixture.
+        let paired: Vec<bool> = rival_fixtures
+            .iter()
+            .map(|&(home, away)| {
+                [home, away].iter().all(|&rival| {
+                    let mut record = records[rival];
+                    record.record_result(0, 0);
+                    !above(rival, &records[rival]) && above(rival, &record)
+                })
+            })
+            .collect();
+        [
+            vec![false; rival_fixtures.len()],
+            paired,
+            vec![true; rival_fixtures.len()],
+        ]
+        .iter()
+        .map(|drawn| lifted(drawn))
+        .max()
+        .unwrap_or(0)
+            + 1
+    }
+}
+
+/// Winner of each of `fixtures` in a largest assignment where team `t` wins at most `caps[t]`
+/// of them, found by augmenting paths; `None` marks fixtures left without a winner.
+fn assign_wins(fixtures: &[(usize, usize)], caps: &[usize]) -> Vec<Option<usize>> {
+    fn augment(
+        fixture: usize,
+        fixtures: &[(usize, usize)],
+        caps: &[usize],
+        winners: &mut [Option<usize>],
+        wins: &mut [Vec<usize>],
+        seen: &mut [bool],
+    ) -> bool {
+        let (home, away) = fixtures[fixture];
+        for team in [home, away] {
+            if seen[team] {
+                continue;
+            }
+            seen[team] = true;
+            if wins[team].len() < caps[team] {
+                wins[team].push(fixture);
+                winners[fixture] = Some(team);
+                return true;
+            }
+            for slot in 0..wins[team].len() {
+                // Hand one of `team`'s wins to its opponent there to make room.
+                if augment(wins[team][slot], fixtures, caps, winners, wins, seen) {
+                    wins[team][slot] = fixture;
+                    winners[fixture] = Some(team);
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    let num_teams = fixtures
+        .iter()
+        .map(|&(home, away)| home.max(away) + 1)
+        .max()
+        .unwrap_or(0)
+        .max(caps.len());
+    let mut winners = vec![None; fixtures.len()];
+    let mut wins = vec![Vec::new(); num_teams];
+    for fixture in 0..fixtures.len() {
+        let mut seen = vec![false; num_teams];
+        augment(fixture, fixtures, caps, &mut winners, &mut wins, &mut seen);
+    }
+    winners
+}
+
+/// `False` when `team` is mathematically unable to finish in `target_position` or higher.
+#[pyfunction]
+fn can_finish_above(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    team: String,
+    target_position: usize,
+) -> PyResult<bool> {
+    let season = RemainingSeason::from_python(py, base_table, fixtures)?;
+    let team_idx = season.team_index(&team)?;
+    if target_position == 0 || target_position > season.teams.len() {
+        return Err(PyValueError::new_err(format!(
+            "target_position must be in 1..={}",
+            season.teams.len()
+        )));
+    }
+    Ok(season.best_possible_position(team_idx) <= target_position)
+}
+
+/// `True` when no combination of remaining results can push `team` out of the top `zone_size`.
+#[pyfunction]
+fn already_qualified(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    team: String,
+    zone_size: usize,
+) -> PyResult<bool> {
+    let season = RemainingSeason::from_python(py, base_table, fixtures)?;
+    let team_idx = season.team_index(&team)?;
+    if zone_size == 0 || zone_size > season.teams.len() {
+        return Err(PyValueError::new_err(format!(
+            "zone_size must be in 1..={}",
+            season.teams.len()
+        )));
+    }
+    Ok(season.worst_possible_position(team_idx) <= zone_size)
+}
+
+/// `(best, worst)` finish of `team` in the two extreme scenarios: it wins every remaining match
+/// 1-0 while the fixtures between its rivals keep as many of them below it as they can, or it
+/// loses every match 0-1 while they lift as many above it as they can. Ties are split by goal difference and goals
+/// scored as in the default table order.
+#[pyfunction]
+fn best_worst_case(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    team: String,
+) -> PyResult<(usize, usize)> {
+    let season = RemainingSeason::from_python(py, base_table, fixtures)?;
+    let team_idx = season.team_index(&team)?;
+    Ok((
+        season.best_case_position(team_idx, 1),
+        season.worst_case_position(team_idx, 1),
+    ))
+}
+
+/// The fewest points the current leader must finish on so that no other team can finish with
+/// more; at that total only goal difference could still cost it the title. When no rival can
+/// catch the leader any more, it is simply the leader's current total.
+#[pyfunction]
+fn points_to_win_league(py: Python, base_table: PyObject, fixtures: PyObject) -> PyResult<i64> {
+    let season = RemainingSeason::from_python(py, base_table, fixtures)?;
+    Ok(season.title_threshold())
+}
+
+/// The settings a checkpointed run was started with, so a resume cannot splice seasons of a
+/// different model onto it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CheckpointSettings {
+    rho: f64,
+    home_advantage: Option<f64>,
+    max_goals: usize,
+    score_model: ScoreModel,
+    tiebreakers: Vec<TiebreakerStage>,
+    /// Sorted by team.
+    deductions: Vec<(String, i64)>,
+}
+
+impl CheckpointSettings {
+    fn new(
+        config: &ModelConfig,
+        tiebreakers: &[TiebreakerStage],
+        deductions: Option<&HashMap<String, i64>>,
+    ) -> Self {
+        let mut deductions: Vec<(String, i64)> = deductions
+            .map(|deductions| deductions.clone().into_iter().collect())
+            .unwrap_or_default();
+        deductions.sort();
+        CheckpointSettings {
+            rho: config.rho,
+            home_advantage: config.home_advantage,
+            max_goals: config.max_goals,
+            score_model: config.score_model,
+            tiebreakers: tiebreakers.to_vec(),
+            deductions,
+        }
+    }
+
+    /// Names the first setting of `self` that differs from `other`, if any.
+    fn mismatch(&self, other: &Self) -> Option<&'static str> {
+        if self.rho != other.rho {
+            Some("rho")
+        } else if self.home_advantage != other.home_advantage {
+            Some("home_advantage")
+        } else if self.max_goals != other.max_goals {
+            Some("max_goals")
+        } else if self.score_model != other.score_model {
+            Some("model")
+        } else if self.tiebreakers != other.tiebreakers {
+            Some("tiebreaker_stages")
+        } else if self.deductions != other.deductions {
+            Some("deductions")
+        } else {
+            None
+        }
+    }
+}
+
+/// A bulk run saved part-way: the base seed, its settings, seasons completed and their
+/// position counts and tables. Stored with `bincode`.
+#[derive(Serialize, Deserialize)]
+struct BulkCheckpoint {
+    base_seed: u64,
+    completed: usize,
+    settings: CheckpointSettings,
+    tally: BulkTally,
+}
+
+impl BulkCheckpoint {
+    /// Writes through a temporary file so an interruption never leaves a half-written checkpoint.
+    fn save(&self, path: &std::path::Path) -> Result<(), String> {
+        let partial = path.with_extension("partial");
+        let bytes = bincode::serialize(self)
+            .map_err(|err| format!("Could not encode checkpoint {}: {err}", path.display()))?;
+        std::fs::write(&partial, bytes)
+            .and_then(|_| std::fs::rename(&partial, path))
+            .map_err(|err| format!("Could not write checkpoint {}: {err}", path.display()))
+    }
+
+    fn load(path: &std::path::Path) -> Result<Self, String> {
+        let bytes = std::fs::read(path)
+            .map_err(|err| format!("Could not read checkpoint {}: {err}", path.display()))?;
+        bincode::deserialize(&bytes).map_err(|err| {
+            format!(
+                "{} is not a bulk simulation checkpoint: {err}",
+                path.display()
+            )
+        })
+    }
+
+    /// Simulates `n_sims` more seasons, saving after every `checkpoint_every` (and at the end).
+    fn extend(
+        mut self,
+        input: &SimulationInput,
+        n_sims: usize,
+        options: &BulkOptions,
+        path: &std::path::Path,
+        checkpoint_every: Option<usize>,
+    ) -> Result<Self, String> {
+        let end = self.completed + n_sims;
+        let chunk = checkpoint_every.unwrap_or(n_sims).max(1);
+        SIMULATION_PROGRESS.store(0, AtomicOrdering::Relaxed);
+        while self.completed < end {
+            let chunk_end = (self.completed + chunk).min(end);
+            let part = run_bulk_range(input, self.completed..chunk_end, self.base_seed, options);
+            self.tally = self.tally.merge(part, options);
+            self.completed = chunk_end;
+            self.save(path)?;
+        }
+        Ok(self)
+    }
+}
+
+/// `(base_table, fixtures, home_table, away_table)` as passed to the bulk simulators.
+type LeagueTables = (PyObject, PyObject, PyObject, PyObject);
+
+fn parse_bulk_input(
+    py: Python,
+    tables: LeagueTables,
+    config: &ModelConfig,
+    deductions: Option<&HashMap<String, i64>>,
+) -> PyResult<SimulationInput> {
+    let (base_table, fixtures, home_table, away_table) = tables;
+    let mut input =
+        parse_simulation_input(py, base_table, fixtures, home_table, away_table, config)?;
+    if let Some(deductions) = deductions {
+        input
+            .apply_deductions(deductions)
+            .map_err(PyValueError::new_err)?;
+    }
+    Ok(input)
+}
+
+/// Team names in alphabetical order with their `[position]` finish-count rows.
+fn position_counts_by_name(
+    teams: &[String],
+    position_counts: &[Vec<u64>],
+) -> (Vec<String>, Vec<Vec<u64>>) {
+    let mut order: Vec<usize> = (0..teams.len()).collect();
+    order.sort_by(|&left, &right| teams[left].cmp(&teams[right]));
+    order
+        .into_iter()
+        .map(|idx| (teams[idx].clone(), position_counts[idx].clone()))
+        .unzip()
+}
+
+/// Like `simulate_bulk`, but returns `(counts, teams)`: a `numpy.ndarray` whose `[i, j]` is
+/// how often team `i` finished in position `j + 1`, and the alphabetical team names for its rows.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn simulate_bulk_array(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    home_table: PyObject,
+    away_table: PyObject,
+    n_sims: usize,
+    seed: Option<u64>,
+    rho: Option<f64>,
+    home_advantage: Option<f64>,
+    max_goals: Option<usize>,
+    deductions: Option<HashMap<String, i64>>,
+) -> PyResult<PyObject> {
+    let config = ModelConfig::from_args(rho, home_advantage, max_goals)?;
+    let input = parse_bulk_input(
+        py,
+        (base_table, fixtures, home_table, away_table),
+        &config,
+        deductions.as_ref(),
+    )?;
+    let base_seed = seed.unwrap_or_else(rand::random);
+    let options = BulkOptions {
+        top_k_tables: 1,
+        ..BulkOptions::default()
+    };
+    let tally = py.allow_threads(|| run_bulk_simulations(&input, n_sims, base_seed, &options));
+
+    let (teams, rows) = position_counts_by_name(&input.teams, &tally.position_counts);
+    let counts = py
+        .import("numpy")?
+        .call_method1("array", (rows, "uint64"))?;
+    Ok((counts, teams).into_py(py))
+}
+
+/// Every tunable of a bulk run, so it can be saved as JSON and replayed exactly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "ConfigFields", into = "ConfigFields")]
+struct SimulationConfig {
+    home_advantage: Option<f64>,
+    rho: f64,
+    max_goals: usize,
+    seed: Option<u64>,
+    n_sims: usize,
+    score_model: ScoreModel,
+}
+
+/// The flat JSON object a `SimulationConfig` is saved as; missing or `null` keys take the
+/// defaults. Floats use serde_json's shortest round-trip formatting.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFields {
+    home_advantage: Option<f64>,
+    rho: Option<f64>,
+    max_goals: Option<usize>,
+    seed: Option<u64>,
+    n_sims: Option<usize>,
+    model: Option<String>,
+    dispersion: Option<f64>,
+    lambda_3: Option<f64>,
+}
+
+impl TryFrom<ConfigFields> for SimulationConfig {
+    type Error = String;
+
+    fn try_from(fields: ConfigFields) -> Result<Self, String> {
+        Self::new(
+            fields.home_advantage,
+            fields.rho,
+            fields.max_goals,
+            fields.seed,
+            fields.n_sims,
+            fields.model.as_deref(),
+            fields.dispersion,
+            fields.lambda_3,
+        )
+    }
+}
+
+impl From<SimulationConfig> for ConfigFields {
+    fn from(config: SimulationConfig) -> Self {
+        ConfigFields {
+            home_advantage: config.home_advantage,
+            rho: Some(config.rho),
+            max_goals: Some(config.max_goals),
+            seed: config.seed,
+            n_sims: Some(config.n_sims),
+            model: Some(config.score_model.name().to_string()),
+            dispersion: config.dispersion(),
+            lambda_3: config.lambda_3(),
+        }
+    }
+}
+
+impl SimulationConfig {
+    const KEYS: [&'static str; 8] = [
+        "home_advantage",
+        "rho",
+        "max_goals",
+        "seed",
+        "n_sims",
+        "model",
+        "dispersion",
+        "lambda_3",
+    ];
+
+    /// Builds and validates a config; unset fields take the `simulate_bulk` defaults.
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        home_advantage: Option<f64>,
+        rho: Option<f64>,
+        max_goals: Option<usize>,
+        seed: Option<u64>,
+        n_sims: Option<usize>,
+        model: Option<&str>,
+        dispersion: Option<f64>,
+        lambda_3: Option<f64>,
+    ) -> Result<Self, String> {
+        let model_config = ModelConfig::validated(rho, home_advantage, max_goals)?;
+        let n_sims = n_sims.unwrap_or(DEFAULT_CONFIG_SIMULATIONS);
+        if n_sims == 0 {
+            return Err("n_sims must be at least 1".to_string());
+        }
+        Ok(SimulationConfig {
+            home_advantage: model_config.home_advantage,
+            rho: model_config.rho,
+            max_goals: model_config.max_goals,
+            seed,
+            n_sims,
+            score_model: ScoreModel::parse(model, dispersion, lambda_3)?,
+        })
+    }
+
+    fn model_config(&self) -> ModelConfig {
+        ModelConfig {
+            rho: self.rho,
+            home_advantage: self.home_advantage,
+            max_goals: self.max_goals,
+            score_model: self.score_model,
+            red_cards: None,
+        }
+    }
+
+    fn dispersion(&self) -> Option<f64> {
+        match self.score_model {
+            ScoreModel::NegativeBinomial { dispersion } => Some(dispersion),
+            _ => None,
+        }
+    }
+
+    fn lambda_3(&self) -> Option<f64> {
+        match self.score_model {
+            ScoreModel::BivariatePoisson { lambda_3 } => Some(lambda_3),
+            _ => None,
+        }
+    }
+
+    fn from_dict(config: &PyDict) -> PyResult<Self> {
+        for key in config.keys() {
+            let key: String = key.extract()?;
+            if !Self::KEYS.contains(&key.as_str()) {
+                return Err(PyValueError::new_err(format!("Unknown config key {key:?}")));
+            }
+        }
+        fn item<'a, T: FromPyObject<'a>>(config: &'a PyDict, key: &str) -> PyResult<Option<T>> {
+            match config.get_item(key) {
+                Some(value) if !value.is_none() => Ok(Some(value.extract()?)),
+                _ => Ok(None),
+            }
+        }
+        let model: Option<String> = item(config, "model")?;
+        Self::new(
+            item(config, "home_advantage")?,
+            item(config, "rho")?,
+            item(config, "max_goals")?,
+            item(config, "seed")?,
+            item(config, "n_sims")?,
+            model.as_deref(),
+            item(config, "dispersion")?,
+            item(config, "lambda_3")?,
+        )
+        .map_err(PyValueError::new_err)
+    }
+
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        let dict = PyDict::new(py);
+        dict.set_item("home_advantage", self.home_advantage)?;
+        dict.set_item("rho", self.rho)?;
+        dict.set_item("max_goals", self.max_goals)?;
+        dict.set_item("seed", self.seed)?;
+        dict.set_item("n_sims", self.n_sims)?;
+        dict.set_item("model", self.score_model.name())?;
+        dict.set_item("dispersion", self.dispersion())?;
+        dict.set_item("lambda_3", self.lambda_3())?;
+        Ok(dict)
+    }
+}
+
+/// Validates a config dict and serializes it to JSON.
+#[pyfunction]
+fn config_to_json(config: &PyDict) -> PyResult<String> {
+    let config = SimulationConfig::from_dict(config)?;
+    serde_json::to_string(&config).map_err(|err| PyValueError::new_err(err.to_string()))
+}
+
+/// Parses JSON written by `config_to_json` back into a config dict with every key present.
+#[pyfunction]
+fn config_from_json(py: Python, json: &str) -> PyResult<PyObject> {
+    let config: SimulationConfig = serde_json::from_str(json)
+        .map_err(|err| PyValueError::new_err(format!("Invalid config JSON: {err}")))?;
+    Ok(config.to_dict(py)?.into())
+}
+
+/// `simulate_bulk` driven by a saved config dict instead of individual arguments.
+#[pyfunction(top_k_tables = "DEFAULT_TOP_K_TABLES")]
+fn simulate_bulk_from_config(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    home_table: PyObject,
+    away_table: PyObject,
+    config: &PyDict,
+    top_k_tables: usize,
+) -> PyResult<PyObject> {
+    let config = SimulationConfig::from_dict(config)?;
+    let input = parse_bulk_input(
+        py,
+        (base_table, fixtures, home_table, away_table),
+        &config.model_config(),
+        None,
+    )?;
+    let base_seed = config.seed.unwrap_or_else(rand::random);
+    let options = BulkOptions {
+        top_k_tables,
+        ..BulkOptions::default()
+    };
+    let n_sims = config.n_sims;
+    let tally = py.allow_threads(|| run_bulk_simulations(&input, n_sims, base_seed, &options));
+    bulk_result(py, &input, tally, top_k_tables)
+}
+
+/// Seasons completed so far by the running (or most recent) `simulate_bulk` call.
+#[pyfunction]
+fn simulation_progress() -> usize {
+    SIMULATION_PROGRESS.load(AtomicOrdering::Relaxed)
+}
+
+#[pyfunction(
+    points_distribution = false,
+    return_record = false,
+    return_goals = false,
+    return_clean_sheets = false,
+    antithetic = false,
+    stratified = false,
+    timeout_ms = "None",
+    chunk_size = "None",
+    n_threads = "None",
+    return_full_history = false
+)]
+#[allow(clippy::too_many_arguments)]
+fn simulate_bulk(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    home_table: PyObject,
+    away_table: PyObject,
+    n_sims: usize,
+    seed: Option<u64>,
+    top_k_tables: usize,
+    rho: Option<f64>,
+    home_advantage: Option<f64>,
+    max_goals: Option<usize>,
+    points_distribution: bool,
+    deductions: Option<HashMap<String, i64>>,
+    checkpoint_every: Option<usize>,
+    checkpoint_path: Option<String>,
+    tiebreaker_stages: Option<&PyList>,
+    return_record: bool,
+    return_goals: bool,
+    return_clean_sheets: bool,
+    antithetic: bool,
+    stratified: bool,
+    timeout_ms: Option<u64>,
+    chunk_size: Option<usize>,
+    n_threads: Option<usize>,
+    return_full_history: bool,
+) -> PyResult<PyObject> {
+    if chunk_size == Some(0) {
+        return Err(PyValueError::new_err("chunk_size must be positive"));
+    }
+    let config = ModelConfig::from_args(rho, home_advantage, max_goals)?;
+    let mut input = parse_bulk_input(
+        py,
+        (base_table, fixtures, home_table, away_table),
+        &config,
+        deductions.as_ref(),
+    )?;
+    if let Some(stages) = tiebreaker_stages {
+        input.tiebreakers = parse_tiebreaker_stages(stages)?;
+    }
+    let base_seed = seed.unwrap_or_else(rand::random);
+    let options = BulkOptions {
+        top_k_tables,
+        points_distribution,
+        record: return_record,
+        goals: return_goals,
+        clean_sheets: return_clean_sheets,
+        antithetic,
+        deadline: timeout_ms.map(|ms| Instant::now() + Duration::from_millis(ms)),
+        chunk_size,
+        full_history: return_full_history,
+    };
+    let Some(path) = checkpoint_path else {
+        if checkpoint_every.is_some() {
+            return Err(PyValueError::new_err(
+                "checkpoint_every requires checkpoint_path",
+            ));
+        }
+        if antithetic && stratified {
+            return Err(PyValueError::new_err(
+                "antithetic and stratified cannot be combined",
+            ));
+        }
+        if stratified && timeout_ms.is_some() {
+            return Err(PyValueError::new_err(
+                "timeout_ms cannot be combined with stratified",
+            ));
+        }
+        let tally = py
+            .allow_threads(|| {
+                with_thread_count(n_threads, || {
+                    if stratified {
+                        run_bulk_stratified(&input, n_sims, base_seed, &options)
+                    } else {
+                        Ok(run_bulk_simulations(&input, n_sims, base_seed, &options))
+                    }
+                })
+            })
+            .map_err(PyRuntimeError::new_err)?
+            .map_err(PyValueError::new_err)?;
+        if timeout_ms.is_some() {
+            let completed = tally.seasons() as usize;
+            let result = bulk_result(py, &input, tally, top_k_tables)?;
+            return Ok((result, completed).into_py(py));
+        }
+        return bulk_result(py, &input, tally, top_k_tables);
+    };
+    if points_distribution
+        || return_record
+        || return_goals
+        || return_clean_sheets
+        || antithetic
+        || stratified
+        || timeout_ms.is_some()
+        || return_full_history
+    {
+        return Err(PyValueError::new_err(
+            "points_distribution, antithetic, stratified, timeout_ms and the return_* options \
+             cannot be combined with checkpoints",
+        ));
+    }
+    let start = BulkCheckpoint {
+        base_seed,
+        completed: 0,
+        settings: CheckpointSettings::new(&config, &input.tiebreakers, deductions.as_ref()),
+        tally: BulkTally::new(input.teams.len(), &options),
+    };
+    let finished = py
+        .allow_threads(|| {
+            with_thread_count(n_threads, || {
+                start.extend(
+                    &input,
+                    n_sims,
+                    &options,
+                    std::path::Path::new(&path),
+                    checkpoint_every,
+                )
+            })
+        })
+        .map_err(PyRuntimeError::new_err)?
+        .map_err(PyIOError::new_err)?;
+    bulk_result(py, &input, finished.tally, top_k_tables)
+}
+
+/// Runs `work` on a dedicated pool of `n_threads` threads (`0` meaning one per CPU), or on the
+/// global pool when `n_threads` is `None` or already matches its size.
+fn with_thread_count<T: Send>(
+    n_threads: Option<usize>,
+    work: impl FnOnce() -> T + Send,
+) -> Result<T, String> {
+    let Some(requested) = n_threads else {
+        return Ok(work());
+    };
+    let threads = if requested == 0 {
+        num_cpus::get()
+    } else {
+        requested
+    };
+    if threads == rayon::current_num_threads() {
+        return Ok(work());
+    }
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .map_err(|err| format!("Could not start {threads} simulation threads: {err}"))?;
+    Ok(pool.install(work))
+}
+
+/// Like `simulate_bulk`, but season `i` draws its scores from point `i` of a Sobol sequence
+/// (one coordinate per fixture) under a random digital shift from `seed`, instead of from
+/// independent random numbers. Estimates converge faster than plain Monte Carlo.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn simulate_bulk_qmc(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    home_table: PyObject,
+    away_table: PyObject,
+    n_sims: usize,
+    seed: Option<u64>,
+    top_k_tables: usize,
+    rho: Option<f64>,
+    home_advantage: Option<f64>,
+    max_goals: Option<usize>,
+) -> PyResult<PyObject> {
+    let config = ModelConfig::from_args(rho, home_advantage, max_goals)?;
+    let input = parse_simulation_input(py, base_table, fixtures, home_table, away_table, &config)?;
+    let base_seed = seed.unwrap_or_else(rand::random);
+    let options = BulkOptions {
+        top_k_tables,
+        ..BulkOptions::default()
+    };
+    let tally = py
+        .allow_threads(|| run_bulk_qmc(&input, n_sims, base_seed, &options))
+        .map_err(PyValueError::new_err)?;
+    bulk_result(py, &input, tally, top_k_tables)
+}
+
+/// Like `simulate_bulk`, with some remaining fixtures fixed in advance: `fixed_results` maps a
+/// fixture index to its `(home_goals, away_goals)` score, and only the others are simulated.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn simulate_season_conditional(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    fixed_results: HashMap<usize, (usize, usize)>,
+    home_table: PyObject,
+    away_table: PyObject,
+    n_sims: usize,
+    seed: Option<u64>,
+    top_k_tables: usize,
+    rho: Option<f64>,
+    home_advantage: Option<f64>,
+    max_goals: Option<usize>,
+) -> PyResult<PyObject> {
+    let config = ModelConfig::from_args(rho, home_advantage, max_goals)?;
+    let league = parse_league(py, base_table, home_table, away_table, &config)?;
+    let fixtures_list: &PyList = fixtures.extract(py)?;
+    let mut fixtures = parse_fixture_pairs(fixtures_list, &league.team_to_idx)?;
+    fix_results(&mut fixtures, &fixed_results).map_err(PyValueError::new_err)?;
+    let input = league.simulation_input(&fixtures, &config);
+
+    let base_seed = seed.unwrap_or_else(rand::random);
+    let options = BulkOptions {
+        top_k_tables,
+        ..BulkOptions::default()
+    };
+    let tally = py.allow_threads(|| run_bulk_simulations(&input, n_sims, base_seed, &options));
+    bulk_result(py, &input, tally, top_k_tables)
+}
+
+/// `[team][position]` finish probability under scenario A minus scenario B, each a set of
+/// `fixed_results` as for `simulate_season_conditional`, simulated from `seeds[0]` and `seeds[1]`.
+fn scenario_delta(
+    league: &League,
+    fixtures: &[ParsedFixture],
+    scenarios: [&HashMap<usize, (usize, usize)>; 2],
+    config: &ModelConfig,
+    n_sims: usize,
+    seeds: [u64; 2],
+) -> Result<Vec<Vec<f64>>, String> {
+    let mut shares = Vec::with_capacity(2);
+    for (fixed_results, seed) in scenarios.into_iter().zip(seeds) {
+        let mut fixtures = fixtures.to_vec();
+        fix_results(&mut fixtures, fixed_results)?;
+        let input = league.clone().simulation_input(&fixtures, config);
+        let tally = run_bulk_simulations(&input, n_sims, seed, &BulkOptions::default());
+        shares.push(tally.position_counts);
+    }
+    Ok(shares[0]
+        .iter()
+        .zip(&shares[1])
+        .map(|(a, b)| {
+            a.iter()
+                .zip(b)
+                .map(|(&a, &b)| (a as f64 - b as f64) / n_sims as f64)
+                .collect()
+        })
+        .collect())
+}
+
+/// How two scenarios of fixed results change every team's finishing-position probabilities.
+/// Both run from the same seed, so the seasons differ only where the fixed results do; the
+/// result maps `team -> position -> P(A) - P(B)`.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn simulate_bulk_scenario_delta(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    scenario_a: HashMap<usize, (usize, usize)>,
+    scenario_b: HashMap<usize, (usize, usize)>,
+    n_sims: usize,
+    seed: Option<u64>,
+    home_table: Option<PyObject>,
+    away_table: Option<PyObject>,
+    rho: Option<f64>,
+    home_advantage: Option<f64>,
+    max_goals: Option<usize>,
+) -> PyResult<PyObject> {
+    if n_sims == 0 {
+        return Err(PyValueError::new_err("n_sims must be positive"));
+    }
+    let (config, league) = match (home_table, away_table) {
+        (Some(home_table), Some(away_table)) => {
+            let config = ModelConfig::from_args(rho, home_advantage, max_goals)?;
+            let league = parse_league(py, base_table, home_table, away_table, &config)?;
+            (config, league)
+        }
+        (None, None) => {
+            let config = ModelConfig::from_args(
+                rho,
+                Some(home_advantage.unwrap_or(HOME_ADVANTAGE)),
+                max_goals,
+            )?;
+            let league = parse_base_league(py, base_table, &config)?;
+            (config, league)
+        }
+        _ => {
+            return Err(PyValueError::new_err(
+                "pass both home_table and away_table or neither",
+            ))
+        }
+    };
+    let fixtures_list: &PyList = fixtures.extract(py)?;
+    let fixtures = parse_fixture_pairs(fixtures_list, &league.team_to_idx)?;
+    let base_seed = seed.unwrap_or_else(rand::random);
+    let deltas = py
+        .allow_threads(|| {
+            scenario_delta(
+                &league,
+                &fixtures,
+                [&scenario_a, &scenario_b],
+                &config,
+                n_sims,
+                [base_seed; 2],
+            )
+        })
+        .map_err(PyValueError::new_err)?;
+
+    let result = PyDict::new(py);
+    for (team, deltas) in league.teams.iter().zip(deltas) {
+        let positions = PyDict::new(py);
+        for (position, delta) in deltas.into_iter().enumerate() {
+            positions.set_item(position + 1, delta)?;
+        }
+        result.set_item(team, positions)?;
+    }
+    Ok(result.into())
+}
+
+/// How much one remaining fixture swings the title race.
+#[derive(Debug, Clone, PartialEq)]
+struct FixtureImportance {
+    fixture: usize,
+    /// Home side's title probability after a home win minus after an away win.
+    delta_home_title: f64,
+    /// Away side's title probability after a home win minus after an away win.
+    delta_away_title: f64,
+    /// Title probability that changes hands between the two outcomes, summed over every team
+    /// and halved, so a fixture that decides the title outright scores `1`.
+    importance_score: f64,
+}
+
+/// Scores every fixture still to be simulated by replaying the season with it pinned to a
+/// 1-0 home win and to a 0-1 away win, all from `seed`, most important first.
+fn fixture_importance_scores(
+    league: &League,
+    fixtures: &[ParsedFixture],
+    config: &ModelConfig,
+    n_sims: usize,
+    seed: u64,
+) -> Vec<FixtureImportance> {
+    let title_shares = |idx: usize, score: (usize, usize)| -> Vec<f64> {
+        let mut fixtures = fixtures.to_vec();
+        fixtures[idx].result = Some(score);
+        let input = league.clone().simulation_input(&fixtures, config);
+        run_bulk_simulations(&input, n_sims, seed, &BulkOptions::default())
+            .position_counts
+            .iter()
+            .map(|counts| counts[0] as f64 / n_sims as f64)
+            .collect()
+    };
+    let mut scores: Vec<FixtureImportance> = fixtures
+        .iter()
+        .enumerate()
+        .filter(|(_, fixture)| fixture.result.is_none())
+        .map(|(idx, fixture)| {
+            let home_win = title_shares(idx, (1, 0));
+            let away_win = title_shares(idx, (0, 1));
+            let swing = |team: usize| home_win[team] - away_win[team];
+            let moved: f64 = (0..home_win.len()).map(|team| swing(team).abs()).sum();
+            FixtureImportance {
+                fixture: idx,
+                delta_home_title: swing(fixture.home_idx),
+                delta_away_title: swing(fixture.away_idx),
+                importance_score: moved / 2.0,
+            }
+        })
+        .collect();
+    scores.sort_by(|a, b| b.importance_score.total_cmp(&a.importance_score));
+    scores
+}
+
+/// Which remaining fixtures move the title odds most. Each is simulated as a home win and as an
+/// away win from the same seed; the result lists `{"home", "away", "delta_home_title",
+/// "delta_away_title", "importance_score"}` dicts, most important first.
+#[pyfunction]
+fn fixture_importance(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    n_sims: usize,
+    seed: u64,
+) -> PyResult<PyObject> {
+    if n_sims == 0 {
+        return Err(PyValueError::new_err("n_sims must be positive"));
+    }
+    let config = ModelConfig::from_args(None, Some(HOME_ADVANTAGE), None)?;
+    let league = parse_base_league(py, base_table, &config)?;
+    let fixtures_list: &PyList = fixtures.extract(py)?;
+    let fixtures = parse_fixture_pairs(fixtures_list, &league.team_to_idx)?;
+    let scores =
+        py.allow_threads(|| fixture_importance_scores(&league, &fixtures, &config, n_sims, seed));
+
+    let result = PyList::empty(py);
+    for score in scores {
+        let fixture = fixtures[score.fixture];
+        let entry = PyDict::new(py);
+        entry.set_item("home", &league.teams[fixture.home_idx])?;
+        entry.set_item("away", &league.teams[fixture.away_idx])?;
+        entry.set_item("delta_home_title", score.delta_home_title)?;
+        entry.set_item("delta_away_title", score.delta_away_title)?;
+        entry.set_item("importance_score", score.importance_score)?;
+        result.append(entry)?;
+    }
+    Ok(result.into())
+}
+
+/// Season outcome scored by `marginal_fixture_value`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SeasonMetric {
+    Championship,
+    Top4,
+    /// Finishing above the bottom `DEFAULT_RELEGATION_ZONE` places.
+    RelegationSurvival,
+    /// Finishing in the bottom `DEFAULT_RELEGATION_ZONE` places.
+    Relegation,
+}
+
+impl SeasonMetric {
+    fn parse(metric: &str) -> Result<Self, String> {
+        match metric {
+            "championship" => Ok(Self::Championship),
+            "top4" => Ok(Self::Top4),
+            "relegation_survival" => Ok(Self::RelegationSurvival),
+            "relegation" => Ok(Self::Relegation),
+            other => Err(format!(
+                "Unknown metric {other:?}; use championship, top4, relegation_survival or \
+                 relegation"
+            )),
+        }
+    }
+
+    /// Zero-based finishing positions that count towards the metric in an `n_teams` league.
+    fn positions(self, n_teams: usize) -> std::ops::Range<usize> {
+        let relegation_cut = n_teams.saturating_sub(DEFAULT_RELEGATION_ZONE);
+        match self {
+            Self::Championship => 0..1.min(n_teams),
+            Self::Top4 => 0..4.min(n_teams),
+            Self::RelegationSurvival => 0..relegation_cut,
+            Self::Relegation => relegation_cut..n_teams,
+        }
+    }
+
+    /// Share of `n_sims` seasons reaching the metric, from one team's `[position]` counts.
+    fn share(self, position_counts: &[u64], n_sims: usize) -> f64 {
+        let seasons: u64 = position_counts[self.positions(position_counts.len())]
+            .iter()
+            .sum();
+        seasons as f64 / n_sims as f64
+    }
+}
+
+/// What one of `team`'s remaining fixtures is worth towards a season metric.
+#[derive(Debug, Clone, PartialEq)]
+struct MarginalValue {
+    fixture: usize,
+    p_win: f64,
+    p_draw: f64,
+    p_loss: f64,
+    /// The three conditional probabilities weighted by the fixture's own win/draw/loss odds,
+    /// renormalized for the score mass beyond `max_goals`.
+    expected_value: f64,
+}
+
+/// Replays the season with each of `team`'s open fixtures pinned to a 1-0 win, a 1-1 draw and
+/// a 0-1 defeat for `team`, all from `seed`, in fixture order.
+fn marginal_values(
+    league: &League,
+    fixtures: &[ParsedFixture],
+    team: usize,
+    metric: SeasonMetric,
+    config: &ModelConfig,
+    n_sims: usize,
+    seed: u64,
+) -> Vec<MarginalValue> {
+    let open = league.clone().simulation_input(fixtures, config);
+    let metric_share = |idx: usize, score: (usize, usize)| -> f64 {
+        let mut fixtures = fixtures.to_vec();
+        fixtures[idx].result = Some(score);
+        let input = league.clone().simulation_input(&fixtures, config);
+        let tally = run_bulk_simulations(&input, n_sims, seed, &BulkOptions::default());
+        metric.share(&tally.position_counts[team], n_sims)
+    };
+    fixtures
+        .iter()
+        .enumerate()
+        .filter(|(_, fixture)| {
+            fixture.result.is_none() && (fixture.home_idx == team || fixture.away_idx == team)
+        })
+        .map(|(idx, fixture)| {
+            let at_home = fixture.home_idx == team;
+            let from_team = |(ours, theirs): (usize, usize)| {
+                if at_home {
+                    (ours, theirs)
+                } else {
+                    (theirs, ours)
+                }
+            };
+            let (home_win, draw, away_win) =
+                open.fixtures[idx].distribution.outcome_probabilities();
+            let (win_odds, loss_odds) = if at_home {
+                (home_win, away_win)
+            } else {
+                (away_win, home_win)
+            };
+            let p_win = metric_share(idx, from_team((1, 0)));
+            let p_draw = metric_share(idx, (1, 1));
+            let p_loss = metric_share(idx, from_team((0, 1)));
+            MarginalValue {
+                fixture: idx,
+                p_win,
+                p_draw,
+                p_loss,
+                expected_value: (win_odds * p_win + draw * p_draw + loss_odds * p_loss)
+                    / (win_odds + draw + loss_odds),
+            }
+        })
+        .collect()
+}
+
+/// For each of `team`'s remaining fixtures, the probability of `metric` (`"championship"`,
+/// `"top4"` or `"relegation_survival"`) after a win, draw and defeat, and their expectation
+/// under the fixture's own odds. All runs share `seed`.
+#[pyfunction]
+fn marginal_fixture_value(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    team: String,
+    metric: String,
+    n_sims: usize,
+    seed: u64,
+) -> PyResult<PyObject> {
+    if n_sims == 0 {
+        return Err(PyValueError::new_err("n_sims must be positive"));
+    }
+    let metric = SeasonMetric::parse(&metric).map_err(PyValueError::new_err)?;
+    let config = ModelConfig::from_args(None, Some(HOME_ADVANTAGE), None)?;
+    let league = parse_base_league(py, base_table, &config)?;
+    let team = *league
+        .team_to_idx
+        .get(&team)
+        .ok_or_else(|| PyValueError::new_err(format!("Team {team} not found in standings")))?;
+    let fixtures_list: &PyList = fixtures.extract(py)?;
+    let fixtures = parse_fixture_pairs(fixtures_list, &league.team_to_idx)?;
+    let values = py
+        .allow_threads(|| marginal_values(&league, &fixtures, team, metric, &config, n_sims, seed));
+
+    let result = PyList::empty(py);
+    for value in values {
+        let entry = PyDict::new(py);
+        entry.set_item("fixture_idx", value.fixture)?;
+        entry.set_item("p_win", value.p_win)?;
+        entry.set_item("p_draw", value.p_draw)?;
+        entry.set_item("p_loss", value.p_loss)?;
+        entry.set_item("expected_value", value.expected_value)?;
+        result.append(entry)?;
+    }
+    Ok(result.into())
+}
+
+/// Importance-sampled probability of a season outcome.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ImportanceEstimate {
+    probability: f64,
+    std_error: f64,
+    /// `(sum w)^2 / sum w^2` over the likelihood-ratio weights; far below `n_sims` means the
+    /// proposal is a poor match and the estimate is unreliable.
+    effective_sample_size: f64,
+}
+
+/// Plays `n_sims` seasons from `proposal` and weights each by the likelihood ratio of its scores
+/// under `real` over `proposal`, so `team` reaching `metric` is estimated for `real`. Both inputs
+/// must list the same fixtures in the same order. The tuple sums are the weights of hits, their
+/// squares, all weights and all squares.
+fn importance_sampled_probability(
+    real: &SimulationInput,
+    proposal: &SimulationInput,
+    team: usize,
+    metric: SeasonMetric,
+    n_sims: usize,
+    base_seed: u64,
+) -> ImportanceEstimate {
+    let positions = metric.positions(real.teams.len());
+    let (hits, hits_squared, weights, weights_squared) = (0..n_sims)
+        .into_par_iter()
+        .map(|sim_index| {
+            let mut rng = ChaCha8Rng::seed_from_u64(simulation_seed(base_seed, sim_index));
+            let season = simulate_season_recording(proposal, &mut rng, true);
+            let weight: f64 = season
+                .results
+                .iter()
+                .zip(real.fixtures.iter().zip(&proposal.fixtures))
+                .filter(|(result, _)| result.home_idx == team || result.away_idx == team)
+                .map(|(result, (real, proposal))| {
+                    let score = (result.home_goals as usize, result.away_goals as usize);
+                    real.distribution.probability(score.0, score.1)
+                        / proposal.distribution.probability(score.0, score.1)
+                })
+                .product();
+            let position = season.order.iter().position(|&idx| idx == team);
+            let hit = if position.is_some_and(|position| positions.contains(&position)) {
+                weight
+            } else {
+                0.0
+            };
+            (hit, hit * weight, weight, weight * weight)
+        })
+        .reduce(
+            || (0.0, 0.0, 0.0, 0.0),
+            |left, right| {
+                (
+                    left.0 + right.0,
+                    left.1 + right.1,
+                    left.2 + right.2,
+                    left.3 + right.3,
+                )
+            },
+        );
+    if weights <= 0.0 {
+        return ImportanceEstimate {
+            probability: 0.0,
+            std_error: 0.0,
+            effective_sample_size: 0.0,
+        };
+    }
+    // Self-normalised, so the estimate stays within [0, 1]; the error is the delta-method one.
+    let probability = hits / weights;
+    let spread = hits_squared * (1.0 - probability).powi(2)
+        + (weights_squared - hits_squared) * probability.powi(2);
+    ImportanceEstimate {
+        probability,
+        std_error: spread.sqrt() / weights,
+        effective_sample_size: weights * weights / weights_squared,
+    }
+}
+
+/// Rare-event estimate of `target_team` reaching `target_outcome` (see
+/// `marginal_fixture_value`, plus `"relegation"`). Seasons are played with the target team's
+/// expected goals scaled by `proposal_lambda_scale` and reweighted by the likelihood ratio of
+/// its scores, which needs far fewer seasons than `simulate_bulk` for long-shot outcomes.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn simulate_bulk_importance(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    home_table: PyObject,
+    away_table: PyObject,
+    n_sims: usize,
+    seed: Option<u64>,
+    target_team: String,
+    target_outcome: String,
+    proposal_lambda_scale: f64,
+    rho: Option<f64>,
+    home_advantage: Option<f64>,
+    max_goals: Option<usize>,
+) -> PyResult<PyObject> {
+    if n_sims == 0 {
+        return Err(PyValueError::new_err("n_sims must be positive"));
+    }
+    if !(proposal_lambda_scale.is_finite() && proposal_lambda_scale > 0.0) {
+        return Err(PyValueError::new_err(format!(
+            "proposal_lambda_scale must be positive, got {proposal_lambda_scale}"
+        )));
+    }
+    let metric = SeasonMetric::parse(&target_outcome).map_err(PyValueError::new_err)?;
+    let config = ModelConfig::from_args(rho, home_advantage, max_goals)?;
+    let league = parse_league(py, base_table, home_table, away_table, &config)?;
+    let team = *league.team_to_idx.get(&target_team).ok_or_else(|| {
+        PyValueError::new_err(format!("Team {target_team} not found in standings"))
+    })?;
+    let fixtures_list: &PyList = fixtures.extract(py)?;
+    let fixtures = parse_fixture_pairs(fixtures_list, &league.team_to_idx)?;
+    let proposal_fixtures = fixtures
+        .iter()
+        .map(|fixture| {
+            let scale = match (fixture.home_idx == team, fixture.away_idx == team) {
+                (true, _) => (proposal_lambda_scale, 1.0),
+                (_, true) => (1.0, proposal_lambda_scale),
+                _ => (1.0, 1.0),
+            };
+            league
+                .ratings
+                .scaled_fixture_simulation(fixture, &config, scale)
+        })
+        .collect();
+    let real = league.simulation_input(&fixtures, &config);
+    let proposal = SimulationInput {
+        fixtures: proposal_fixtures,
+        ..real.clone()
+    };
+    let base_seed = seed.unwrap_or_else(rand::random);
+    let estimate = py.allow_threads(|| {
+        importance_sampled_probability(&real, &proposal, team, metric, n_sims, base_seed)
+    });
+
+    let result = PyDict::new(py);
+    result.set_item("probability", estimate.probability)?;
+    result.set_item("std_error", estimate.std_error)?;
+    result.set_item("effective_sample_size", estimate.effective_sample_size)?;
+    Ok(result.into())
+}
+
+/// Seasons per batch in `simulate_bulk_converging`.
+const CONVERGENCE_BATCH: usize = 1000;
+
+/// Plays batches of `CONVERGENCE_BATCH` seasons until `team`'s running `metric` share moves by less
+/// than `tolerance` between two consecutive batches, or `max_sims` is reached. Season indices run
+/// on from batch to batch, so the tally equals `run_bulk_simulations` over the seasons played.
+fn converging_tally(
+    input: &SimulationInput,
+    team: usize,
+    metric: SeasonMetric,
+    tolerance: f64,
+    max_sims: usize,
+    base_seed: u64,
+) -> (BulkTally, usize) {
+    let options = BulkOptions::default();
+    SIMULATION_PROGRESS.store(0, AtomicOrdering::Relaxed);
+    let mut tally = BulkTally::new(input.teams.len(), &options);
+    let (mut played, mut previous) = (0, None);
+    while played < max_sims {
+        let batch_end = (played + CONVERGENCE_BATCH).min(max_sims);
+        let batch = run_bulk_range(input, played..batch_end, base_seed, &options);
+        tally = tally.merge(batch, &options);
+        played = batch_end;
+        let estimate = metric.share(&tally.position_counts[team], played);
+        if previous.is_some_and(|previous: f64| (estimate - previous).abs() < tolerance) {
+            break;
+        }
+        previous = Some(estimate);
+    }
+    (tally, played)
+}
+
+/// `simulate_bulk` that picks its own `n_sims`: batches of 1000 seasons are added until
+/// `target_team`'s `target_metric` share (as in `marginal_fixture_value`) changes by less than
+/// `tolerance` from one batch to the next. Returns the result and the seasons played.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn simulate_bulk_converging(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    target_team: String,
+    target_metric: String,
+    tolerance: f64,
+    max_sims: usize,
+    seed: Option<u64>,
+) -> PyResult<(PyObject, usize)> {
+    if max_sims == 0 {
+        return Err(PyValueError::new_err("max_sims must be positive"));
+    }
+    if !(tolerance.is_finite() && tolerance > 0.0) {
+        return Err(PyValueError::new_err(format!(
+            "tolerance must be positive, got {tolerance}"
+        )));
+    }
+    let metric = SeasonMetric::parse(&target_metric).map_err(PyValueError::new_err)?;
+    let config = ModelConfig::from_args(None, Some(HOME_ADVANTAGE), None)?;
+    let league = parse_base_league(py, base_table, &config)?;
+    let team = *league.team_to_idx.get(&target_team).ok_or_else(|| {
+        PyValueError::new_err(format!("Team {target_team} not found in standings"))
+    })?;
+    let fixtures_list: &PyList = fixtures.extract(py)?;
+    let fixtures = parse_fixture_pairs(fixtures_list, &league.team_to_idx)?;
+    let input = league.simulation_input(&fixtures, &config);
+    let base_seed = seed.unwrap_or_else(rand::random);
+    let (tally, played) =
+        py.allow_threads(|| converging_tally(&input, team, metric, tolerance, max_sims, base_seed));
+    Ok((bulk_result(py, &input, tally, 0)?, played))
+}
+
+/// Iterator over simulated seasons, each yielded as the `simulate_season` standings list.
+#[pyclass]
+struct SimulationStream {
+    input: Arc<SimulationInput>,
+    receiver: Receiver<SeasonResult>,
+}
+
+#[pymethods]
+impl SimulationStream {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python) -> PyResult<Option<PyObject>> {
+        let receiver = &mut self.receiver;
+        let Ok(season) = py.allow_threads(move || receiver.recv()) else {
+            return Ok(None);
+        };
+        Ok(Some(standings_list(py, &self.input, &season, None)?.into()))
+    }
+}
+
+/// `(home, away, home_goals, away_goals)` from `{"home", "away", "home_goals", "away_goals"}`.
+fn parse_completed_result(item: &PyAny) -> PyResult<(String, String, i64, i64)> {
+    let dict: &PyDict = item.extract()?;
+    let field = |key: &str| {
+        dict.get_item(key)
+            .ok_or_else(|| PyValueError::new_err(format!("Result missing '{key}'")))
+    };
+    Ok((
+        field("home")?.extract()?,
+        field("away")?.extract()?,
+        field("home_goals")?.extract()?,
+        field("away_goals")?.extract()?,
+    ))
+}
+
+/// `base_table` with `completed_results` added to its M/W/D/L/GF/GA/PTS columns. The header,
+/// row order and any further columns are kept, so the result can go straight back into
+/// `simulate_season`.
+#[pyfunction]
+fn apply_results(
+    py: Python,
+    base_table: PyObject,
+    completed_results: PyObject,
+) -> PyResult<PyObject> {
+    let base: &PyList = base_table.extract(py)?;
+    let (_, mut stats, team_to_idx) = parse_base_table(base)?;
+    let results: &PyList = completed_results.extract(py)?;
+    for item in results.iter() {
+        let (home, away, home_goals, away_goals) = parse_completed_result(item)?;
+        record_played_match(
+            &mut stats,
+            &team_to_idx,
+            (&home, &away),
+            (home_goals, away_goals),
+        )
+        .map_err(PyValueError::new_err)?;
+    }
+
+    let updated = PyList::empty(py);
+    for (idx, row) in base.iter().enumerate() {
+        let row_list: &PyList = row.extract()?;
+        let new_row = PyList::new(py, row_list);
+        updated.append(new_row)?;
+        let Some(stats) = idx.checked_sub(1).map(|team_idx| &stats[team_idx]) else {
+            continue;
+        };
+        let columns = [
+            stats.matches,
+            stats.wins,
+            stats.draws,
+            stats.losses,
+            stats.goals_for,
+            stats.goals_against,
+            stats.points,
+        ];
+        for (column, value) in columns.into_iter().enumerate() {
+            new_row.set_item(column + 1, value)?;
+        }
+    }
+    Ok(updated.into())
+}
+
+/// Checks `fixtures` against `base_table` and returns every problem found as a list of
+/// `{"error_type", "fixture_idx", "message"}` dicts, empty when the fixtures are fine. The
+/// error types are `malformed_fixture`, `unknown_team`, `duplicate_fixture`, `self_play` and
+/// `unbalanced_venues`.
+#[pyfunction]
+fn validate_fixtures(py: Python, base_table: PyObject, fixtures: PyObject) -> PyResult<PyObject> {
+    let base: &PyList = base_table.extract(py)?;
+    let (_, _, team_to_idx) = parse_base_table(base)?;
+    let fixtures_list: &PyList = fixtures.extract(py)?;
+
+    let mut problems = Vec::new();
+    let mut names = Vec::with_capacity(fixtures_list.len());
+    for (fixture_idx, item) in fixtures_list.iter().enumerate() {
+        match fixture_team_names(item) {
+            Ok((home, away, _)) => names.push((fixture_idx, (home, away))),
+            Err(err) => problems.push(FixtureProblem {
+                error_type: "malformed_fixture",
+                fixture_idx,
+                message: err.value(py).to_string(),
+            }),
+        }
+    }
+    problems.extend(fixture_problems(&names, &team_to_idx));
+    problems.sort_by_key(|problem| problem.fixture_idx);
+
+    let result = PyList::empty(py);
+    for problem in problems {
+        let dict = PyDict::new(py);
+        dict.set_item("error_type", problem.error_type)?;
+        dict.set_item("fixture_idx", problem.fixture_idx)?;
+        dict.set_item("message", problem.message)?;
+        result.append(dict)?;
+    }
+    Ok(result.into())
+}
+
+/// `(home, away)` fixture counts of every team.
+fn venue_counts(pairs: &[(usize, usize)], num_teams: usize) -> Vec<(usize, usize)> {
+    let mut counts = vec![(0, 0); num_teams];
+    for &(home, away) in pairs {
+        counts[home].0 += 1;
+        counts[away].1 += 1;
+    }
+    counts
+}
+
+/// Indices of the fixtures to play the other way round so every team's home and away counts
+/// end within one of each other. While some team has two or more surplus home fixtures, the
+/// shortest chain of its home fixtures leading to a team short of home fixtures is reversed,
+/// which moves the surplus along without touching the teams in between. A team two or more
+/// short of home fixtures follows its away fixtures to a team with a surplus the same way.
+/// `rng` breaks ties between equally short chains. Self-play fixtures are never swapped.
+fn balancing_swaps(pairs: &[(usize, usize)], num_teams: usize, rng: &mut impl Rng) -> Vec<usize> {
+    let mut pairs = pairs.to_vec();
+    let mut order: Vec<usize> = (0..pairs.len()).collect();
+    order.shuffle(rng);
+    let mut swapped = vec![false; pairs.len()];
+    loop {
+        let surplus: Vec<i64> = venue_counts(&pairs, num_teams)
+            .into_iter()
+            .map(|(home, away)| home as i64 - away as i64)
+            .collect();
+        let Some(start) = (0..num_teams).find(|&team| surplus[team].abs() >= 2) else {
+            break;
+        };
+        // A surplus walks from hosts to visitors, a shortfall from visitors to hosts.
+        let hosting = surplus[start] > 0;
+        let mut next_fixtures = vec![Vec::new(); num_teams];
+        for &fixture in &order {
+            let (home, away) = pairs[fixture];
+            if home != away {
+                next_fixtures[if hosting { home } else { away }].push(fixture);
+            }
+        }
+        // Fixture that first reached each team, breadth first from `start`.
+        let mut reached_by: Vec<Option<usize>> = vec![None; num_teams];
+        let mut queue = VecDeque::from([start]);
+        let mut end = None;
+        while let Some(team) = queue.pop_front() {
+            let unbalanced_other_way = if hosting {
+                surplus[team] < 0
+            } else {
+                surplus[team] > 0
+            };
+            if unbalanced_other_way {
+                end = Some(team);
+                break;
+            }
+            for &fixture in &next_fixtures[team] {
+                let (home, away) = pairs[fixture];
+                let next = if hosting { away } else { home };
+                if next != start && reached_by[next].is_none() {
+                    reached_by[next] = Some(fixture);
+                    queue.push_back(next);
+                }
+            }
+        }
+        // The teams a surplus reaches cannot all host more than they visit, and the teams a
+        // shortfall reaches cannot all visit more than they host, so the walk always ends.
+        let mut team = end.expect("an unbalanced team leads to one unbalanced the other way");
+        while let Some(fixture) = reached_by[team] {
+            let (home, away) = pairs[fixture];
+            pairs[fixture] = (away, home);
+            swapped[fixture] = !swapped[fixture];
+            team = if hosting { home } else { away };
+        }
+    }
+    (0..pairs.len()).filter(|&idx| swapped[idx]).collect()
+}
+
+/// Team indices of every fixture, failing on malformed fixtures and unknown teams.
+fn fixture_team_indices(
+    fixtures_list: &PyList,
+    team_to_idx: &TeamIndex,
+) -> PyResult<Vec<(usize, usize)>> {
+    fixtures_list
+        .iter()
+        .map(|item| {
+            let (home_name, away_name, _) = fixture_team_names(item)?;
+            let index = |name: &String| {
+                team_to_idx.get(name).copied().ok_or_else(|| {
+                    PyValueError::new_err(format!("Team {name} not found in standings"))
+                })
+            };
+            Ok((index(&home_name)?, index(&away_name)?))
+        })
+        .collect()
+}
+
+/// `{team: {"home", "away"}}` for every team in `base_table` whose home and away fixture
+/// counts in `fixtures` differ by more than one; empty when the schedule is balanced.
+#[pyfunction]
+fn check_home_away_balance(
+    py: Python,
+    fixtures: PyObject,
+    base_table: PyObject,
+) -> PyResult<PyObject> {
+    let base: &PyList = base_table.extract(py)?;
+    let (team_names, _, team_to_idx) = parse_base_table(base)?;
+    let fixtures_list: &PyList = fixtures.extract(py)?;
+    let pairs = fixture_team_indices(fixtures_list, &team_to_idx)?;
+
+    let result = PyDict::new(py);
+    for (team, (home, away)) in team_names
+        .iter()
+        .zip(venue_counts(&pairs, team_names.len()))
+    {
+        if home.abs_diff(away) > 1 {
+            let counts = PyDict::new(py);
+            counts.set_item("home", home)?;
+            counts.set_item("away", away)?;
+            result.set_item(team, counts)?;
+        }
+    }
+    Ok(result.into())
+}
+
+/// `pair` the other way round, as a tuple if it was one and a list otherwise.
+fn reversed_pair(py: Python, pair: &PyAny) -> PyResult<PyObject> {
+    let items: Vec<PyObject> = pair.extract()?;
+    Ok(if pair.is_instance_of::<PyTuple>()? {
+        PyTuple::new(py, items.iter().rev()).into()
+    } else {
+        PyList::new(py, items.iter().rev()).into()
+    })
+}
+
+/// A copy of `fixtures` with just enough fixtures played the other way round that every
+/// team's home and away counts end within one of each other. Swapped fixtures keep their
+/// format: pairs are reversed, SofaScore dicts get their `"h"` and `"a"` (and any `"result"`)
+/// exchanged. `seed` picks among equally small sets of swaps.
+#[pyfunction(seed = "None")]
+fn fix_home_away_balance(
+    py: Python,
+    fixtures: PyObject,
+    base_table: PyObject,
+    seed: Option<u64>,
+) -> PyResult<PyObject> {
+    let base: &PyList = base_table.extract(py)?;
+    let (team_names, _, team_to_idx) = parse_base_table(base)?;
+    let fixtures_list: &PyList = fixtures.extract(py)?;
+    let pairs = fixture_team_indices(fixtures_list, &team_to_idx)?;
+    let mut rng = ChaCha8Rng::seed_from_u64(seed.unwrap_or_else(rand::random));
+    let swaps: HashSet<usize> = balancing_swaps(&pairs, team_names.len(), &mut rng)
+        .into_iter()
+        .collect();
+
+    let result = PyList::empty(py);
+    for (idx, item) in fixtures_list.iter().enumerate() {
+        if !swaps.contains(&idx) {
+            result.append(item)?;
+            continue;
+        }
+        if let Ok(dict) = item.downcast::<PyDict>() {
+            let flipped = dict.copy()?;
+            flipped.set_item("h", dict.get_item("a"))?;
+            flipped.set_item("a", dict.get_item("h"))?;
+            if let Some(score) = dict.get_item("result").filter(|score| !score.is_none()) {
+                flipped.set_item("result", reversed_pair(py, score)?)?;
+            }
+            result.append(flipped)?;
+        } else {
+            result.append(reversed_pair(py, item)?)?;
+        }
+    }
+    Ok(result.into())
+}
+
+/// A season kept in Rust between calls for live use: results are added as they come in and the
+/// rest of the season is re-simulated without sending the tables again. Teams stay rated from
+/// the tables given to the constructor.
+#[pyclass]
+struct LiveSeason {
+    league: League,
+    config: ModelConfig,
+}
+
+#[pymethods]
+impl LiveSeason {
+    #[new]
+    fn new(
+        py: Python,
+        base_table: PyObject,
+        home_table: PyObject,
+        away_table: PyObject,
+    ) -> PyResult<Self> {
+        let config = ModelConfig::from_args(None, None, None)?;
+        let league = parse_league(py, base_table, home_table, away_table, &config)?;
+        Ok(LiveSeason { league, config })
+    }
+
+    fn add_result(
+        &mut self,
+        home: &str,
+        away: &str,
+        home_goals: i64,
+        away_goals: i64,
+    ) -> PyResult<()> {
+        self.league
+            .record_result(home, away, home_goals, away_goals)
+            .map_err(PyValueError::new_err)
+    }
+
+    /// The table so far, in `simulate_season` standings format.
+    fn standings(&self, py: Python) -> PyResult<PyObject> {
+        let input = self.league.clone().simulation_input(&[], &self.config);
+        let season = simulate_season_recording(&input, &mut ChaCha8Rng::seed_from_u64(0), false);
+        Ok(standings_list(py, &input, &season, None)?.into())
+    }
+
+    /// `simulate_bulk` output for the `remaining_fixtures` from the table so far.
+    #[args(seed = "None")]
+    fn simulate_remaining(
+        &self,
+        py: Python,
+        remaining_fixtures: PyObject,
+        n_sims: usize,
+        seed: Option<u64>,
+    ) -> PyResult<PyObject> {
+        let fixtures_list: &PyList = remaining_fixtures.extract(py)?;
+        let fixtures = parse_fixture_pairs(fixtures_list, &self.league.team_to_idx)?;
+        let input = self
+            .league
+            .clone()
+            .simulation_input(&fixtures, &self.config);
+        let base_seed = seed.unwrap_or_else(rand::random);
+        let options = BulkOptions::default();
+        let tally = py.allow_threads(|| run_bulk_simulations(&input, n_sims, base_seed, &options));
+        bulk_result(py, &input, tally, 0)
+    }
+}
+
+/// Like `simulate_bulk`, but returns a `SimulationStream` of individual seasons instead
+/// of waiting for the aggregate.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn simulate_stream(
+    py: Python,
+    base_table: PyObject,
+    fixtures: PyObject,
+    home_table: PyObject,
+    away_table: PyObject,
+    n_sims: usize,
+    seed: Option<u64>,
+    rho: Option<f64>,
+    home_advantage: Option<f64>,
+    max_goals: Option<usize>,
+    deductions: Option<HashMap<String, i64>>,
+) -> PyResult<SimulationStream> {
+    let config = ModelConfig::from_args(rho, home_advantage, max_goals)?;
+    let input = Arc::new(parse_bulk_input(
+        py,
+        (base_table, fixtures, home_table, away_table),
+        &config,
+        deductions.as_ref(),
+    )?);
+    let base_seed = seed.unwrap_or_else(rand::random);
+    Ok(SimulationStream {
+        receiver: stream_seasons(Arc::clone(&input), n_sims, base_seed),
+        input,
+    })
+}
+
+/// Continues a checkpointed `simulate_bulk` run for `remaining_sims` more seasons with the
+/// checkpoint's seed and tiebreakers, so the result matches one uninterrupted run of the
+/// combined length. The model settings and deductions must match the original call.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn simulate_bulk_resume(
+    py: Python,
+    checkpoint_path: &str,
+    remaining_sims: usize,
+    base_table: PyObject,
+    fixtures: PyObject,
+    home_table: PyObject,
+    away_table: PyObject,
+    top_k_tables: usize,
+    rho: Option<f64>,
+    home_advantage: Option<f64>,
+    max_goals: Option<usize>,
+    deductions: Option<HashMap<String, i64>>,
+    checkpoint_every: Option<usize>,
+) -> PyResult<PyObject> {
+    let config = ModelConfig::from_args(rho, home_advantage, max_goals)?;
+    let mut input = parse_bulk_input(
+        py,
+        (base_table, fixtures, home_table, away_table),
+        &config,
+        deductions.as_ref(),
+    )?;
+    let path = std::path::Path::new(checkpoint_path);
+    let checkpoint = BulkCheckpoint::load(path).map_err(PyIOError::new_err)?;
+    input.tiebreakers = checkpoint.settings.tiebreakers.clone();
+    let settings = CheckpointSettings::new(&config, &input.tiebreakers, deductions.as_ref());
+    if let Some(setting) = checkpoint.settings.mismatch(&settings) {
+        return Err(PyValueError::new_err(format!(
+            "Checkpoint {checkpoint_path} was saved with different {setting}"
+        )));
+    }
+    if checkpoint.tally.position_counts.len() != input.teams.len() {
+        return Err(PyValueError::new_err(format!(
+            "Checkpoint has {} teams but the table has {}",
+            checkpoint.tally.position_counts.len(),
+            input.teams.len()
+        )));
+    }
+    let options = BulkOptions {
+        top_k_tables,
+        ..BulkOptions::default()
+    };
+    let finished = py
+        .allow_threads(|| {
+            checkpoint.extend(&input, remaining_sims, &options, path, checkpoint_every)
+        })
+        .map_err(PyIOError::new_err)?;
+    bulk_result(py, &input, finished.tally, top_k_tables)
+}
+
+/// `{"position_counts", "top_tables"}` as returned by `simulate_bulk`.
+fn bulk_result(
+    py: Python,
+    input: &SimulationInput,
+    tally: BulkTally,
+    top_k_tables: usize,
+) -> PyResult<PyObject> {
+    let result = PyDict::new(py);
+    let position_counts = PyDict::new(py);
+    for (team_idx, team_name) in input.teams.iter().enumerate() {
+        let positions = PyDict::new(py);
+        for (pos_idx, &count) in tally.position_counts[team_idx].iter().enumerate() {
+            positions.set_item(pos_idx + 1, count)?;
+        }
+        if tally.points.is_none()
+            && tally.records.is_none()
+            && tally.goals.is_none()
+            && tally.clean_sheets.is_none()
+        {
+            position_counts.set_item(team_name, positions)?;
+            continue;
+        }
+        let entry = PyDict::new(py);
+        entry.set_item("positions", positions)?;
+        if let Some(points) = &tally.points {
+            entry.set_item("points", points[team_idx].clone())?;
+        }
+        if let Some(records) = &tally.records {
+            let record = PyDict::new(py);
+            for (key, histogram) in ["wins", "draws", "losses"].iter().zip(&records[team_idx]) {
+                record.set_item(key, histogram.clone())?;
+            }
+            entry.set_item("record", record)?;
+        }
+        if let Some(goals) = &tally.goals {
+            let [goals_for, goals_against] = &goals[team_idx];
+            entry.set_item("goals_for", goals_for.clone())?;
+            entry.set_item("goals_against", goals_against.clone())?;
+        }
+        if let Some(clean_sheets) = &tally.clean_sheets {
+            entry.set_item("clean_sheets", clean_sheets[team_idx].clone())?;
+        }
+        position_counts.set_item(team_name, entry)?;
+    }
+
+    let mut top_tables: Vec<(Vec<usize>, u64)> = tally.tables.into_iter().collect();
+    top_tables.sort_by_key(|table| Reverse(table.1));
+    if top_k_tables > 0 {
+        top_tables.truncate(top_k_tables);
+    }
+    let top_tables_py = PyList::empty(py);
+    for (table, count) in top_tables {
+        let entry = PyDict::new(py);
+        let ordered_names: Vec<&String> = table.iter().map(|&idx| &input.teams[idx]).collect();
+        entry.set_item("table", ordered_names)?;
+        entry.set_item("count", count)?;
+        top_tables_py.append(entry)?;
+    }
+
+    result.set_item("position_counts", position_counts)?;
+    result.set_item("top_tables", top_tables_py)?;
+    if let Some(history) = tally.history {
+        let history_py = PyList::empty(py);
+        for positions in history {
+            let finishes: Vec<usize> = positions.into_iter().map(|pos_idx| pos_idx + 1).collect();
+            history_py.append(finishes)?;
+        }
+        result.set_item("position_history", history_py)?;
+    }
+    Ok(result.into())
+}
+
+#[pyfunction(locale = "\"en\"")]
+fn format_standings(py: Python, result: PyObject, locale: &str) -> PyResult<PyObject> {
+    let lang = Language::from_code(locale).ok_or_else(|| {
+        PyValueError::new_err(format!(
+            "Unsupported locale '{locale}', expected one of en, de, es, fr"
+        ))
+    })?;
+    let rows: &PyList = result.extract(py)?;
+    let mut standings: Vec<(String, Stats)> = Vec::with_capacity(rows.len());
+    for row in rows.iter() {
+        let (team, dict): (String, &PyDict) = row.extract()?;
+        standings.push((
+            team,
+            Stats {
+                matches: extract_standings_stat(dict, "M")?,
+                wins: extract_standings_stat(dict, "W")?,
+                draws: extract_standings_stat(dict, "D")?,
+                losses: extract_standings_stat(dict, "L")?,
+                goals_for: extract_standings_stat(dict, "GF")?,
+                goals_against: extract_standings_stat(dict, "GA")?,
+                points: extract_standings_stat(dict, "PTS")?,
+            },
+        ));
+    }
+
+    let locale = StandingsLocale::new(lang);
+    let header = standings_header(&locale);
+    let formatted = PyList::empty(py);
+    for row in format_standings_locale(&standings, &locale) {
+        let dict = PyDict::new(py);
+        for label in &header {
+            dict.set_item(label, &row[label])?;
+        }
+        formatted.append(dict)?;
+    }
+    Ok(formatted.into())
+}
+
+/// Kick-by-kick shootout: five each, then sudden death. `true` when the first team wins.
+/// Sudden death that can never separate the sides (both always or never score) is a coin toss.
+fn penalty_shootout<R: Rng>(rng: &mut R, p_first: f64, p_second: f64) -> bool {
+    let (mut first, mut second) = (0u32, 0u32);
+    for kick in 0..5u32 {
+        first += u32::from(rng.gen_bool(p_first));
+        if first > second + (5 - kick) || second > first + (4 - kick) {
+            return first > second;
+        }
+        second += u32::from(rng.gen_bool(p_second));
+        if first > second + (4 - kick) || second > first + (4 - kick) {
+            return first > second;
+        }
+    }
+    let deadlocked = p_first == p_second && (p_first == 0.0 || p_first == 1.0);
+    if deadlocked {
+        return rng.gen_bool(0.5);
+    }
+    loop {
+        let first_scores = rng.gen_bool(p_first);
+        let second_scores = rng.gen_bool(p_second);
+        if first_scores != second_scores {
+            return first_scores;
+        }
+    }
+}
+
+/// Teams and attack/defense ratings for a knockout bracket, from `(gf, ga, m)` records.
+struct Playoff {
+    teams: Vec<String>,
+    bracket: Vec<(usize, usize)>,
+    avg_goals: f64,
+    ratings: Vec<(f64, f64)>,
+    penalty_rate: f64,
+}
+
+impl Playoff {
+    fn new(
+        bracket: &[(String, String)],
+        records: &HashMap<String, (i64, i64, i64)>,
+        penalty_rate: f64,
+    ) -> Result<Self, String> {
+        let mut teams: Vec<String> = Vec::new();
+        let mut index_of = |team: &String| -> Result<usize, String> {
+            if !records.contains_key(team) {
+                return Err(format!("Team {team} has no entry in team_stats"));
+            }
+            Ok(match teams.iter().position(|known| known == team) {
+                Some(idx) => idx,
+                None => {
+                    teams.push(team.clone());
+                    teams.len() - 1
+                }
+            })
+        };
+        let bracket = bracket
+            .iter()
+            .map(|(first, second)| Ok((index_of(first)?, index_of(second)?)))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let goals: i64 = teams.iter().map(|team| records[team].0).sum();
+        let matches: i64 = teams.iter().map(|team| records[team].2).sum();
+        let avg_goals = if matches > 0 {
+            goals as f64 / matches as f64
+        } else {
+            DEFAULT_LAMBDA
+        };
+        let ratings = teams
+            .iter()
+            .map(|team| {
+                let (gf, ga, m) = records[team];
+                if m > 0 && avg_goals > 0.0 {
+                    let per_match = m as f64 * avg_goals;
+                    (gf as f64 / per_match, ga as f64 / per_match)
+                } else {
+                    (1.0, 1.0)
+                }
+            })
+            .collect();
+        Ok(Playoff {
+            teams,
+            bracket,
+            avg_goals,
+            ratings,
+            penalty_rate,
+        })
+    }
+
+    /// One 90-minute tie hosted by `home`; level scores go to penalties.
+    fn play_tie<R: Rng>(&self, rng: &mut R, home: usize, away: usize) -> usize {
+        let (lambda_h, lambda_a) = calculate_lambdas(
+            self.avg_goals,
+            self.ratings[home],
+            self.ratings[away],
+            HOME_ADVANTAGE,
+        );
+        let pd = DixonColes::get_probability_matrix(lambda_h, lambda_a, DEFAULT_RHO, MAX_GOALS);
+        let (home_goals, away_goals) = DixonColes::simulate_from_distribution(rng, &pd);
+        match home_goals.cmp(&away_goals) {
+            Ordering::Greater => home,
+            Ordering::Less => away,
+            Ordering::Equal if penalty_shootout(rng, self.penalty_rate, self.penalty_rate) => home,
+            Ordering::Equal => away,
+        }
+    }
+
+    /// Winner of the whole bracket; winners of consecutive ties meet next, an odd one out gets a bye.
+    fn simulate<R: Rng>(&self, rng: &mut R) -> usize {
+        let mut survivors: Vec<usize> = self
+            .bracket
+            .iter()
+            .map(|&(home, away)| self.play_tie(rng, home, away))
+            .collect();
+        while survivors.len() > 1 {
+            survivors = survivors
+                .chunks(2)
+                .map(|pair| match *pair {
+                    [home, away] => self.play_tie(rng, home, away),
+                    [bye] => bye,
+                    _ => unreachable!("chunks(2) yields one or two teams"),
+                })
+                .collect();
+        }
+        survivors[0]
+    }
+
+    fn win_counts(&self, n_sims: usize, base_seed: u64) -> Vec<u64> {
+        (0..n_sims)
+            .into_par_iter()
+            .fold(
+                || vec![0u64; self.teams.len()],
+                |mut counts, sim_index| {
+                    let mut rng = ChaCha8Rng::seed_from_u64(simulation_seed(base_seed, sim_index));
+                    counts[self.simulate(&mut rng)] += 1;
+                    counts
+                },
+            )
+            .reduce(
+                || vec![0u64; self.teams.len()],
+                |mut left, right| {
+                    for (left_count, right_count) in left.iter_mut().zip(right) {
+                        *left_count += right_count;
+                    }
+                    left
+                },
+            )
+    }
+}
+
+/// Each team's `(lambda_attack, lambda_defense)`, checking that every team is listed once.
+fn team_lambdas(
+    teams: &[String],
+    lambdas: &HashMap<String, (f64, f64)>,
+) -> Result<Vec<(f64, f64)>, String> {
+    let mut seen = std::collections::HashSet::new();
+    teams
+        .iter()
+        .map(|team| {
+            if !seen.insert(team) {
+                return Err(format!("Team {team} is listed twice"));
+            }
+            let &(attack, defense) = lambdas
+                .get(team)
+                .ok_or_else(|| format!("Team {team} has no entry in team_stats"))?;
+            if !(attack.is_finite() && attack > 0.0 && defense.is_finite() && defense > 0.0) {
+                return Err(format!("{team} lambdas must be positive"));
+            }
+            Ok((attack, defense))
+        })
+        .collect()
+}
+
+/// Goals per team per match among the entrants.
+fn mean_attack(rates: &[(f64, f64)]) -> f64 {
+    rates.iter().map(|&(attack, _)| attack).sum::<f64>() / rates.len() as f64
+}
+
+/// A round-robin group of `teams` rated from `(lambda_attack, lambda_defense)` goals per match
+/// against `avg_goals`, by default the group's own scoring rate. A single round is played at
+/// neutral venues; a double round gives every pair a home game each.
+fn group_stage_input(
+    teams: &[String],
+    lambdas: &HashMap<String, (f64, f64)>,
+    double_round_robin: bool,
+    avg_goals: Option<f64>,
+) -> Result<SimulationInput, String> {
+    if teams.len() < 2 {
+        return Err("a group needs at least 2 teams".to_string());
+    }
+    let rates = team_lambdas(teams, lambdas)?;
+    let team_to_idx: TeamIndex = teams
+        .iter()
+        .enumerate()
+        .map(|(idx, team)| (team.clone(), idx))
+        .collect();
+    let avg_league_goals = avg_goals.unwrap_or_else(|| mean_attack(&rates));
+    let (attack, defense): (Vec<f64>, Vec<f64>) = rates
+        .iter()
+        .map(|&(attack, defense)| (attack / avg_league_goals, defense / avg_league_goals))
+        .unzip();
+    let league = League {
+        teams: teams.to_vec(),
+        initial_stats: vec![Stats::default(); teams.len()],
+        team_to_idx,
+        ratings: LeagueRatings {
+            avg_league_goals,
+            home_advantage: HOME_ADVANTAGE,
+            home_attack: attack.clone(),
+            home_defense: defense.clone(),
+            away_attack: attack,
+            away_defense: defense,
+        },
+    };
+    let mut fixtures = Vec::new();
+    for home_idx in 0..teams.len() {
+        for away_idx in 0..teams.len() {
+            if home_idx < away_idx || (double_round_robin && home_idx > away_idx) {
+                fixtures.push(ParsedFixture {
+                    home_idx,
+                    away_idx,
+                    neutral: !double_round_robin,
+                    result: None,
+                });
+            }
+        }
+    }
+    Ok(league.simulation_input(&fixtures, &ModelConfig::default()))
+}
+
+/// `[home * teams + away]` score matrices for every pairing of teams rated by
+/// `(lambda_attack, lambda_defense)`, with expected goals `attack * opposing defense / avg_goals`.
+fn pairwise_matrices(
+    rates: &[(f64, f64)],
+    avg_goals: f64,
+    home_advantage: f64,
+) -> Vec<ProbabilityDistribution> {
+    let ratings: Vec<(f64, f64)> = rates
+        .iter()
+        .map(|&(attack, defense)| (attack / avg_goals, defense / avg_goals))
+        .collect();
+    let mut matrices = Vec::with_capacity(ratings.len() * ratings.len());
+    for home in &ratings {
+        for away in &ratings {
+            let (lambda_h, lambda_a) = calculate_lambdas(avg_goals, *home, *away, home_advantage);
+            matrices.push(DixonColes::get_probability_matrix(
+                lambda_h,
+                lambda_a,
+                DEFAULT_RHO,
+                MAX_GOALS,
+            ));
+        }
+    }
+    matrices
+}
+
+/// Finishing-position counts for a round-robin group. `team_stats` maps each team to its
+/// `(lambda_attack, lambda_defense)` goals scored and conceded per match.
+#[pyfunction(seed = "None", double_round_robin = true)]
+fn simulate_group_stage(
+    py: Python,
+    teams: PyObject,
+    team_stats: PyObject,
+    n_sims: usize,
+    seed: Option<u64>,
+    double_round_robin: bool,
+) -> PyResult<PyObject> {
+    let teams: Vec<String> = teams.extract(py)?;
+    let lambdas: HashMap<String, (f64, f64)> = team_stats.extract(py)?;
+    if n_sims == 0 {
+        return Err(PyValueError::new_err("n_sims must be positive"));
+    }
+    let input = group_stage_input(&teams, &lambdas, double_round_robin, None)
+        .map_err(PyValueError::new_err)?;
+    let base_seed = seed.unwrap_or_else(rand::random);
+    let tally = py
+        .allow_threads(|| run_bulk_simulations(&input, n_sims, base_seed, &BulkOptions::default()));
+
+    let result = PyDict::new(py);
+    for (team, counts) in input.teams.iter().zip(tally.position_counts) {
+        let positions = PyDict::new(py);
+        for (position, count) in counts.into_iter().enumerate() {
+            positions.set_item(position + 1, count)?;
+        }
+        result.set_item(team, positions)?;
+    }
+    Ok(result.into())
+}
+
+/// Knockout bracket win probabilities. `bracket` lists first-round `(home, away)` ties and
+/// `team_stats` maps each team to its `(gf, ga, m)` record.
+#[pyfunction(penalty_rate = "DEFAULT_PENALTY_RATE")]
+fn simulate_playoff(
+    py: Python,
+    bracket: PyObject,
+    team_stats: PyObject,
+    n_sims: usize,
+    seed: Option<u64>,
+    penalty_rate: f64,
+) -> PyResult<PyObject> {
+    let bracket: Vec<(String, String)> = bracket.extract(py)?;
+    let records: HashMap<String, (i64, i64, i64)> = team_stats.extract(py)?;
+    if bracket.is_empty() || n_sims == 0 {
+        return Err(PyValueError::new_err(
+            "bracket must contain at least one tie and n_sims must be positive",
+        ));
+    }
+    let penalty_rate = validate_conversion_rate("penalty_rate", penalty_rate)?;
+    let playoff = Playoff::new(&bracket, &records, penalty_rate).map_err(PyValueError::new_err)?;
+    let base_seed = seed.unwrap_or_else(rand::random);
+    let counts = py.allow_threads(|| playoff.win_counts(n_sims, base_seed));
+
+    let result = PyDict::new(py);
+    for (team, count) in playoff.teams.iter().zip(counts) {
+        result.set_item(team, count as f64 / n_sims as f64)?;
+    }
+    Ok(result.into())
+}
+
+/// A two-legged tie: team 1 hosts the first leg, team 2 the second.
+struct TwoLegTie {
+    first_leg: ProbabilityDistribution,
+    second_leg: ProbabilityDistribution,
+    away_goals_rule: bool,
+    penalty_rate: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct TwoLegTally {
+    team1: u64,
+    team2: u64,
+    level_on_aggregate: u64,
+    shootouts: u64,
+}
+
+impl TwoLegTie {
+    /// Plays both legs and records the outcome; `true` when team 1 advances.
+    fn play<R: Rng>(&self, rng: &mut R, tally: &mut TwoLegTally) -> bool {
+        play_two_legs(
+            rng,
+            [&self.first_leg, &self.second_leg],
+            self.away_goals_rule,
+            self.penalty_rate,
+            tally,
+        )
+    }
+
+    fn simulate(&self, n_sims: usize, base_seed: u64) -> TwoLegTally {
+        (0..n_sims)
+            .into_par_iter()
+            .fold(TwoLegTally::default, |mut tally, sim_index| {
+                let mut rng = ChaCha8Rng::seed_from_u64(simulation_seed(base_seed, sim_index));
+                self.play(&mut rng, &mut tally);
+                tally
+            })
+            .reduce(TwoLegTally::default, |left, right| TwoLegTally {
+                team1: left.team1 + right.team1,
+                team2: left.team2 + right.team2,
+                level_on_aggregate: left.level_on_aggregate + right.level_on_aggregate,
+                shootouts: left.shootouts + right.shootouts,
+            })
+    }
+}
+
+/// Both legs of a tie, team 1 hosting `legs[0]`; `true` when team 1 advances.
+fn play_two_legs<R: Rng>(
+    rng: &mut R,
+    legs: [&ProbabilityDistribution; 2],
+    away_goals_rule: bool,
+    penalty_rate: f64,
+    tally: &mut TwoLegTally,
+) -> bool {
+    let (home1, away1) = DixonColes::simulate_from_distribution(rng, legs[0]);
+    let (home2, away2) = DixonColes::simulate_from_distribution(rng, legs[1]);
+    let team1_aggregate = home1 + away2;
+    let team2_aggregate = away1 + home2;
+    let mut decider = team1_aggregate.cmp(&team2_aggregate);
+    if decider == Ordering::Equal {
+        tally.level_on_aggregate += 1;
+        if away_goals_rule {
+            decider = away2.cmp(&away1);
+        }
+    }
+    let team1_advances = match decider {
+        Ordering::Greater => true,
+        Ordering::Less => false,
+        Ordering::Equal => {
+            tally.shootouts += 1;
+            penalty_shootout(rng, penalty_rate, penalty_rate)
+        }
+    };
+    if team1_advances {
+        tally.team1 += 1;
+    } else {
+        tally.team2 += 1;
+    }
+    team1_advances
+}
+
+/// Advance probabilities for a two-legged tie. Team 1 hosts the first leg (`lambda_h1` is its
+/// expected goals); team 2 hosts the second (`lambda_h2`).
+#[pyfunction(
+    away_goals_rule = false,
+    n_sims = 10000,
+    seed = "None",
+    penalty_rate = "DEFAULT_PENALTY_RATE"
+)]
+#[allow(clippy::too_many_arguments)]
+fn simulate_two_leg_tie(
+    py: Python,
+    lambda_h1: f64,
+    lambda_a1: f64,
+    lambda_h2: f64,
+    lambda_a2: f64,
+    rho: f64,
+    max_goals: usize,
+    away_goals_rule: bool,
+    n_sims: usize,
+    seed: Option<u64>,
+    penalty_rate: f64,
+) -> PyResult<PyObject> {
+    for (name, value) in [
+        ("lambda_h1", lambda_h1),
+        ("lambda_a1", lambda_a1),
+        ("lambda_h2", lambda_h2),
+        ("lambda_a2", lambda_a2),
+    ] {
+        validate_lambda(name, value)?;
+    }
+    if n_sims == 0 {
+        return Err(PyValueError::new_err("n_sims must be positive"));
+    }
+    let penalty_rate = validate_conversion_rate("penalty_rate", penalty_rate)?;
+    let config = ModelConfig::from_args(Some(rho), None, Some(max_goals))?;
+    let tie = TwoLegTie {
+        first_leg: DixonColes::get_probability_matrix(
+            lambda_h1,
+            lambda_a1,
+            config.rho,
+            config.max_goals,
+        ),
+        second_leg: DixonColes::get_probability_matrix(
+            lambda_h2,
+            lambda_a2,
+            config.rho,
+            config.max_goals,
+        ),
+        away_goals_rule,
+        penalty_rate,
+    };
+    let base_seed = seed.unwrap_or_else(rand::random);
+    let tally = py.allow_threads(|| tie.simulate(n_sims, base_seed));
+
+    let seasons = n_sims as f64;
+    let result = PyDict::new(py);
+    result.set_item("team1_advance", tally.team1 as f64 / seasons)?;
+    result.set_item("team2_advance", tally.team2 as f64 / seasons)?;
+    result.set_item("penalties", tally.shootouts as f64 / seasons)?;
+    Ok(result.into())
+}
+
+/// Championship-style promotion playoff: 3rd v 6th and 4th v 5th over two legs with the
+/// higher-placed side at home second, then a one-off final at a neutral venue.
+struct ChampionshipPlayoff {
+    /// Entrants by league position, highest first.
+    teams: Vec<String>,
+    semi_finals: [TwoLegTie; 2],
+    /// `[first * 4 + second]` score matrices for the final, without home advantage.
+    finals: Vec<ProbabilityDistribution>,
+    penalty_rate: f64,
+}
+
+impl ChampionshipPlayoff {
+    /// `ranks` holds the four entrants' league positions; `stats` their `(lambda_attack,
+    /// lambda_defense)`, i.e. goals scored and conceded per match.
+    fn new(
+        ranks: &HashMap<String, usize>,
+        stats: &HashMap<String, (f64, f64)>,
+        penalty_rate: f64,
+    ) -> Result<Self, String> {
+        let mut teams: Vec<String> = ranks.keys().cloned().collect();
+        teams.sort_by(|left, right| ranks[left].cmp(&ranks[right]).then(left.cmp(right)));
+        if teams.len() != 4 {
+            return Err(format!(
+                "team_ranks must list exactly 4 playoff teams, got {}",
+                teams.len()
+            ));
+        }
+        if teams
+            .windows(2)
+            .any(|pair| ranks[&pair[0]] == ranks[&pair[1]])
+        {
+            return Err("team_ranks must give each team a different position".to_string());
+        }
+        let lambdas = teams
+            .iter()
+            .map(|team| {
+                let &(attack, defense) = stats
+                    .get(team)
+                    .ok_or_else(|| format!("Team {team} has no entry in team_stats"))?;
+                if !(attack.is_finite() && attack > 0.0 && defense.is_finite() && defense > 0.0) {
+                    return Err(format!(
+                        "{team} lambda_attack and lambda_defense must be positive"
+                    ));
+                }
+                Ok((attack, defense))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        // Expected goals are attack * opposing defense / mu, with mu the entrants' scoring rate.
+        let avg_goals = lambdas.iter().map(|&(attack, _)| attack).sum::<f64>() / 4.0;
+        let ratings: Vec<(f64, f64)> = lambdas
+            .iter()
+            .map(|&(attack, defense)| (attack / avg_goals, defense / avg_goals))
+            .collect();
+        let matrix = |home: usize, away: usize, home_advantage: f64| {
+            let (lambda_h, lambda_a) =
+                calculate_lambdas(avg_goals, ratings[home], ratings[away], home_advantage);
+            DixonColes::get_probability_matrix(lambda_h, lambda_a, DEFAULT_RHO, MAX_GOALS)
+        };
+        let semi_final = |higher: usize, lower: usize| TwoLegTie {
+            first_leg: matrix(lower, higher, HOME_ADVANTAGE),
+            second_leg: matrix(higher, lower, HOME_ADVANTAGE),
+            away_goals_rule: true,
+            penalty_rate,
+        };
+        let finals = (0..4)
+            .flat_map(|first| (0..4).map(move |second| (first, second)))
+            .map(|(first, second)| matrix(first, second, 1.0))
+            .collect();
+        Ok(ChampionshipPlayoff {
+            teams,
+            semi_finals: [semi_final(0, 3), semi_final(1, 2)],
+            finals,
+            penalty_rate,
+        })
+    }
+
+    /// Index of the promoted team.
+    fn simulate<R: Rng>(&self, rng: &mut R) -> usize {
+        let mut tally = TwoLegTally::default();
+        // Semi-final `n` is position n against position 3 - n; the lower-placed side is team 1.
+        let [first, second] = [0, 1].map(|semi| {
+            if self.semi_finals[semi].play(rng, &mut tally) {
+                3 - semi
+            } else {
+                semi
+            }
+        });
+        let (goals_first, goals_second) =
+            DixonColes::simulate_from_distribution(rng, &self.finals[first * 4 + second]);
+        match goals_first.cmp(&goals_second) {
+            Ordering::Greater => first,
+            Ordering::Less => second,
+            Ordering::Equal if penalty_shootout(rng, self.penalty_rate, self.penalty_rate) => first,
+            Ordering::Equal => second,
+        }
+    }
+
+    fn win_counts(&self, n_sims: usize, base_seed: u64) -> Vec<u64> {
+        (0..n_sims)
+            .into_par_iter()
+            .fold(
+                || vec![0u64; 4],
+                |mut counts, sim_index| {
+                    let mut rng = ChaCha8Rng::seed_from_u64(simulation_seed(base_seed, sim_index));
+                    counts[self.simulate(&mut rng)] += 1;
+                    counts
+                },
+            )
+            .reduce(
+                || vec![0u64; 4],
+                |mut left, right| {
+                    for (left_count, right_count) in left.iter_mut().zip(right) {
+                        *left_count += right_count;
+                    }
+                    left
+                },
+            )
+    }
+}
+
+/// Promotion probabilities from a Championship-style playoff. `team_ranks` maps the four
+/// entrants to their league positions and `team_stats` maps each to
+/// `{"lambda_attack": f, "lambda_defense": f}`.
+#[pyfunction(penalty_rate = "DEFAULT_PENALTY_RATE")]
+fn simulate_championship_playoff(
+    py: Python,
+    team_ranks: PyObject,
+    team_stats: PyObject,
+    n_sims: usize,
+    seed: Option<u64>,
+    penalty_rate: f64,
+) -> PyResult<PyObject> {
+    let ranks: HashMap<String, usize> = team_ranks.extract(py)?;
+    let raw_stats: HashMap<String, HashMap<String, f64>> = team_stats.extract(py)?;
+    if n_sims == 0 {
+        return Err(PyValueError::new_err("n_sims must be positive"));
+    }
+    let mut stats = HashMap::new();
+    for (team, params) in raw_stats {
+        let lambda = |key: &str| {
+            params.get(key).copied().ok_or_else(|| {
+                PyValueError::new_err(format!("Parameters for {team} missing '{key}'"))
+            })
+        };
+        let lambdas = (lambda("lambda_attack")?, lambda("lambda_defense")?);
+        stats.insert(team, lambdas);
+    }
+    let penalty_rate = validate_conversion_rate("penalty_rate", penalty_rate)?;
+    let playoff =
+        ChampionshipPlayoff::new(&ranks, &stats, penalty_rate).map_err(PyValueError::new_err)?;
+    let base_seed = seed.unwrap_or_else(rand::random);
+    let counts = py.allow_threads(|| playoff.win_counts(n_sims, base_seed));
+
+    let result = PyDict::new(py);
+    for (team, count) in playoff.teams.iter().zip(counts) {
+        result.set_item(team, count as f64 / n_sims as f64)?;
+    }
+    Ok(result.into())
+}
+
+/// Champions League style tournament: double round-robin groups, then knockout rounds in which
+/// group winners meet runners-up at random. Ties are two-legged, without away goals, up to a
+/// one-off final at a neutral venue.
+struct ChampionsLeague {
+    /// Every entrant, group by group.
+    teams: Vec<String>,
+    /// Each group's season and the tournament index of its local teams.
+    groups: Vec<(SimulationInput, Vec<usize>)>,
+    /// `[home * teams + away]` score matrices with and without home advantage.
+    home_matrices: Vec<ProbabilityDistribution>,
+    neutral_matrices: Vec<ProbabilityDistribution>,
+    penalty_rate: f64,
+}
+
+impl ChampionsLeague {
+    fn new(
+        groups: &[Vec<String>],
+        lambdas: &HashMap<String, (f64, f64)>,
+        penalty_rate: f64,
+    ) -> Result<Self, String> {
+        if !groups.len().is_power_of_two() {
+            return Err(format!(
+                "the number of groups must be a power of two, got {}",
+                groups.len()
+            ));
+        }
+        let teams: Vec<String> = groups.iter().flatten().cloned().collect();
+        let rates = team_lambdas(&teams, lambdas)?;
+        // One scoring rate for the whole tournament keeps group and knockout games comparable.
+        let avg_goals = mean_attack(&rates);
+        let mut next_idx = 0;
+        let groups = groups
+            .iter()
+            .map(|group| {
+                let input = group_stage_input(group, lambdas, true, Some(avg_goals))?;
+                let members = (next_idx..next_idx + group.len()).collect();
+                next_idx += group.len();
+                Ok((input, members))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(ChampionsLeague {
+            home_matrices: pairwise_matrices(&rates, avg_goals, HOME_ADVANTAGE),
+            neutral_matrices: pairwise_matrices(&rates, avg_goals, 1.0),
+            teams,
+            groups,
+            penalty_rate,
+        })
+    }
+
+    /// Knockout rounds from the first one down to the winner, e.g. 16, 8, 4, 2 and 1 teams.
+    fn stages(&self) -> Vec<usize> {
+        let first_round = 2 * self.groups.len();
+        (0..=first_round.trailing_zeros())
+            .map(|halvings| first_round >> halvings)
+            .collect()
+    }
+
+    /// Two-legged tie with `first` at home in the first leg; returns the team that goes through.
+    fn two_legs<R: Rng>(&self, rng: &mut R, first: usize, second: usize) -> usize {
+        let num_teams = self.teams.len();
+        let legs = [
+            &self.home_matrices[first * num_teams + second],
+            &self.home_matrices[second * num_teams + first],
+        ];
+        let mut tally = TwoLegTally::default();
+        if play_two_legs(rng, legs, false, self.penalty_rate, &mut tally) {
+            first
+        } else {
+            second
+        }
+    }
+
+    /// Adds one tournament to `reached[team][stage]`, the knockout stages each team played in.
+    fn simulate<R: Rng>(&self, rng: &mut R, reached: &mut [Vec<u64>]) {
+        let mut winners = Vec::with_capacity(self.groups.len());
+        let mut runners_up = Vec::with_capacity(self.groups.len());
+        for (input, members) in &self.groups {
+            let order = simulate_single_season(input, rng).order;
+            winners.push(members[order[0]]);
+            runners_up.push(members[order[1]]);
+        }
+        runners_up.shuffle(rng);
+        let mut survivors: Vec<usize> = winners.iter().chain(&runners_up).copied().collect();
+        // Runners-up host the first leg, group winners the second.
+        let mut pairs: Vec<(usize, usize)> = runners_up.into_iter().zip(winners).collect();
+        let mut stage = 0;
+        loop {
+            for &team in &survivors {
+                reached[team][stage] += 1;
+            }
+            stage += 1;
+            match survivors.len() {
+                1 => break,
+                2 => {
+                    let num_teams = self.teams.len();
+                    let (first, second) = (survivors[0], survivors[1]);
+                    let matrix = &self.neutral_matrices[first * num_teams + second];
+                    let (goals_first, goals_second) =
+                        DixonColes::simulate_from_distribution(rng, matrix);
+                    let first_wins = match goals_first.cmp(&goals_second) {
+                        Ordering::Equal => {
+                            penalty_shootout(rng, self.penalty_rate, self.penalty_rate)
+                        }
+                        decided => decided == Ordering::Greater,
+                    };
+                    survivors = vec![if first_wins { first } else { second }];
+                }
+                _ => {
+                    survivors = pairs
+                        .iter()
+                        .map(|&(first, second)| self.two_legs(rng, first, second))
+                        .collect();
+                    survivors.shuffle(rng);
+                    pairs = survivors.chunks(2).map(|pair| (pair[0], pair[1])).collect();
+                }
+            }
+        }
+    }
+
+    fn reach_counts(&self, n_sims: usize, base_seed: u64) -> Vec<Vec<u64>> {
+        let empty = || vec![vec![0u64; self.stages().len()]; self.teams.len()];
+        (0..n_sims)
+            .into_par_iter()
+            .fold(empty, |mut reached, sim_index| {
+                let mut rng = ChaCha8Rng::seed_from_u64(simulation_seed(base_seed, sim_index));
+                self.simulate(&mut rng, &mut reached);
+                reached
+            })
+            .reduce(empty, |mut left, right| {
+                for (left, right) in left.iter_mut().zip(right) {
+                    for (left_count, right_count) in left.iter_mut().zip(right) {
+                        *left_count += right_count;
+                    }
+                }
+                left
+            })
+    }
+}
+
+/// Knockout stage label for a round with `teams` left.
+fn knockout_stage_name(teams: usize) -> String {
+    match teams {
+        1 => "winner".to_string(),
+        2 => "final".to_string(),
+        4 => "semi_final".to_string(),
+        8 => "quarter_final".to_string(),
+        _ => format!("round_of_{teams}"),
+    }
+}
+
+/// Champions League style tournament. `groups` lists each group's teams and `team_stats` maps
+/// every team to `(lambda_attack, lambda_defense)`. Returns, per team, the probability of
+/// reaching each knockout stage, e.g. `round_of_16` through `final` and `winner`.
+#[pyfunction(seed = "None", penalty_rate = "DEFAULT_PENALTY_RATE")]
+fn simulate_champions_league(
+    py: Python,
+    groups: PyObject,
+    team_stats: PyObject,
+    n_sims: usize,
+    seed: Option<u64>,
+    penalty_rate: f64,
+) -> PyResult<PyObject> {
+    let groups: Vec<Vec<String>> = groups.extract(py)?;
+    let lambdas: HashMap<String, (f64, f64)> = team_stats.extract(py)?;
+    if n_sims == 0 {
+        return Err(PyValueError::new_err("n_sims must be positive"));
+    }
+    let penalty_rate = validate_conversion_rate("penalty_rate", penalty_rate)?;
+    let tournament =
+        ChampionsLeague::new(&groups, &lambdas, penalty_rate).map_err(PyValueError::new_err)?;
+    let base_seed = seed.unwrap_or_else(rand::random);
+    let reached = py.allow_threads(|| tournament.reach_counts(n_sims, base_seed));
+
+    let stages = tournament.stages();
+    let result = PyDict::new(py);
+    for (team, counts) in tournament.teams.iter().zip(reached) {
+        let probabilities = PyDict::new(py);
+        for (&stage, count) in stages.iter().zip(counts) {
+            probabilities.set_item(knockout_stage_name(stage), count as f64 / n_sims as f64)?;
+        }
+        result.set_item(team, probabilities)?;
+    }
+    Ok(result.into())
+}
+
+/// Playoff places per MLS conference.
+const MLS_PLAYOFF_TEAMS: usize = 7;
+
+/// An MLS regular season over both conferences, then the playoff: the top seven of each
+/// conference qualify, the top seed has a first-round bye, the higher seed hosts every
+/// single-match round up to the conference final, and the side that finished higher overall hosts
+/// MLS Cup.
+struct MlsSeason {
+    input: SimulationInput,
+    /// Teams `0..east_size` are the Eastern Conference, the rest the Western.
+    east_size: usize,
+    /// `[home * teams + away]` score matrices for playoff games.
+    home_matrices: Vec<ProbabilityDistribution>,
+    penalty_rate: f64,
+}
+
+/// `[qualified, reached the conference final, won MLS Cup]` seasons per team.
+type MlsTally = Vec<[u64; 3]>;
+
+impl MlsSeason {
+    fn new(
+        league: League,
+        east_size: usize,
+        fixtures: &[ParsedFixture],
+        config: &ModelConfig,
+    ) -> Result<Self, String> {
+        let num_teams = league.teams.len();
+        if east_size < MLS_PLAYOFF_TEAMS || num_teams - east_size < MLS_PLAYOFF_TEAMS {
+            return Err(format!(
+                "each conference needs at least {MLS_PLAYOFF_TEAMS} teams"
+            ));
+        }
+        let mut home_matrices = Vec::with_capacity(num_teams * num_teams);
+        for home_idx in 0..num_teams {
+            for away_idx in 0..num_teams {
+                let fixture = ParsedFixture {
+                    home_idx,
+                    away_idx,
+                    neutral: false,
+                    result: None,
+                };
+                home_matrices.push(
+                    league
+                        .ratings
+                        .fixture_simulation(&fixture, config)
+                        .distribution,
+                );
+            }
+        }
+        Ok(MlsSeason {
+            input: league.simulation_input(fixtures, config),
+            east_size,
+            home_matrices,
+            penalty_rate: DEFAULT_PENALTY_RATE,
+        })
+    }
+
+    /// Single playoff game hosted by `home`; level scores go to penalties.
+    fn knockout<R: Rng>(&self, rng: &mut R, home: usize, away: usize) -> usize {
+        let matrix = &self.home_matrices[home * self.input.teams.len() + away];
+        let (home_goals, away_goals) = DixonColes::simulate_from_distribution(rng, matrix);
+        match home_goals.cmp(&away_goals) {
+            Ordering::Greater => home,
+            Ordering::Less => away,
+            Ordering::Equal if penalty_shootout(rng, self.penalty_rate, self.penalty_rate) => home,
+            Ordering::Equal => away,
+        }
+    }
+
+    /// Conference bracket over `seeds` (teams best first); returns the conference champion.
+    fn conference_playoff<R: Rng>(
+        &self,
+        rng: &mut R,
+        seeds: &[usize],
+        tally: &mut MlsTally,
+    ) -> usize {
+        // Seed positions, so the better-placed survivor always hosts.
+        let play = |rng: &mut R, higher: usize, lower: usize| {
+            if self.knockout(rng, seeds[higher], seeds[lower]) == seeds[higher] {
+                higher
+            } else {
+                lower
+            }
+        };
+        let mut semi_finalists = vec![0];
+        semi_finalists
+            .extend([(1, 6), (2, 5), (3, 4)].map(|(higher, lower)| play(rng, higher, lower)));
+        semi_finalists.sort_unstable();
+        let finalists = [
+            play(rng, semi_finalists[0], semi_finalists[3]),
+            play(rng, semi_finalists[1], semi_finalists[2]),
+        ];
+        for &finalist in &finalists {
+            tally[seeds[finalist]][1] += 1;
+        }
+        let (higher, lower) = (
+            finalists[0].min(finalists[1]),
+            finalists[0].max(finalists[1]),
+        );
+        seeds[play(rng, higher, lower)]
+    }
+
+    fn simulate<R: Rng>(&self, rng: &mut R, tally: &mut MlsTally) {
+        let order = simulate_single_season(&self.input, rng).order;
+        let seeds = |east: bool| -> Vec<usize> {
+            order
+                .iter()
+                .copied()
+                .filter(|&team| (team < self.east_size) == east)
+                .take(MLS_PLAYOFF_TEAMS)
+                .collect()
+        };
+        let champions = [true, false].map(|east| {
+            let seeds = seeds(east);
+            for &team in &seeds {
+                tally[team][0] += 1;
+            }
+            self.conference_playoff(rng, &seeds, tally)
+        });
+        let rank = |team: usize| order.iter().position(|&ranked| ranked == team);
+        let (home, away) = if rank(champions[0]) < rank(champions[1]) {
+            (champions[0], champions[1])
+        } else {
+            (champions[1], champions[0])
+        };
+        tally[self.knockout(rng, home, away)][2] += 1;
+    }
+
+    fn tally(&self, n_sims: usize, base_seed: u64) -> MlsTally {
+        let num_teams = self.input.teams.len();
+        (0..n_sims)
+            .into_par_iter()
+            .fold(
+                || vec![[0u64; 3]; num_teams],
+                |mut tally, sim_index| {
+                    let mut rng = ChaCha8Rng::seed_from_u64(simulation_seed(base_seed, sim_index));
+                    self.simulate(&mut rng, &mut tally);
+                    tally
+                },
+            )
+            .reduce(
+                || vec![[0u64; 3]; num_teams],
+                |mut left, right| {
+                    for (left, right) in left.iter_mut().zip(right) {
+                        for (left_count, right_count) in left.iter_mut().zip(right) {
+                            *left_count += right_count;
+                        }
+                    }
+                    left
+                },
+            )
+    }
+}
+
+/// MLS regular season and playoff. The conference tables are standings lists as for
+/// `simulate_season`; `inter_fixtures` holds the games between the conferences. Returns
+/// `{team: {"cup_win", "playoff", "conference_final"}}` probabilities.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn simulate_mls_season(
+    py: Python,
+    east_base: PyObject,
+    west_base: PyObject,
+    east_fixtures: PyObject,
+    west_fixtures: PyObject,
+    inter_fixtures: PyObject,
+    n_sims: usize,
+    seed: Option<u64>,
+    rho: Option<f64>,
+    home_advantage: Option<f64>,
+    max_goals: Option<usize>,
+) -> PyResult<PyObject> {
+    if n_sims == 0 {
+        return Err(PyValueError::new_err("n_sims must be positive"));
+    }
+    // Only overall tables are given, so home advantage cannot be estimated from venue splits.
+    let config = ModelConfig::from_args(
+        rho,
+        Some(home_advantage.unwrap_or(HOME_ADVANTAGE)),
+        max_goals,
+    )?;
+    let conference = |base: PyObject| -> PyResult<Vec<(String, Stats)>> {
+        let base: &PyList = base.extract(py)?;
+        let (teams, stats, _) = parse_base_table(base)?;
+        Ok(teams.into_iter().zip(stats).collect())
+    };
+    let mut rows = conference(east_base)?;
+    let east_size = rows.len();
+    rows.extend(conference(west_base)?);
+    let league = League::new(rows.clone(), &rows, &rows, &config).map_err(PyValueError::new_err)?;
+    if league.team_to_idx.len() != rows.len() {
+        return Err(PyValueError::new_err(
+            "a team is listed in both conferences",
+        ));
+    }
+    let mut fixtures = Vec::new();
+    for list in [east_fixtures, west_fixtures, inter_fixtures] {
+        let list: &PyList = list.extract(py)?;
+        fixtures.extend(parse_fixture_pairs(list, &league.team_to_idx)?);
+    }
+    let season =
+        MlsSeason::new(league, east_size, &fixtures, &config).map_err(PyValueError::new_err)?;
+    let base_seed = seed.unwrap_or_else(rand::random);
+    let tally = py.allow_threads(|| season.tally(n_sims, base_seed));
+
+    let result = PyDict::new(py);
+    for (team, [playoff, conference_final, cup_win]) in season.input.teams.iter().zip(tally) {
+        let probabilities = PyDict::new(py);
+        probabilities.set_item("cup_win", cup_win as f64 / n_sims as f64)?;
+        probabilities.set_item("playoff", playoff as f64 / n_sims as f64)?;
+        probabilities.set_item("conference_final", conference_final as f64 / n_sims as f64)?;
+        result.set_item(team, probabilities)?;
+    }
+    Ok(result.into())
+}
+
+/// Swiss-system league phase: every round pairs teams close in the standings who have not met
+/// yet, and the final table ranks them on points, goal difference and goals scored.
+struct SwissTournament {
+    teams: Vec<String>,
+    n_rounds: usize,
+    /// `[home * teams + away]` score matrices.
+    home_matrices: Vec<ProbabilityDistribution>,
+}
+
+/// Pairs `ranked` (best first) so that nobody meets a past opponent, preferring the nearest
+/// available team below: the leader takes the next team it has not played, and later choices
+/// are revisited only when the rest of the field cannot be paired.
+fn swiss_pairings(ranked: &[usize], played: &[Vec<bool>]) -> Option<Vec<(usize, usize)>> {
+    let Some((&first, rest)) = ranked.split_first() else {
+        return Some(Vec::new());
+    };
+    for (offset, &opponent) in rest.iter().enumerate() {
+        if played[first][opponent] {
+            continue;
+        }
+        let remaining: Vec<usize> = rest
+            .iter()
+            .enumerate()
+            .filter(|&(idx, _)| idx != offset)
+            .map(|(_, &team)| team)
+            .collect();
+        if let Some(mut pairs) = swiss_pairings(&remaining, played) {
+            pairs.insert(0, (first, opponent));
+            return Some(pairs);
+        }
+    }
+    None
+}
+
+impl SwissTournament {
+    fn new(
+        teams: Vec<String>,
+        lambdas: &HashMap<String, (f64, f64)>,
+        n_rounds: usize,
+    ) -> Result<Self, String> {
+        if teams.len() < 2 || !teams.len().is_multiple_of(2) {
+            return Err(format!(
+                "a Swiss tournament needs an even number of teams, got {}",
+                teams.len()
+            ));
+        }
+        // With at most half the field already met, a pairing without rematches always exists.
+        if n_rounds == 0 || n_rounds > teams.len() / 2 {
+            return Err(format!(
+                "n_rounds must be between 1 and {} for {} teams",
+                teams.len() / 2,
+                teams.len()
+            ));
+        }
+        let rates = team_lambdas(&teams, lambdas)?;
+        Ok(SwissTournament {
+            home_matrices: pairwise_matrices(&rates, mean_attack(&rates), HOME_ADVANTAGE),
+            teams,
+            n_rounds,
+        })
+    }
+
+    /// One tournament: the final order (best first) and every `(home, away)` game played.
+    fn play<R: Rng>(&self, rng: &mut R) -> (Vec<usize>, Vec<(usize, usize)>) {
+        let num_teams = self.teams.len();
+        let mut standings = vec![Stats::default(); num_teams];
+        let mut played = vec![vec![false; num_teams]; num_teams];
+        let mut home_games = vec![0usize; num_teams];
+        let mut games = Vec::with_capacity(self.n_rounds * num_teams / 2);
+        let mut order: Vec<usize> = (0..num_teams).collect();
+        for _ in 0..self.n_rounds {
+            sort_standings(&mut order, &self.teams, &standings);
+            let pairs = swiss_pairings(&order, &played)
+                .expect("at most half the field has been met, so a pairing exists");
+            for (higher, lower) in pairs {
+                // Whoever has hosted less plays at home; the better-placed side breaks ties.
+                let (home, away) = if home_games[lower] < home_games[higher] {
+                    (lower, higher)
+                } else {
+                    (higher, lower)
+                };
+                let matrix = &self.home_matrices[home * num_teams + away];
+                let (home_goals, away_goals) = DixonColes::simulate_from_distribution(rng, matrix);
+                standings[home].record_result(home_goals, away_goals);
+                standings[away].record_result(away_goals, home_goals);
+                played[home][away] = true;
+                played[away][home] = true;
+                home_games[home] += 1;
+                games.push((home, away));
+            }
+        }
+        sort_standings(&mut order, &self.teams, &standings);
+        (order, games)
+    }
+
+    /// `[top 8, top 16, below 16]` finishes per team.
+    fn tally(&self, n_sims: usize, base_seed: u64) -> Vec<[u64; 3]> {
+        let num_teams = self.teams.len();
+        (0..n_sims)
+            .into_par_iter()
+            .fold(
+                || vec![[0u64; 3]; num_teams],
+                |mut tally, sim_index| {
+                    let mut rng = ChaCha8Rng::seed_from_u64(simulation_seed(base_seed, sim_index));
+                    let (order, _) = self.play(&mut rng);
+                    for (position, team) in order.into_iter().enumerate() {
+                        match position {
+                            0..=7 => {
+                                tally[team][0] += 1;
+                                tally[team][1] += 1;
+                            }
+                            8..=15 => tally[team][1] += 1,
+                            _ => tally[team][2] += 1,
+                        }
+                    }
+                    tally
+                },
+            )
+            .reduce(
+                || vec![[0u64; 3]; num_teams],
+                |mut left, right| {
+                    for (left, right) in left.iter_mut().zip(right) {
+                        for (left_count, right_count) in left.iter_mut().zip(right) {
+                            *left_count += right_count;
+                        }
+                    }
+                    left
+                },
+            )
+    }
+}
+
+/// Swiss-system league phase like the Champions League's since 2024. `team_stats` maps each
+/// team to `(lambda_attack, lambda_defense)`. Returns `{team: {"qualified_top_8",
+/// "qualified_top_16", "eliminated"}}`, where the top 16 includes the top 8 and eliminated
+/// teams finished below 16th.
+#[pyfunction]
+fn simulate_swiss(
+    py: Python,
+    teams: PyObject,
+    team_stats: PyObject,
+    n_rounds: usize,
+    n_sims: usize,
+    seed: Option<u64>,
+) -> PyResult<PyObject> {
+    let teams: Vec<String> = teams.extract(py)?;
+    let lambdas: HashMap<String, (f64, f64)> = team_stats.extract(py)?;
+    if n_sims == 0 {
+        return Err(PyValueError::new_err("n_sims must be positive"));
+    }
+    let tournament =
+        SwissTournament::new(teams, &lambdas, n_rounds).map_err(PyValueError::new_err)?;
+    let base_seed = seed.unwrap_or_else(rand::random);
+    let tally = py.allow_threads(|| tournament.tally(n_sims, base_seed));
+
+    let result = PyDict::new(py);
+    for (team, [top_8, top_16, eliminated]) in tournament.teams.iter().zip(tally) {
+        let probabilities = PyDict::new(py);
+        probabilities.set_item("qualified_top_8", top_8 as f64 / n_sims as f64)?;
+        probabilities.set_item("qualified_top_16", top_16 as f64 / n_sims as f64)?;
+        probabilities.set_item("eliminated", eliminated as f64 / n_sims as f64)?;
+        result.set_item(team, probabilities)?;
+    }
+    Ok(result.into())
+}
+
+fn validate_conversion_rate(name: &str, value: f64) -> PyResult<f64> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(PyValueError::new_err(format!(
+            "{name} must be in [0.0, 1.0], got {value}"
+        )))
+    }
+}
+
+/// `(team1 wins, team2 wins)` over `n_sims` independent shootouts.
+fn shootout_wins(p_team1: f64, p_team2: f64, n_sims: usize, base_seed: u64) -> (u64, u64) {
+    let team1 = (0..n_sims)
+        .into_par_iter()
+        .filter(|&sim_index| {
+            let mut rng = ChaCha8Rng::seed_from_u64(simulation_seed(base_seed, sim_index));
+            penalty_shootout(&mut rng, p_team1, p_team2)
+        })
+        .count() as u64;
+    (team1, n_sims as u64 - team1)
+}
+
+/// Winner (`"team1"` or `"team2"`) of one shootout; team 1 kicks first.
+#[pyfunction]
+fn simulate_penalty_shootout(p_team1: f64, p_team2: f64, seed: Option<u64>) -> PyResult<String> {
+    let p_team1 = validate_conversion_rate("p_team1", p_team1)?;
+    let p_team2 = validate_conversion_rate("p_team2", p_team2)?;
+    let winner = if penalty_shootout(&mut season_rng(seed), p_team1, p_team2) {
+        "team1"
+    } else {
+        "team2"
+    };
+    Ok(winner.to_string())
+}
+
+#[pyfunction]
+fn simulate_penalty_shootout_bulk(
+    py: Python,
+    p_team1: f64,
+    p_team2: f64,
+    n_sims: usize,
+    seed: Option<u64>,
+) -> PyResult<(u64, u64)> {
+    let p_team1 = validate_conversion_rate("p_team1", p_team1)?;
+    let p_team2 = validate_conversion_rate("p_team2", p_team2)?;
+    let base_seed = seed.unwrap_or_else(rand::random);
+    Ok(py.allow_threads(|| shootout_wins(p_team1, p_team2, n_sims, base_seed)))
+}
+
+#[pymodule]
+fn league_outcome_simulator_rust(_py: Python, m: &PyModule) -> PyResult<()> {
+    INIT_RAYON.call_once(|| {
+        let _ = ThreadPoolBuilder::new()
+            .num_threads(num_cpus::get())
+            .build_global();
+    });
+
+    m.add_function(wrap_pyfunction!(simulate_season, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_season_form, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_season_dc, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_season_zip, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_season_split, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_multi_season, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_bulk, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_bulk_qmc, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_season_conditional, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_bulk_scenario_delta, m)?)?;
+    m.add_function(wrap_pyfunction!(fixture_importance, m)?)?;
+    m.add_function(wrap_pyfunction!(marginal_fixture_value, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_bulk_importance, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_bulk_converging, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_bulk_resume, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_stream, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_bulk_array, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_bulk_from_config, m)?)?;
+    m.add_function(wrap_pyfunction!(config_to_json, m)?)?;
+    m.add_function(wrap_pyfunction!(config_from_json, m)?)?;
+    m.add_function(wrap_pyfunction!(simulation_progress, m)?)?;
+    m.add_function(wrap_pyfunction!(fit_dixon_coles, m)?)?;
+    m.add_function(wrap_pyfunction!(estimate_lambdas_poisson, m)?)?;
+    m.add_function(wrap_pyfunction!(brier_score, m)?)?;
+    m.add_function(wrap_pyfunction!(log_loss, m)?)?;
+    m.add_function(wrap_pyfunction!(ranked_probability_score, m)?)?;
+    m.add_function(wrap_pyfunction!(rps_position, m)?)?;
+    m.add_function(wrap_pyfunction!(mean_rps, m)?)?;
+    m.add_function(wrap_pyfunction!(backtest, m)?)?;
+    m.add_function(wrap_pyfunction!(zone_probabilities, m)?)?;
+    m.add_function(wrap_pyfunction!(competitiveness_index, m)?)?;
+    m.add_function(wrap_pyfunction!(position_correlation_matrix, m)?)?;
+    m.add_function(wrap_pyfunction!(joint_top_n_probability, m)?)?;
+    m.add_function(wrap_pyfunction!(position_summary_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(league_gini, m)?)?;
+    m.add_function(wrap_pyfunction!(per_position_gini, m)?)?;
+    m.add_function(wrap_pyfunction!(bootstrap_ci, m)?)?;
+    m.add_function(wrap_pyfunction!(can_finish_above, m)?)?;
+    m.add_function(wrap_pyfunction!(already_qualified, m)?)?;
+    m.add_function(wrap_pyfunction!(best_worst_case, m)?)?;
+    m.add_function(wrap_pyfunction!(points_to_win_league, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_group_stage, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_playoff, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_two_leg_tie, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_championship_playoff, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_champions_league, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_mls_season, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_swiss, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_penalty_shootout, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_penalty_shootout_bulk, m)?)?;
+    m.add_function(wrap_pyfunction!(format_standings, m)?)?;
+    m.add_function(wrap_pyfunction!(match_probabilities, m)?)?;
+    m.add_function(wrap_pyfunction!(bulk_match_probabilities, m)?)?;
+    m.add_function(wrap_pyfunction!(scoreline_probabilities, m)?)?;
+    m.add_function(wrap_pyfunction!(head_to_head_matrix, m)?)?;
+    m.add_function(wrap_pyfunction!(over_under_probability, m)?)?;
+    m.add_function(wrap_pyfunction!(asian_handicap_probability, m)?)?;
+    m.add_function(wrap_pyfunction!(expected_points, m)?)?;
+    m.add_function(wrap_pyfunction!(btts_probability, m)?)?;
+    m.add_function(wrap_pyfunction!(clean_sheet_probability, m)?)?;
+    m.add_function(wrap_pyfunction!(halftime_probabilities, m)?)?;
+    m.add_function(wrap_pyfunction!(lambdas_from_elo, m)?)?;
+    m.add_function(wrap_pyfunction!(update_elo, m)?)?;
+    m.add_function(wrap_pyfunction!(flush_probability_cache, m)?)?;
+    m.add_function(wrap_pyfunction!(probability_cache_size, m)?)?;
+    m.add_function(wrap_pyfunction!(cache_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_cache_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(warm_cache, m)?)?;
+    m.add_function(wrap_pyfunction!(warm_cache_parallel, m)?)?;
+    m.add_class::<MatchProbCache>()?;
+    m.add_class::<SimulationStream>()?;
+    m.add_class::<LiveSeason>()?;
+    m.add_function(wrap_pyfunction!(apply_results, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_fixtures, m)?)?;
+    m.add_function(wrap_pyfunction!(check_home_away_balance, m)?)?;
+    m.add_function(wrap_pyfunction!(fix_home_away_balance, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::sync::Mutex;
+
+    static CACHE_TEST_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Serializes tests that read or reset the shared probability cache, its counters or the
+    /// bulk progress counter.
+    fn cache_guard() -> std::sync::MutexGuard<'static, ()> {
+        CACHE_TEST_LOCK
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner())
+    }
+
+    proptest! {
+        #[test]
+        fn probability_matrix_is_normalized_for_any_goal_ceiling(
+            lambda_h in 0.0f64..6.0,
+            lambda_a in 0.0f64..6.0,
+            rho in -0.99f64..0.99,
+            max_goals in 1usize..=25,
+        ) {
+            let pd = DixonColes::precompute_probability_matrix(lambda_h, lambda_a, rho, max_goals);
+            prop_assert_eq!(pd.cdf.len(), (max_goals + 1) * (max_goals + 1));
+            let total: f64 = pd.probabilities().sum();
+            prop_assert!((total - 1.0).abs() <= 1e-9);
+            prop_assert!(pd.probabilities().all(|probability| probability >= -1e-12));
+        }
+
+        #[test]
+        fn expected_points_add_up_to_three_per_decisive_result_and_two_per_draw(
+            lambda_h in 0.0f64..6.0,
+            lambda_a in 0.0f64..6.0,
+            rho in -0.99f64..0.99,
+        ) {
+            let pd = DixonColes::precompute_probability_matrix(lambda_h, lambda_a, rho, MAX_GOALS);
+            let (home, draw, away) = pd.outcome_probabilities();
+            let (xpts_home, xpts_away) = pd.expected_points();
+            prop_assert!((xpts_home + xpts_away - (3.0 * (home + away) + 2.0 * draw)).abs() <= 1e-9);
+        }
+    }
+
+    #[test]
+    fn cached_matrices_are_keyed_by_goal_ceiling() {
+        let _cache = cache_guard();
+        let small = DixonColes::get_probability_matrix(3.7, 2.9, DEFAULT_RHO, MAX_GOALS);
+        let large = DixonColes::get_probability_matrix(3.7, 2.9, DEFAULT_RHO, 15);
+        assert_eq!(small.dim, MAX_GOALS + 1);
+        assert_eq!(large.dim, 16);
+    }
+
+    #[test]
+    fn zero_lambda_side_never_scores() {
+        let pd = DixonColes::precompute_probability_matrix(1.5, 0.0, DEFAULT_RHO, MAX_GOALS);
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        for _ in 0..200 {
+            let (_, away) = DixonColes::simulate_from_distribution(&mut rng, &pd);
+            assert_eq!(away, 0);
+        }
+    }
+
+    #[test]
+    fn dixon_coles_match_returns_reasonable_scores() {
+        let _cache = cache_guard();
+        let mut rng = ChaCha8Rng::seed_from_u64(123);
+        let pd = DixonColes::get_probability_matrix(1.4, 0.9, DEFAULT_RHO, MAX_GOALS);
+        let (home, away) = DixonColes::simulate_from_distribution(&mut rng, &pd);
+        assert!(home >= 0);
+        assert!(away >= 0);
+        assert!(home <= MAX_GOALS as i64);
+        assert!(away <= MAX_GOALS as i64);
+    }
+
+    #[test]
+    fn match_prob_cache_evicts_least_recently_used_entry() {
+        let _cache = cache_guard();
+        let cache = MatchProbCache::with_capacity(NonZeroUsize::new(2).unwrap(), MAX_GOALS);
+        assert_eq!(cache.get(1.2, 0.8, DEFAULT_RHO), None);
+
+        cache.precompute(vec![(1.2, 0.8), (1.5, 1.1)], DEFAULT_RHO);
+        let (home, draw, away) = cache.get(1.2, 0.8, DEFAULT_RHO).unwrap();
+        assert!((home + draw + away - 1.0).abs() < 1e-9);
+        assert_eq!(
+            (home, draw, away),
+            match_outcome_probs(1.2, 0.8, DEFAULT_RHO, MAX_GOALS)
+        );
+
+        cache.precompute(vec![(0.9, 0.9)], DEFAULT_RHO);
+        assert_eq!(cache.__len__(), 2);
+        assert!(cache.get(1.2, 0.8, DEFAULT_RHO).is_some());
+        assert!(cache.get(1.5, 1.1, DEFAULT_RHO).is_none());
+        assert!(cache.get(0.9, 0.9, DEFAULT_RHO).is_some());
+    }
+
+    #[test]
+    #[ignore = "benchmark; run with cargo test --release -- --ignored"]
+    fn bench_match_prob_cache_hits_beat_recomputation() {
+        let _cache = cache_guard();
+        let pairs: Vec<(f64, f64)> = (0..64)
+            .map(|step| (0.6 + step as f64 * 0.02, 1.4 - step as f64 * 0.01))
+            .collect();
+        let rounds = 200;
+
+        let start = std::time::Instant::now();
+        for _ in 0..rounds {
+            for &(lambda_h, lambda_a) in &pairs {
+                std::hint::black_box(match_outcome_probs(
+                    lambda_h,
+                    lambda_a,
+                    DEFAULT_RHO,
+                    MAX_GOALS,
+                ));
+            }
+        }
+        let uncached = start.elapsed();
+
+        let cache =
+            MatchProbCache::with_capacity(NonZeroUsize::new(pairs.len()).unwrap(), MAX_GOALS);
+        cache.precompute(pairs.clone(), DEFAULT_RHO);
+        let start = std::time::Instant::now();
+        for _ in 0..rounds {
+            for &(lambda_h, lambda_a) in &pairs {
+                std::hint::black_box(cache.get(lambda_h, lambda_a, DEFAULT_RHO).unwrap());
+            }
+        }
+        let cached = start.elapsed();
+
+        let speedup = uncached.as_secs_f64() / cached.as_secs_f64();
+        println!("match_outcome_probs: {uncached:?} uncached, {cached:?} cached ({speedup:.1}x)");
+        assert!(
+            speedup >= 5.0,
+            "expected at least 5x speedup, got {speedup:.1}x"
+        );
+    }
+
+    #[test]
+    fn german_locale_translates_wins_and_keeps_column_count() {
+        let mut stats = Stats::default();
+        stats.record_result(2, 0);
+        stats.record_result(1, 1);
+        let standings = vec![("Alpha".to_string(), stats)];
+
+        let english = StandingsLocale::new(Language::English);
+        let german = StandingsLocale::new(Language::German);
+        let english_header = standings_header(&english);
+        let german_header = standings_header(&german);
+        assert_eq!(english_header.len(), german_header.len());
+        assert!(german_header.contains(&"S".to_string()));
+
+        let english_rows = format_standings_locale(&standings, &english);
+        let german_rows = format_standings_locale(&standings, &german);
+        assert_eq!(english_rows[0].len(), german_rows[0].len());
+        assert_eq!(german_rows[0]["S"], "1");
+        assert_eq!(german_rows[0]["U"], "1");
+        assert_eq!(german_rows[0]["Pkt."], "4");
+        assert_eq!(english_rows[0]["GD"], "2");
+    }
+
+    fn group_of_four(tiebreakers: Vec<TiebreakerStage>) -> SimulationInput {
+        // A, B and C finish on 6 points, +2 and 4 scored; only their mutual results
+        // (A beat B 2-0, B beat C 1-0, C beat A 1-0) put C above B.
+        let results = [
+            (0, 1, 2, 0),
+            (1, 2, 1, 0),
+            (2, 0, 1, 0),
+            (0, 3, 2, 1),
+            (1, 3, 3, 0),
+            (2, 3, 3, 1),
+        ];
+        SimulationInput {
+            teams: ["A", "B", "C", "D"].map(str::to_string).to_vec(),
+            initial_stats: vec![Stats::default(); 4],
+            fixtures: results
+                .iter()
+                .map(|&(home_idx, away_idx, gh, ga)| FixtureSimulation {
+                    home_idx,
+                    away_idx,
+                    distribution: ProbabilityDistribution::fixed(gh, ga, MAX_GOALS),
+                })
+                .collect(),
+            tiebreakers,
+        }
+    }
+
+    /// A double round-robin with graded team strengths, built without the Python tables.
+    fn sample_league(num_teams: usize) -> SimulationInput {
+        league_from_strengths(num_teams, |idx| 0.7 + idx as f64 * 0.8 / num_teams as f64)
+    }
+
+    fn league_from_strengths(num_teams: usize, strength: impl Fn(usize) -> f64) -> SimulationInput {
+        let teams: Vec<String> = (0..num_teams).map(|idx| format!("Team {idx:02}")).collect();
+        let mut fixtures = Vec::new();
+        for home_idx in 0..num_teams {
+            for away_idx in 0..num_teams {
+                if home_idx != away_idx {
+                    let distribution = DixonColes::get_probability_matrix(
+                        strength(home_idx) * HOME_ADVANTAGE,
+                        strength(away_idx),
+                        DEFAULT_RHO,
+                        MAX_GOALS,
+                    );
+                    fixtures.push(FixtureSimulation {
+                        home_idx,
+                        away_idx,
+                        distribution,
+                    });
+                }
+            }
+        }
+        SimulationInput {
+            teams,
+            initial_stats: vec![Stats::default(); num_teams],
+            fixtures,
+            tiebreakers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn same_seed_replays_identical_season() {
+        let _cache = cache_guard();
+        let input = sample_league(20);
+        let first = simulate_single_season(&input, &mut season_rng(Some(99)));
+        let second = simulate_single_season(&input, &mut season_rng(Some(99)));
+        assert_eq!(first.order, second.order);
+        assert_eq!(first.final_stats, second.final_stats);
+    }
+
+    #[test]
+    fn different_seeds_almost_always_change_the_standings() {
+        let _cache = cache_guard();
+        let input = sample_league(20);
+        let differing = (0..100u64)
+            .filter(|&draw| {
+                let left = simulate_single_season(&input, &mut season_rng(Some(2 * draw)));
+                let right = simulate_single_season(&input, &mut season_rng(Some(2 * draw + 1)));
+                left.order != right.order || left.final_stats != right.final_stats
+            })
+            .count();
+        assert!(differing >= 95, "only {differing}/100 seed pairs differed");
+    }
+
+    #[test]
+    fn head_to_head_separates_teams_level_on_points_goal_difference_and_goals() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let overall = simulate_single_season(&group_of_four(Vec::new()), &mut rng);
+        assert_eq!(overall.order, vec![0, 1, 2, 3]);
+        assert_eq!(overall.final_stats[1], overall.final_stats[2]);
+
+        let stages = vec![
+            TiebreakerStage::Points,
+            TiebreakerStage::HeadToHead,
+            TiebreakerStage::GoalDifference,
+        ];
+        let with_h2h = simulate_single_season(&group_of_four(stages), &mut rng);
+        assert_eq!(with_h2h.order, vec![0, 2, 1, 3]);
+    }
+
+    #[test]
+    fn coefficient_stage_orders_teams_level_on_every_table_stage() {
+        let teams: Vec<String> = ["A", "B", "C"].map(str::to_string).to_vec();
+        let standings = vec![Stats::default(); 3];
+        let table = TableView {
+            teams: &teams,
+            standings: &standings,
+            results: &[],
+        };
+        let coefficients = HashMap::from([("B".to_string(), 80.0), ("C".to_string(), 95.5)]);
+        let stages = vec![
+            TiebreakerStage::Points,
+            TiebreakerStage::HeadToHead,
+            TiebreakerStage::UefaCoefficient(coefficients),
+        ];
+        let mut order = vec![0, 1, 2];
+        apply_multistage_tiebreaker(
+            &mut order,
+            &table,
+            &stages,
+            &mut ChaCha8Rng::seed_from_u64(1),
+        );
+        assert_eq!(order, vec![2, 1, 0]);
+    }
+
+    fn low_scoring_draw_share(rho: f64) -> f64 {
+        let mut rng = ChaCha8Rng::seed_from_u64(2024);
+        let pd = DixonColes::precompute_probability_matrix(1.3, 1.1, rho, MAX_GOALS);
+        let samples = 20_000;
+        let low_draws = (0..samples)
+            .filter(|_| {
+                matches!(
+                    DixonColes::simulate_from_distribution(&mut rng, &pd),
+                    (0, 0) | (1, 1)
+                )
+            })
+            .count();
+        low_draws as f64 / samples as f64
+    }
+
+    #[test]
+    fn negative_rho_inflates_low_scoring_draws() {
+        let negative = low_scoring_draw_share(-0.2);
+        let positive = low_scoring_draw_share(0.2);
+        assert!(
+            negative > positive + 0.03,
+            "0-0/1-1 share {negative:.3} with rho=-0.2 vs {positive:.3} with rho=0.2"
+        );
+    }
+
+    #[test]
+    fn home_advantage_scales_only_the_home_lambda() {
+        let neutral = calculate_lambdas(1.4, (1.2, 0.8), (1.0, 1.1), 1.0);
+        let boosted = calculate_lambdas(1.4, (1.2, 0.8), (1.0, 1.1), 1.5);
+        assert!((boosted.0 - neutral.0 * 1.5).abs() < 1e-12);
+        assert_eq!(boosted.1, neutral.1);
+    }
+
+    #[test]
+    fn seeded_bulk_counts_do_not_depend_on_thread_count() {
+        let _cache = cache_guard();
+        let input = sample_league(8);
+        let run_on = |threads: usize| {
+            ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| {
+                    let options = BulkOptions {
+                        top_k_tables: 10,
+                        ..BulkOptions::default()
+                    };
+                    run_bulk_simulations(&input, 2_000, 1234, &options).position_counts
+                })
+        };
+        assert_eq!(run_on(2), run_on(4));
+    }
+
+    #[test]
+    fn thread_count_changes_the_pool_but_not_the_counts() {
+        let _cache = cache_guard();
+        let input = sample_league(6);
+        let run_on = |threads: usize| {
+            with_thread_count(Some(threads), || {
+                let counts = run_bulk_simulations(&input, 1_500, 330, &BulkOptions::default())
+                    .position_counts;
+                (rayon::current_num_threads(), counts)
+            })
+            .unwrap()
+        };
+        let (single, single_counts) = run_on(1);
+        let (four, four_counts) = run_on(4);
+        assert_eq!((single, four), (1, 4));
+        assert_eq!(single_counts, four_counts);
+        assert_eq!(run_on(0).0, num_cpus::get());
+    }
+
+    #[test]
+    fn simd_cdf_scan_matches_binary_search() {
+        let _cache = cache_guard();
+        let mut rng = ChaCha8Rng::seed_from_u64(331);
+        for (lambda_home, lambda_away) in [(1.4, 1.1), (3.2, 0.4), (0.2, 0.3)] {
+            let distribution =
+                DixonColes::get_probability_matrix(lambda_home, lambda_away, DEFAULT_RHO, 10);
+            let cdf = &distribution.cdf;
+            assert_eq!(cdf.len(), 121);
+            for _ in 0..10_000 {
+                let u: f64 = rand::Rng::gen(&mut rng);
+                let expected = match cdf.binary_search_by(|value| {
+                    value.partial_cmp(&u).unwrap_or(std::cmp::Ordering::Greater)
+                }) {
+                    Ok(index) | Err(index) => index.min(cdf.len() - 1),
+                };
+                assert_eq!(cdf_sample_simd(cdf, u), expected, "u = {u}");
+            }
+            for u in [0.0, 1.0, 1.5] {
+                assert_eq!(cdf_sample_simd(cdf, u), cdf_sample_binary(cdf, u));
+            }
+        }
+    }
+
+    #[test]
+    fn poisson_pmf_matches_the_closed_form() {
+        for lambda in [0.5f64, 1.0, 2.0, 3.5] {
+            let mut factorial = 1.0;
+            for k in 0..=15i64 {
+                if k > 0 {
+                    factorial *= k as f64;
+                }
+                let reference = (-lambda).exp() * lambda.powi(k as i32) / factorial;
+                let pmf = DixonColes::poisson_pmf(k, lambda);
+                assert!(
+                    (pmf - reference).abs() <= 1e-12 * reference.max(1e-300),
+                    "k = {k}, lambda = {lambda}: {pmf} vs {reference}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn poisson_pmf_batch_matches_single_values() {
+        for lambda in [0.0, 0.3, 1.0, 1.7, 3.5, 6.0] {
+            let batch = DixonColes::poisson_pmf_batch(lambda, 15);
+            assert_eq!(batch.len(), 16);
+            for (k, p) in batch.into_iter().enumerate() {
+                let single = DixonColes::poisson_pmf(k as i64, lambda);
+                assert!(
+                    (p - single).abs() <= f64::EPSILON,
+                    "k = {k}, lambda = {lambda}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn score_grid_marginals_are_the_poisson_goal_distributions() {
+        for (lambda_a, lambda_b, rho) in [(1.4, 1.1, -0.13), (2.2, 0.6, 0.05), (0.9, 0.9, 0.0)] {
+            let distribution =
+                DixonColes::precompute_probability_matrix(lambda_a, lambda_b, rho, MAX_GOALS);
+            let total: f64 = distribution.probabilities().sum();
+            assert!((total - 1.0).abs() < 1e-12, "total = {total}");
+            let (team_a_goals, team_b_goals) = distribution.goal_marginals();
+            for goals in 0..=MAX_GOALS {
+                // The Dixon-Coles correction moves mass within rows and columns, so only the
+                // truncated tail separates the marginals from plain Poisson.
+                let expected_a = DixonColes::poisson_pmf(goals as i64, lambda_a);
+                let expected_b = DixonColes::poisson_pmf(goals as i64, lambda_b);
+                assert!((team_a_goals[goals] - expected_a).abs() < 1e-4, "{goals}");
+                assert!((team_b_goals[goals] - expected_b).abs() < 1e-4, "{goals}");
+            }
+        }
+    }
+
+    #[test]
+    #[ignore = "benchmark; run with cargo test --release -- --ignored"]
+    fn bench_poisson_pmf_table_beats_summing_logs() {
+        let lambdas: Vec<f64> = (1..=64).map(|step| step as f64 * 0.06).collect();
+        let rounds = 2_000;
+        let summed = |k: i64, lambda: f64| {
+            let log_k_factorial = (1..=k).map(|i| (i as f64).ln()).sum::<f64>();
+            (-lambda + k as f64 * lambda.ln() - log_k_factorial).exp()
+        };
+
+        let start = std::time::Instant::now();
+        for _ in 0..rounds {
+            for &lambda in &lambdas {
+                for k in 0..=MAX_GOALS as i64 {
+                    std::hint::black_box(summed(std::hint::black_box(k), lambda));
+                }
+            }
+        }
+        let before = start.elapsed();
+
+        let start = std::time::Instant::now();
+        for _ in 0..rounds {
+            for &lambda in &lambdas {
+                for k in 0..=MAX_GOALS as i64 {
+                    std::hint::black_box(DixonColes::poisson_pmf(std::hint::black_box(k), lambda));
+                }
+            }
+        }
+        let after = start.elapsed();
+        assert!(after < before, "table {after:?} vs summed logs {before:?}");
+    }
+
+    fn round_robin_pairs(num_teams: usize) -> Vec<(f64, f64)> {
+        let ratings = LeagueRatings::from_strengths(
+            1.4,
+            HOME_ADVANTAGE,
+            &(0..num_teams)
+                .map(|idx| (0.6 + idx as f64 * 0.05, 1.3 - idx as f64 * 0.03))
+                .collect::<Vec<_>>(),
+        );
+        let mut pairs = Vec::new();
+        for home_idx in 0..num_teams {
+            for away_idx in (0..num_teams).filter(|&away_idx| away_idx != home_idx) {
+                pairs.push(ratings.lambdas(&ParsedFixture {
+                    home_idx,
+                    away_idx,
+                    neutral: false,
+                    result: None,
+                }));
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn warmed_cache_serves_every_fixture_without_a_miss() {
+        let _cache = cache_guard();
+        PROBABILITY_CACHE.clear();
+        let pairs = round_robin_pairs(20);
+        assert_eq!(pairs.len(), 380);
+        assert_eq!(
+            DixonColes::warm_cache(pairs.clone(), DEFAULT_RHO, MAX_GOALS),
+            380
+        );
+        assert_eq!(
+            DixonColes::warm_cache(pairs.clone(), DEFAULT_RHO, MAX_GOALS),
+            0
+        );
+
+        let misses = CACHE_MISSES.load(AtomicOrdering::Relaxed);
+        for &(lambda_h, lambda_a) in &pairs {
+            let cached =
+                DixonColes::get_probability_matrix(lambda_h, lambda_a, DEFAULT_RHO, MAX_GOALS);
+            let fresh = DixonColes::precompute_probability_matrix(
+                lambda_h,
+                lambda_a,
+                DEFAULT_RHO,
+                MAX_GOALS,
+            );
+            assert_eq!(cached.cdf, fresh.cdf);
+        }
+        assert_eq!(CACHE_MISSES.load(AtomicOrdering::Relaxed), misses);
+    }
+
+    #[test]
+    fn parallel_warmup_caches_the_sequential_matrices() {
+        let _cache = cache_guard();
+        let mut pairs = round_robin_pairs(12);
+        pairs.extend(pairs.clone());
+        let cached_cdfs = || {
+            pairs
+                .iter()
+                .map(|&(lambda_h, lambda_a)| {
+                    let key = (
+                        lambda_h.to_bits(),
+                        lambda_a.to_bits(),
+                        DEFAULT_RHO.to_bits(),
+                        MAX_GOALS,
+                    );
+                    PROBABILITY_CACHE.get(&key).expect("warmed").cdf
+                })
+                .collect::<Vec<_>>()
+        };
+
+        PROBABILITY_CACHE.clear();
+        assert_eq!(
+            DixonColes::warm_cache(pairs.clone(), DEFAULT_RHO, MAX_GOALS),
+            132
+        );
+        let sequential = cached_cdfs();
+
+        PROBABILITY_CACHE.clear();
+        let inserted = with_thread_count(Some(4), || {
+            DixonColes::warm_cache_parallel(pairs.clone(), DEFAULT_RHO, MAX_GOALS)
+        })
+        .unwrap();
+        assert_eq!(inserted, 132);
+        assert_eq!(cached_cdfs(), sequential);
+        assert_eq!(
+            DixonColes::warm_cache_parallel(pairs.clone(), DEFAULT_RHO, MAX_GOALS),
+            0
+        );
+    }
+
+    #[test]
+    #[ignore = "benchmark; run with cargo test --release -- --ignored"]
+    fn bench_warm_cache_speeds_up_cold_bulk_runs() {
+        let _cache = cache_guard();
+        let pairs = round_robin_pairs(20);
+        let (num_teams, n_sims) = (20, 20);
+        // Builds the season's FixtureSimulations through the cache, as parsing does.
+        let run = || {
+            let fixtures = (0..num_teams)
+                .flat_map(|home_idx| (0..num_teams).map(move |away_idx| (home_idx, away_idx)))
+                .filter(|(home_idx, away_idx)| home_idx != away_idx)
+                .zip(&pairs)
+                .map(
+                    |((home_idx, away_idx), &(lambda_h, lambda_a))| FixtureSimulation {
+                        home_idx,
+                        away_idx,
+                        distribution: DixonColes::get_probability_matrix(
+                            lambda_h,
+                            lambda_a,
+                            DEFAULT_RHO,
+                            MAX_GOALS,
+                        ),
+                    },
+                )
+                .collect();
+            let input = SimulationInput {
+                fixtures,
+                ..sample_league(num_teams)
+            };
+            std::hint::black_box(run_bulk_simulations(
+                &input,
+                n_sims,
+                334,
+                &BulkOptions::default(),
+            ));
+        };
+
+        let mut cold = std::time::Duration::ZERO;
+        let mut warm = std::time::Duration::ZERO;
+        for _ in 0..20 {
+            PROBABILITY_CACHE.clear();
+            let start = std::time::Instant::now();
+            run();
+            cold += start.elapsed();
+
+            PROBABILITY_CACHE.clear();
+            DixonColes::warm_cache(pairs.clone(), DEFAULT_RHO, MAX_GOALS);
+            let start = std::time::Instant::now();
+            run();
+            warm += start.elapsed();
+        }
+        assert!(warm < cold, "warm {warm:?} vs cold {cold:?}");
+    }
+
+    #[test]
+    fn fixture_validation_reports_every_planted_problem() {
+        let team_to_idx: TeamIndex = ["Ash", "Birch", "Cedar", "Dogwood"]
+            .iter()
+            .enumerate()
+            .map(|(idx, team)| (team.to_string(), idx))
+            .collect();
+        let fixtures = |pairs: &[(&str, &str)]| -> Vec<(usize, (String, String))> {
+            pairs
+                .iter()
+                .map(|&(home, away)| (home.to_string(), away.to_string()))
+                .enumerate()
+                .collect()
+        };
+        let clean = fixtures(&[
+            ("Ash", "Birch"),
+            ("Cedar", "Dogwood"),
+            ("Birch", "Cedar"),
+            ("Dogwood", "Ash"),
+        ]);
+        assert!(fixture_problems(&clean, &team_to_idx).is_empty());
+
+        let planted = fixtures(&[
+            ("Ash", "Birch"),
+            ("Cedar", "Elm"),
+            ("Cedar", "Cedar"),
+            ("Birch", "Dogwood"),
+            ("Ash", "Birch"),
+            ("Ash", "Cedar"),
+        ]);
+        let found: Vec<_> = fixture_problems(&planted, &team_to_idx)
+            .into_iter()
+            .map(|problem| (problem.error_type, problem.fixture_idx))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("unknown_team", 1),
+                ("self_play", 2),
+                ("duplicate_fixture", 4),
+                ("unbalanced_venues", 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn balancing_swaps_even_out_one_directional_schedules() {
+        let num_teams = 9;
+        // Every pair once, the lower index always at home: team 0 hosts everything.
+        let one_way: Vec<(usize, usize)> = (0..num_teams)
+            .flat_map(|home| (home + 1..num_teams).map(move |away| (home, away)))
+            .collect();
+        // And the reverse, where team 0 visits everyone.
+        let other_way: Vec<(usize, usize)> =
+            one_way.iter().map(|&(home, away)| (away, home)).collect();
+        for schedule in [&one_way, &other_way] {
+            for seed in 0..5 {
+                let swaps =
+                    balancing_swaps(schedule, num_teams, &mut ChaCha8Rng::seed_from_u64(seed));
+                let mut fixed = schedule.clone();
+                for &idx in &swaps {
+                    fixed[idx] = (fixed[idx].1, fixed[idx].0);
+                }
+                for (home, away) in venue_counts(&fixed, num_teams) {
+                    assert!(home.abs_diff(away) <= 1, "{home} home vs {away} away");
+                }
+            }
+        }
+
+        // Team 0 two away fixtures short, while no team has two surplus home fixtures.
+        let short = [(1, 0), (2, 0)];
+        let swaps = balancing_swaps(&short, 3, &mut ChaCha8Rng::seed_from_u64(0));
+        assert_eq!(swaps.len(), 1);
+        let mut fixed = short.to_vec();
+        fixed[swaps[0]] = (fixed[swaps[0]].1, fixed[swaps[0]].0);
+        assert_eq!(venue_counts(&fixed, 3)[0], (1, 1));
+
+        let balanced: Vec<(usize, usize)> = (0..num_teams)
+            .map(|team| (team, (team + 1) % num_teams))
+            .collect();
+        assert!(
+            balancing_swaps(&balanced, num_teams, &mut ChaCha8Rng::seed_from_u64(0)).is_empty()
+        );
+    }
+
+    #[test]
+    fn applied_results_add_up_on_the_base_table() {
+        let teams = ["Ash", "Birch", "Cedar", "Dogwood"];
+        let team_to_idx: TeamIndex = teams
+            .iter()
+            .enumerate()
+            .map(|(idx, team)| (team.to_string(), idx))
+            .collect();
+        let base: Vec<Stats> = (0..4)
+            .map(|idx| Stats {
+                matches: 5,
+                wins: idx,
+                draws: 1,
+                losses: 4 - idx,
+                goals_for: 4 + idx,
+                goals_against: 8 - idx,
+                points: 3 * idx + 1,
+            })
+            .collect();
+        let results = [
+            ("Ash", "Birch", 2, 2),
+            ("Cedar", "Dogwood", 0, 3),
+            ("Birch", "Cedar", 4, 1),
+            ("Dogwood", "Ash", 1, 0),
+        ];
+        let mut stats = base.clone();
+        for &(home, away, home_goals, away_goals) in &results {
+            record_played_match(
+                &mut stats,
+                &team_to_idx,
+                (home, away),
+                (home_goals, away_goals),
+            )
+            .unwrap();
+        }
+        for (idx, team) in teams.iter().enumerate() {
+            let (mut scored, mut conceded, mut points) = (0, 0, 0);
+            for &(home, away, home_goals, away_goals) in &results {
+                let (ours, theirs) = match (home == *team, away == *team) {
+                    (true, _) => (home_goals, away_goals),
+                    (_, true) => (away_goals, home_goals),
+                    _ => continue,
+                };
+                scored += ours;
+                conceded += theirs;
+                points += match ours.cmp(&theirs) {
+                    std::cmp::Ordering::Greater => 3,
+                    std::cmp::Ordering::Equal => 1,
+                    std::cmp::Ordering::Less => 0,
+                };
+            }
+            assert_eq!(stats[idx].goals_for, base[idx].goals_for + scored, "{team}");
+            assert_eq!(
+                stats[idx].goals_against,
+                base[idx].goals_against + conceded,
+                "{team}"
+            );
+            assert_eq!(stats[idx].points, base[idx].points + points, "{team}");
+            assert_eq!(stats[idx].matches, base[idx].matches + 2, "{team}");
+        }
+        assert!(record_played_match(&mut stats, &team_to_idx, ("Ash", "Elm"), (1, 0)).is_err());
+    }
+
+    #[test]
+    fn live_results_accumulate_into_the_standings() {
+        let _cache = cache_guard();
+        let config = ModelConfig::default();
+        let rows: Vec<(String, Stats)> = ["Ash", "Birch", "Cedar"]
+            .iter()
+            .map(|&team| {
+                let stats = Stats {
+                    matches: 2,
+                    wins: 1,
+                    losses: 1,
+                    goals_for: 3,
+                    goals_against: 3,
+                    points: 3,
+                    ..Stats::default()
+                };
+                (team.to_string(), stats)
+            })
+            .collect();
+        let mut league = League::new(rows.clone(), &rows, &rows, &config).unwrap();
+        let results = [
+            ("Ash", "Birch", 2, 0),
+            ("Cedar", "Ash", 1, 1),
+            ("Birch", "Cedar", 3, 1),
+        ];
+        let expected_points = [[6, 3, 3], [7, 3, 4], [7, 6, 4]];
+        let expected_goals = [[5, 3, 3], [6, 3, 4], [6, 6, 5]];
+        for (step, &(home, away, home_goals, away_goals)) in results.iter().enumerate() {
+            league
+                .record_result(home, away, home_goals, away_goals)
+                .unwrap();
+            let points: Vec<i64> = league
+                .initial_stats
+                .iter()
+                .map(|stats| stats.points)
+                .collect();
+            let goals: Vec<i64> = league
+                .initial_stats
+                .iter()
+                .map(|stats| stats.goals_for)
+                .collect();
+            assert_eq!(points, expected_points[step]);
+            assert_eq!(goals, expected_goals[step]);
+            assert!(league
+                .initial_stats
+                .iter()
+                .all(|stats| stats.matches == stats.wins + stats.draws + stats.losses));
+
+            let remaining = [league.fixture("Birch", "Ash").unwrap()];
+            let input = league.clone().simulation_input(&remaining, &config);
+            let tally = run_bulk_simulations(&input, 200, 337, &BulkOptions::default());
+            assert_eq!(tally.seasons(), 200);
+        }
+        // Ash leads on 7 points whatever the last match does.
+        let input = league.clone().simulation_input(&[], &config);
+        let tally = run_bulk_simulations(&input, 10, 337, &BulkOptions::default());
+        assert_eq!(tally.position_counts[0][0], 10);
+        assert!(league.record_result("Ash", "Ash", 1, 0).is_err());
+        assert!(league.record_result("Ash", "Oak", 1, 0).is_err());
+        assert!(league.record_result("Ash", "Birch", -1, 0).is_err());
+    }
+
+    #[test]
+    fn cache_capacity_reads_positive_override() {
+        assert_eq!(cache_capacity(Some("128")).get(), 128);
+        assert_eq!(cache_capacity(Some("0")).get(), DEFAULT_CACHE_CAPACITY);
+        assert_eq!(cache_capacity(Some("lots")).get(), DEFAULT_CACHE_CAPACITY);
+        assert_eq!(cache_capacity(None).get(), DEFAULT_CACHE_CAPACITY);
+    }
+
+    #[test]
+    fn probability_cache_never_exceeds_its_capacity() {
+        let _cache = cache_guard();
+        let cap = PROBABILITY_CACHE.capacity();
+        for idx in 0..10_000 {
+            let lambda_h = 0.5 + idx as f64 * 1e-4;
+            DixonColes::get_probability_matrix(lambda_h, 1.0, DEFAULT_RHO, 2);
+            assert!(probability_cache_size() <= cap);
+        }
+    }
+
+    #[test]
+    fn bounded_cache_evicts_the_oldest_batch_at_once() {
+        let cache = BoundedCache::new(NonZeroUsize::new(16).unwrap());
+        for key in 0..16_u64 {
+            assert!(cache.insert(key, key));
+        }
+        assert_eq!(cache.get(&0), Some(0));
+        assert!(cache.insert(16, 16));
+        // An eighth of the capacity goes in one pass, skipping the key just read.
+        assert_eq!(cache.len(), 15);
+        assert!(cache.contains(&0) && cache.contains(&16));
+        assert!(!cache.contains(&1) && !cache.contains(&2));
+    }
+
+    #[test]
+    fn bounded_cache_stays_within_capacity_under_parallel_misses() {
+        let cache = BoundedCache::new(NonZeroUsize::new(64).unwrap());
+        let pool = ThreadPoolBuilder::new().num_threads(8).build().unwrap();
+        // `len` sums the shards one by one, so it is only exact once the inserts have stopped.
+        for round in 0..20_u64 {
+            pool.install(|| {
+                (0..8_u64).into_par_iter().for_each(|thread| {
+                    for idx in 0..1_000_u64 {
+                        cache.insert((round * 8 + thread) * 1_000 + idx, idx);
+                    }
+                })
+            });
+            assert!(cache.len() <= 64, "round {round}: {} entries", cache.len());
+        }
+        cache.clear();
+        assert_eq!(cache.len(), 0);
+        for key in 0..64_u64 {
+            cache.insert(key, key);
+        }
+        assert_eq!(cache.len(), 64);
+    }
+
+    #[test]
+    fn sharded_cache_agrees_with_direct_computation() {
+        let _cache = cache_guard();
+        PROBABILITY_CACHE.clear();
+        let pairs: Vec<(f64, f64)> = (0..1000)
+            .map(|idx| {
+                (
+                    0.4 + (idx % 40) as f64 * 0.07,
+                    0.3 + (idx / 40) as f64 * 0.09,
+                )
+            })
+            .collect();
+        let direct: Vec<Vec<f64>> = pairs
+            .iter()
+            .map(|&(h, a)| {
+                DixonColes::precompute_probability_matrix(h, a, DEFAULT_RHO, MAX_GOALS).cdf
+            })
+            .collect();
+        // Misses then hits, with eight threads racing on every key.
+        for _ in 0..2 {
+            let cached: Vec<Vec<Vec<f64>>> = (0..8)
+                .into_par_iter()
+                .map(|_| {
+                    pairs
+                        .iter()
+                        .map(|&(h, a)| {
+                            DixonColes::get_probability_matrix(h, a, DEFAULT_RHO, MAX_GOALS).cdf
+                        })
+                        .collect()
+                })
+                .collect();
+            assert!(cached.iter().all(|run| *run == direct));
+        }
+        assert_eq!(probability_cache_size(), 1000);
+    }
+
+    #[test]
+    #[ignore = "benchmark; run with cargo test --release -- --ignored"]
+    fn bench_sharded_cache_hits_beat_a_single_lock() {
+        let _cache = cache_guard();
+        // Lock contention needs the threads to really run at once.
+        if num_cpus::get() < 16 {
+            eprintln!("skipped: needs 16 cores, found {}", num_cpus::get());
+            return;
+        }
+        let pairs: Vec<(f64, f64)> = (0..64).map(|idx| (0.8 + idx as f64 * 0.02, 1.1)).collect();
+        let keys: Vec<MatrixKey> = pairs
+            .iter()
+            .map(|&(h, a)| (h.to_bits(), a.to_bits(), DEFAULT_RHO.to_bits(), MAX_GOALS))
+            .collect();
+        let locked: std::sync::Mutex<HashMap<MatrixKey, ProbabilityDistribution>> =
+            std::sync::Mutex::new(HashMap::new());
+        for (&key, &(h, a)) in keys.iter().zip(&pairs) {
+            let distribution = DixonColes::get_probability_matrix(h, a, DEFAULT_RHO, MAX_GOALS);
+            locked.lock().unwrap().insert(key, distribution);
+        }
+        let pool = ThreadPoolBuilder::new().num_threads(16).build().unwrap();
+        let time = |lookup: &(dyn Fn(&MatrixKey) -> ProbabilityDistribution + Sync)| {
+            let start = std::time::Instant::now();
+            pool.install(|| {
+                (0..16).into_par_iter().for_each(|_| {
+                    for _ in 0..2_000 {
+                        for key in &keys {
+                            std::hint::black_box(lookup(key));
+                        }
+                    }
+                })
+            });
+            start.elapsed()
+        };
+        let single_lock = time(&|key| locked.lock().unwrap()[key].clone());
+        let sharded = time(&|key| PROBABILITY_CACHE.get(key).unwrap());
+        assert!(
+            sharded < single_lock,
+            "sharded {sharded:?} vs one mutex {single_lock:?}"
+        );
+    }
+
+    #[test]
+    fn flushed_cache_repopulates_identical_matrices() {
+        let _cache = cache_guard();
+        let lambdas = [(1.31, 0.92), (2.05, 1.4), (0.61, 0.58)];
+        let first: Vec<Vec<f64>> = lambdas
+            .iter()
+            .map(|&(h, a)| DixonColes::get_probability_matrix(h, a, DEFAULT_RHO, MAX_GOALS).cdf)
+            .collect();
+        flush_probability_cache();
+        let second: Vec<Vec<f64>> = lambdas
+            .iter()
+            .map(|&(h, a)| DixonColes::get_probability_matrix(h, a, DEFAULT_RHO, MAX_GOALS).cdf)
+            .collect();
+        assert_eq!(first, second);
+        assert!(probability_cache_size() >= 1);
+    }
+
+    #[test]
+    fn identical_lambdas_hit_the_cache_after_a_flush() {
+        let _cache = cache_guard();
+        let even = |_| 1.2;
+        league_from_strengths(11, even);
+        flush_probability_cache();
+        reset_cache_stats();
+        let input = league_from_strengths(11, even);
+        run_bulk_simulations(&input, 50, 3, &BulkOptions::default());
+
+        let (hits, misses) = cache_stats();
+        assert_eq!(hits + misses, input.fixtures.len() as u64);
+        let hit_rate = hits as f64 / (hits + misses) as f64;
+        assert!(hit_rate > 0.99, "hit rate {hit_rate:.3}");
+    }
+
+    #[test]
+    fn recorded_fixture_scores_add_up_to_the_standings() {
+        let _cache = cache_guard();
+        let input = sample_league(6);
+        let season = simulate_season_recording(&input, &mut season_rng(Some(17)), true);
+        assert_eq!(season.results.len(), input.fixtures.len());
+
+        let mut goals = vec![(0, 0); input.teams.len()];
+        for result in &season.results {
+            goals[result.home_idx].0 += result.home_goals;
+            goals[result.home_idx].1 += result.away_goals;
+            goals[result.away_idx].0 += result.away_goals;
+            goals[result.away_idx].1 += result.home_goals;
+        }
+        for (stats, &(scored, conceded)) in season.final_stats.iter().zip(&goals) {
+            assert_eq!((stats.goals_for, stats.goals_against), (scored, conceded));
+        }
+        assert!(simulate_single_season(&input, &mut season_rng(Some(17)))
+            .results
+            .is_empty());
+    }
+
+    #[test]
+    fn equal_lambdas_without_correlation_are_symmetric() {
+        let (home, draw, away) =
+            DixonColes::precompute_probability_matrix(1.35, 1.35, 0.0, MAX_GOALS)
+                .outcome_probabilities();
+        assert!((home - away).abs() < 1e-12);
+        assert!((home + draw + away - 1.0).abs() < 1e-9);
+
+        for &(lambda_h, lambda_a, rho) in &[(0.4, 2.7, -0.2), (3.1, 0.9, 0.15), (0.0, 1.2, 0.0)] {
+            let (home, draw, away) =
+                DixonColes::precompute_probability_matrix(lambda_h, lambda_a, rho, MAX_GOALS)
+                    .outcome_probabilities();
+            assert!((home + draw + away - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn bulk_outcomes_match_single_fixture_results() {
+        let _cache = cache_guard();
+        let pairs = [(1.4, 1.0), (0.8, 2.2), (1.4, 1.0), (2.9, 0.3), (0.8, 2.2)];
+        let bulk = bulk_outcome_probs(&pairs, -0.13, MAX_GOALS);
+        assert_eq!(bulk.len(), pairs.len());
+        for (&(lambda_h, lambda_a), outcome) in pairs.iter().zip(&bulk) {
+            let single =
+                DixonColes::precompute_probability_matrix(lambda_h, lambda_a, -0.13, MAX_GOALS)
+                    .outcome_probabilities();
+            assert_eq!(*outcome, single);
+        }
+    }
+
+    #[test]
+    fn goalless_draw_gets_less_likely_as_lambdas_grow() {
+        let goalless = |lambda: f64| {
+            DixonColes::precompute_probability_matrix(lambda, lambda, DEFAULT_RHO, MAX_GOALS)
+                .scorelines()
+                .find(|&(score, _)| score == (0, 0))
+                .map(|(_, probability)| probability)
+                .unwrap()
+        };
+        assert!(goalless(2.2) < goalless(1.1));
+        assert!(goalless(1.1) < goalless(0.5));
+
+        let scores: Vec<_> = DixonColes::precompute_probability_matrix(1.6, 1.2, DEFAULT_RHO, 7)
+            .scorelines()
+            .collect();
+        assert_eq!(scores.len(), 64);
+        let total: f64 = scores.iter().map(|(_, probability)| probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn raising_the_total_goals_line_lowers_the_over_probability() {
+        let _cache = cache_guard();
+        let pd = DixonColes::get_probability_matrix(1.7, 1.2, DEFAULT_RHO, MAX_GOALS);
+        let mut previous_over = f64::INFINITY;
+        for line in [0.5, 1.5, 2.5, 3.5, 4.5] {
+            let (over, under, push) = pd.total_goals_split(line);
+            assert!((over + under + push - 1.0).abs() < 1e-9);
+            assert_eq!(push, 0.0);
+            assert!(over < previous_over, "over {over} at line {line}");
+            previous_over = over;
+        }
+
+        let (over, under, push) = pd.total_goals_split(2.0);
+        assert!(push > 0.0);
+        assert!((over - pd.total_goals_split(2.5).0).abs() < 1e-12);
+        assert!((under - pd.total_goals_split(1.5).1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn both_teams_score_tracks_the_weaker_attack() {
+        let btts = |lambda_h: f64, lambda_a: f64| {
+            DixonColes::precompute_probability_matrix(lambda_h, lambda_a, DEFAULT_RHO, MAX_GOALS)
+                .both_teams_score()
+        };
+        assert!(btts(1.2, 1.0) < btts(1.8, 1.5));
+        assert!(btts(1.8, 1.5) < btts(2.6, 2.2));
+        assert!(btts(1.5, 0.01) < 0.01);
+    }
+
+    #[test]
+    fn clean_sheets_follow_the_opposing_attack_and_correlate_at_nil_nil() {
+        let uncorrelated = |lambda_h: f64| {
+            DixonColes::precompute_probability_matrix(lambda_h, 1.1, 0.0, MAX_GOALS).clean_sheets()
+        };
+        // Without the Dixon-Coles correction each side's clean sheet ignores its own attack.
+        assert!((uncorrelated(0.8).0 - uncorrelated(2.4).0).abs() < 1e-6);
+        assert!((uncorrelated(0.8).0 - (-1.1f64).exp()).abs() < 1e-6);
+
+        let pd = DixonColes::precompute_probability_matrix(1.3, 1.1, DEFAULT_RHO, MAX_GOALS);
+        let (home_cs, away_cs) = pd.clean_sheets();
+        let nil_nil = pd.probabilities().next().unwrap();
+        assert!(home_cs * away_cs < nil_nil);
+    }
+
+    #[test]
+    fn full_scale_first_half_matches_the_whole_match() {
+        let _cache = cache_guard();
+        let (first_half, full_time) = halftime_outcome_probs(1.6, 1.1, DEFAULT_RHO, 1.0, MAX_GOALS);
+        assert_eq!(first_half, full_time);
+        assert_eq!(
+            full_time,
+            match_outcome_probs(1.6, 1.1, DEFAULT_RHO, MAX_GOALS)
+        );
+
+        let (first_half, full_time) =
+            halftime_outcome_probs(1.6, 1.1, DEFAULT_RHO, DEFAULT_HALF_SCALE, MAX_GOALS);
+        assert!(first_half.1 > full_time.1);
+    }
+
+    #[test]
+    fn points_histograms_cover_every_simulated_season() {
+        let _cache = cache_guard();
+        let input = sample_league(6);
+        let options = BulkOptions {
+            top_k_tables: 5,
+            points_distribution: true,
+            ..BulkOptions::default()
+        };
+        let tally = run_bulk_simulations(&input, 500, 11, &options);
+        let points = tally.points.expect("points histogram requested");
+        for histogram in &points {
+            assert_eq!(histogram.values().sum::<u64>(), 500);
+            assert!(histogram.keys().all(|&value| (0..=30).contains(&value)));
+        }
+        assert!(
+            run_bulk_simulations(&input, 10, 11, &BulkOptions::default())
+                .points
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn record_histograms_match_expected_wins() {
+        let _cache = cache_guard();
+        let mut input = sample_league(6);
+        input.initial_stats[0] = Stats {
+            matches: 4,
+            wins: 3,
+            losses: 1,
+            ..Stats::default()
+        };
+        let options = BulkOptions {
+            record: true,
+            ..BulkOptions::default()
+        };
+        let n_sims = 20_000;
+        let tally = run_bulk_simulations(&input, n_sims, 316, &options);
+        let records = tally.records.expect("record histograms requested");
+
+        let mut expected_wins = vec![0.0; input.teams.len()];
+        expected_wins[0] = 3.0;
+        for fixture in &input.fixtures {
+            let (home_win, _, away_win) = fixture.distribution.outcome_probabilities();
+            expected_wins[fixture.home_idx] += home_win;
+            expected_wins[fixture.away_idx] += away_win;
+        }
+        for (record, expected) in records.iter().zip(expected_wins) {
+            // Each team plays ten fixtures, so wins + draws + losses is fixed per season.
+            let played = |histogram: &HashMap<i64, u64>| {
+                histogram
+                    .iter()
+                    .map(|(&k, &c)| k as f64 * c as f64)
+                    .sum::<f64>()
+            };
+            assert!(record
+                .iter()
+                .all(|histogram| histogram.values().sum::<u64>() == n_sims as u64));
+            let mean_wins = played(&record[0]) / n_sims as f64;
+            assert!(
+                (mean_wins - expected).abs() < 0.05,
+                "{mean_wins} vs {expected}"
+            );
+            let games = record.iter().map(played).sum::<f64>() / n_sims as f64;
+            assert!(games == 10.0 || games == 14.0, "games {games}");
+        }
+        assert!(
+            run_bulk_simulations(&input, 10, 316, &BulkOptions::default())
+                .records
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn goal_histograms_average_to_the_scoring_rates() {
+        let _cache = cache_guard();
+        let num_teams = 6;
+        let input = sample_league(num_teams);
+        let options = BulkOptions {
+            goals: true,
+            ..BulkOptions::default()
+        };
+        let n_sims = 20_000;
+        let tally = run_bulk_simulations(&input, n_sims, 317, &options);
+        let goals = tally.goals.expect("goal histograms requested");
+
+        // sample_league scores at `strength(home) * HOME_ADVANTAGE` at home and `strength(away)`
+        // away, against every opponent once at each venue.
+        let strength = |idx: usize| 0.7 + idx as f64 * 0.8 / num_teams as f64;
+        let mean = |histogram: &HashMap<i64, u64>| {
+            assert_eq!(histogram.values().sum::<u64>(), n_sims as u64);
+            let total: f64 = histogram.iter().map(|(&k, &c)| k as f64 * c as f64).sum();
+            total / n_sims as f64
+        };
+        for (team, [goals_for, goals_against]) in goals.iter().enumerate() {
+            let opponents = (0..num_teams).filter(|&other| other != team);
+            let expected_for: f64 = opponents
+                .clone()
+                .map(|_| strength(team) * (HOME_ADVANTAGE + 1.0))
+                .sum();
+            let expected_against: f64 = opponents
+                .map(|other| strength(other) * (HOME_ADVANTAGE + 1.0))
+                .sum();
+            let (scored, conceded) = (mean(goals_for), mean(goals_against));
+            assert!(
+                (scored / expected_for - 1.0).abs() < 0.02,
+                "{scored} vs {expected_for}"
+            );
+            assert!(
+                (conceded / expected_against - 1.0).abs() < 0.02,
+                "{conceded} vs {expected_against}"
+            );
+        }
+    }
+
+    #[test]
+    fn clean_sheets_follow_the_poisson_zero_probability() {
+        let _cache = cache_guard();
+        // Ten meetings: the hosts score at 2.0 and concede at 0.4, without the low-score
+        // correction, so each side keeps a clean sheet with probability exp(-lambda).
+        let (lambda_h, lambda_a) = (2.0, 0.4);
+        let fixtures = (0..10)
+            .map(|_| FixtureSimulation {
+                home_idx: 0,
+                away_idx: 1,
+                distribution: DixonColes::get_probability_matrix(
+                    lambda_h, lambda_a, 0.0, MAX_GOALS,
+                ),
+            })
+            .collect();
+        let input = SimulationInput {
+            teams: vec!["Wall".to_string(), "Sieve".to_string()],
+            initial_stats: vec![Stats::default(); 2],
+            fixtures,
+            tiebreakers: Vec::new(),
+        };
+        let options = BulkOptions {
+            clean_sheets: true,
+            ..BulkOptions::default()
+        };
+        let n_sims = 20_000;
+        let tally = run_bulk_simulations(&input, n_sims, 318, &options);
+        let clean_sheets = tally.clean_sheets.expect("clean sheets requested");
+        let mean = |histogram: &HashMap<i64, u64>| {
+            assert_eq!(histogram.values().sum::<u64>(), n_sims as u64);
+            let total: f64 = histogram.iter().map(|(&k, &c)| k as f64 * c as f64).sum();
+            total / n_sims as f64
+        };
+        let (wall, sieve) = (mean(&clean_sheets[0]), mean(&clean_sheets[1]));
+        assert!(wall > sieve * 3.0, "{wall} vs {sieve}");
+        let expected = 10.0 * f64::exp(-lambda_a);
+        assert!((wall - expected).abs() < 0.05, "{wall} vs {expected}");
+        let expected = 10.0 * f64::exp(-lambda_h);
+        assert!((sieve - expected).abs() < 0.05, "{sieve} vs {expected}");
+        // Keeping all ten is binomial with p = exp(-lambda_a).
+        let all_ten = clean_sheets[0].get(&10).copied().unwrap_or(0) as f64 / n_sims as f64;
+        let expected = f64::exp(-lambda_a).powi(10);
+        assert!(
+            (all_ten - expected).abs() < 0.005,
+            "{all_ten} vs {expected}"
+        );
+    }
+
+    #[test]
+    fn typical_seasons_are_dominated_by_one_goal_scorelines() {
+        let _cache = cache_guard();
+        let input = league_from_strengths(20, |_| 1.3);
+        let mut rng = ChaCha8Rng::seed_from_u64(319);
+        let mut totals: HashMap<(i64, i64), u64> = HashMap::new();
+        for _ in 0..1000 {
+            let season = simulate_season_recording(&input, &mut rng, true);
+            let counts = scoreline_frequencies(&season.results);
+            assert_eq!(counts.values().sum::<u64>(), 380);
+            for (scoreline, count) in counts {
+                *totals.entry(scoreline).or_insert(0) += count;
+            }
+        }
+        let (&most_common, _) = totals.iter().max_by_key(|(_, &count)| count).unwrap();
+        assert!(
+            most_common == (1, 1) || most_common == (1, 0),
+            "most common scoreline {most_common:?}"
+        );
+    }
+
+    #[test]
+    fn bootstrap_intervals_narrow_with_more_seasons_and_cover_the_truth() {
+        let mut rng = ChaCha8Rng::seed_from_u64(322);
+        let width = |rng: &mut ChaCha8Rng, n_sims: usize| {
+            let count = (0.3 * n_sims as f64) as u64;
+            let (low, high) = bootstrap_interval(rng, count, n_sims, 2_000, 0.95).unwrap();
+            assert!(low <= 0.3 && 0.3 <= high);
+            high - low
+        };
+        let (small, large) = (width(&mut rng, 100), width(&mut rng, 10_000));
+        assert!(large < small / 5.0, "{large} vs {small}");
+
+        // Simulated bulk runs with a true probability of 0.3: a 90% interval should contain
+        // it in about 90% of runs.
+        let (n_sims, trials) = (500, 400);
+        let observed = rand_distr::Binomial::new(n_sims as u64, 0.3).unwrap();
+        let covered = (0..trials)
+            .filter(|_| {
+                let count = rand_distr::Distribution::sample(&observed, &mut rng);
+                let (low, high) = bootstrap_interval(&mut rng, count, n_sims, 500, 0.9).unwrap();
+                low <= 0.3 && 0.3 <= high
+            })
+            .count();
+        let coverage = covered as f64 / trials as f64;
+        assert!((0.85..=0.95).contains(&coverage), "coverage {coverage}");
+
+        assert!(bootstrap_interval(&mut rng, 1, 10, 100, 1.0).is_err());
+        assert!(bootstrap_interval(&mut rng, 11, 10, 100, 0.9).is_err());
+    }
+
+    #[test]
+    fn antithetic_pairs_shrink_the_title_probability_error() {
+        let _cache = cache_guard();
+        let input = sample_league(6);
+        let leader = input.teams.len() - 1;
+        let n_sims = 200;
+        let spread = |antithetic: bool| {
+            let options = BulkOptions {
+                antithetic,
+                ..BulkOptions::default()
+            };
+            let estimates: Vec<f64> = (0..200)
+                .map(|run| {
+                    let tally = run_bulk_simulations(&input, n_sims, 323 + run, &options);
+                    assert_eq!(tally.position_counts[0].iter().sum::<u64>(), n_sims as u64);
+                    tally.position_counts[leader][0] as f64 / n_sims as f64
+                })
+                .collect();
+            let mean = estimates.iter().sum::<f64>() / estimates.len() as f64;
+            let variance = estimates.iter().map(|p| (p - mean).powi(2)).sum::<f64>()
+                / (estimates.len() - 1) as f64;
+            (mean, variance.sqrt())
+        };
+        let (plain_mean, plain_error) = spread(false);
+        let (paired_mean, paired_error) = spread(true);
+        assert!(
+            paired_error < plain_error * 0.9,
+            "{paired_error} vs {plain_error}"
+        );
+        assert!(
+            (paired_mean - plain_mean).abs() < 0.02,
+            "{paired_mean} vs {plain_mean}"
+        );
+    }
+
+    #[test]
+    fn importance_sampling_matches_brute_force_for_a_long_shot() {
+        let _cache = cache_guard();
+        let config = ModelConfig {
+            home_advantage: Some(HOME_ADVANTAGE),
+            ..ModelConfig::default()
+        };
+        // Team 5 scores far less than the rest, so a title is a long shot.
+        let rows: Vec<(String, Stats)> = (0..6)
+            .map(|idx| {
+                let stats = Stats {
+                    matches: 10,
+                    goals_for: if idx == 5 { 9 } else { 15 },
+                    goals_against: 14,
+                    ..Stats::default()
+                };
+                (format!("Team {idx}"), stats)
+            })
+            .collect();
+        let league = League::new(rows.clone(), &rows, &rows, &config).unwrap();
+        let mut fixtures = Vec::new();
+        for home in &rows {
+            for away in &rows {
+                if home.0 != away.0 {
+                    fixtures.push(league.fixture(&home.0, &away.0).unwrap());
+                }
+            }
+        }
+        let real = league.clone().simulation_input(&fixtures, &config);
+        let n_brute = 200_000;
+        let brute_force = SeasonMetric::Championship.share(
+            &run_bulk_simulations(&real, n_brute, 324, &BulkOptions::default()).position_counts[5],
+            n_brute,
+        );
+        assert!(brute_force > 0.0 && brute_force < 0.05, "{brute_force}");
+
+        for scale in [1.0, 1.3, 1.6] {
+            let proposal = SimulationInput {
+                fixtures: fixtures
+                    .iter()
+                    .map(|fixture| {
+                        let scale = match (fixture.home_idx, fixture.away_idx) {
+                            (5, _) => (scale, 1.0),
+                            (_, 5) => (1.0, scale),
+                            _ => (1.0, 1.0),
+                        };
+                        league
+                            .ratings
+                            .scaled_fixture_simulation(fixture, &config, scale)
+                    })
+                    .collect(),
+                ..real.clone()
+            };
+            let n_sims = 20_000;
+            let estimate = importance_sampled_probability(
+                &real,
+                &proposal,
+                5,
+                SeasonMetric::Championship,
+                n_sims,
+                325,
+            );
+            let brute_error = (brute_force * (1.0 - brute_force) / n_brute as f64).sqrt();
+            let tolerance = 4.0 * (estimate.std_error.powi(2) + brute_error.powi(2)).sqrt();
+            assert!(
+                (estimate.probability - brute_force).abs() < tolerance,
+                "scale {scale}: {estimate:?} vs {brute_force}"
+            );
+            if scale == 1.0 {
+                assert!((estimate.effective_sample_size - n_sims as f64).abs() < 1e-6);
+            } else {
+                let plain_error = (brute_force * (1.0 - brute_force) / n_sims as f64).sqrt();
+                assert!(
+                    estimate.std_error < plain_error,
+                    "{estimate:?} vs {plain_error}"
+                );
+            }
+        }
+        assert_eq!(SeasonMetric::Relegation.positions(20), 17..20);
+    }
+
+    fn chunked_options(chunk_size: usize) -> BulkOptions {
+        BulkOptions {
+            chunk_size: Some(chunk_size),
+            ..BulkOptions::default()
+        }
+    }
+
+    #[test]
+    fn chunk_size_leaves_the_tally_unchanged() {
+        let _cache = cache_guard();
+        let input = sample_league(4);
+        let whole = run_bulk_simulations(&input, 300, 329, &BulkOptions::default());
+        for chunk_size in [1, 7, 300, 1000] {
+            let chunked = run_bulk_simulations(&input, 300, 329, &chunked_options(chunk_size));
+            assert_eq!(
+                chunked.position_counts, whole.position_counts,
+                "chunk_size {chunk_size}"
+            );
+        }
+    }
+
+    #[test]
+    fn title_rivals_finish_positions_are_negatively_correlated() {
+        let _cache = cache_guard();
+        // Two strong sides well clear of two weak ones: one contender's title is the other's
+        // second place.
+        let input = league_from_strengths(4, |idx| [2.4, 2.3, 0.6, 0.5][idx]);
+        let options = |chunk_size| BulkOptions {
+            full_history: true,
+            chunk_size: Some(chunk_size),
+            ..BulkOptions::default()
+        };
+        let tally = run_bulk_simulations(&input, 2_000, 343, &options(2_000));
+        let history = tally.history.expect("full_history was requested");
+        assert_eq!(history.len(), 2_000);
+        for (team, counts) in tally.position_counts.iter().enumerate() {
+            for (position, &count) in counts.iter().enumerate() {
+                let seasons = history.iter().filter(|season| season[team] == position);
+                assert_eq!(seasons.count() as u64, count);
+            }
+        }
+        let chunked = run_bulk_simulations(&input, 2_000, 343, &options(7));
+        assert_eq!(chunked.history.as_ref(), Some(&history));
+
+        let correlations = position_correlations(&history);
+        assert!(correlations[0][1] < -0.8, "{correlations:?}");
+        for (team, row) in correlations.iter().enumerate() {
+            assert_eq!(row[team], 1.0);
+            for (other, &value) in row.iter().enumerate() {
+                assert!((value - correlations[other][team]).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn joint_top_n_shares_over_every_group_sum_to_one() {
+        let _cache = cache_guard();
+        let options = BulkOptions {
+            full_history: true,
+            ..BulkOptions::default()
+        };
+        let tally = run_bulk_simulations(&sample_league(5), 1_000, 344, &options);
+        let history = tally.history.expect("full_history was requested");
+        for n in 1..=5 {
+            // Every n-team group, as bit masks over the five teams.
+            let total: f64 = (0u32..1 << 5)
+                .filter(|mask| mask.count_ones() as usize == n)
+                .map(|mask| {
+                    let teams: Vec<usize> = (0..5).filter(|team| mask >> team & 1 == 1).collect();
+                    joint_top_n_share(&history, &teams, n)
+                })
+                .sum();
+            assert!((total - 1.0).abs() < 1e-12, "n = {n}: {total}");
+        }
+        let leader_share = tally.position_counts[4][0] as f64 / 1_000.0;
+        assert_eq!(joint_top_n_share(&history, &[4], 1), leader_share);
+    }
+
+    #[test]
+    fn position_quantiles_sit_where_the_cdf_crosses_them() {
+        // A favourite that usually wins but sometimes collapses: the mean hides the skew.
+        let skewed = position_summary(&[600, 100, 100, 200], 1_000);
+        assert_eq!((skewed.median, skewed.p25, skewed.p75), (1.0, 1.0, 3.0));
+        assert!((skewed.mean - 1.9).abs() < 1e-12);
+        assert!((skewed.std - 1.49_f64.sqrt()).abs() < 1e-12);
+
+        let _cache = cache_guard();
+        let n_sims = 2_000;
+        let tally = run_bulk_simulations(&sample_league(6), n_sims, 345, &BulkOptions::default());
+        for counts in &tally.position_counts {
+            let summary = position_summary(counts, n_sims);
+            let cdf = |place: f64| -> f64 {
+                counts[..place as usize].iter().sum::<u64>() as f64 / n_sims as f64
+            };
+            for (quantile, level) in [
+                (summary.p25, 0.25),
+                (summary.median, 0.5),
+                (summary.p75, 0.75),
+            ] {
+                assert!(cdf(quantile - 1.0) < level && cdf(quantile) >= level);
+            }
+            let inner = cdf(summary.p75) - cdf(summary.p25 - 1.0);
+            assert!(inner >= 0.5, "{summary:?} holds {inner}");
+        }
+    }
+
+    #[test]
+    fn gini_tells_open_title_races_from_foregone_ones() {
+        assert_eq!(normalized_gini(&[0.25; 4]), 0.0);
+        assert!((normalized_gini(&[1.0, 0.0, 0.0, 0.0]) - 1.0).abs() < 1e-12);
+
+        let _cache = cache_guard();
+        let n_sims = 2_000;
+        let even = run_bulk_simulations(
+            &league_from_strengths(4, |_| 1.2),
+            n_sims,
+            346,
+            &BulkOptions::default(),
+        );
+        let even_ginis = position_ginis(&even.position_counts, n_sims);
+        assert!(even_ginis.iter().all(|&gini| gini < 0.1), "{even_ginis:?}");
+
+        let dominant = league_from_strengths(4, |idx| if idx == 0 { 4.0 } else { 0.6 });
+        let dominant = run_bulk_simulations(&dominant, n_sims, 346, &BulkOptions::default());
+        let dominant_ginis = position_ginis(&dominant.position_counts, n_sims);
+        assert!(dominant_ginis[0] > 0.95, "{dominant_ginis:?}");
+    }
+
+    #[test]
+    #[ignore = "benchmark; run with cargo test --release -- --ignored"]
+    fn bench_small_runs_skip_the_thread_pool() {
+        let _cache = cache_guard();
+        // No fixtures left, so a season costs about as much as the per-task bookkeeping.
+        let mut input = sample_league(2);
+        input.fixtures.clear();
+        let time = |chunk_size: usize| {
+            let options = chunked_options(chunk_size);
+            let start = std::time::Instant::now();
+            for _ in 0..200 {
+                std::hint::black_box(run_bulk_simulations(&input, 100, 329, &options));
+            }
+            start.elapsed()
+        };
+        let (sequential, chunked) = (time(1000), time(1));
+        assert!(
+            sequential * 2 < chunked,
+            "sequential {sequential:?} vs one season per task {chunked:?}"
+        );
+    }
+
+    #[test]
+    fn deadline_returns_the_seasons_finished_in_time() {
+        let _cache = cache_guard();
+        let input = sample_league(4);
+        let n_sims = 10_000_000;
+        let options = BulkOptions {
+            deadline: Some(Instant::now() + Duration::from_millis(50)),
+            ..BulkOptions::default()
+        };
+        let tally = run_bulk_simulations(&input, n_sims, 328, &options);
+        let completed = tally.seasons();
+        assert!(
+            completed > 0 && completed < n_sims as u64 / 10,
+            "{completed}"
+        );
+        assert!(tally
+            .position_counts
+            .iter()
+            .all(|counts| counts.iter().sum::<u64>() == completed));
+    }
+
+    #[test]
+    fn every_block_checks_an_expired_deadline_on_its_first_season() {
+        let _cache = cache_guard();
+        let input = sample_league(4);
+        let options = BulkOptions {
+            deadline: Some(Instant::now()),
+            chunk_size: Some(40),
+            ..BulkOptions::default()
+        };
+        // None of these indices is a multiple of `DEADLINE_CHECK_EVERY`.
+        let tally = run_bulk_range(&input, 101..181, 328, &options);
+        assert_eq!(tally.seasons(), 0);
+    }
+
+    #[test]
+    fn open_leagues_need_more_seasons_to_converge() {
+        let _cache = cache_guard();
+        let converge = |input: SimulationInput| {
+            let (tally, played) =
+                converging_tally(&input, 0, SeasonMetric::Championship, 0.002, 20_000, 327);
+            let exact = run_bulk_simulations(&input, played, 327, &BulkOptions::default());
+            assert_eq!(tally.position_counts, exact.position_counts);
+            played
+        };
+        let level = converge(league_from_strengths(6, |_| 1.0));
+        let dominated = converge(league_from_strengths(
+            6,
+            |idx| if idx == 0 { 4.0 } else { 1.0 },
+        ));
+        assert!(dominated < level, "{dominated} vs {level}");
+        assert_eq!(dominated % CONVERGENCE_BATCH, 0);
+    }
+
+    #[test]
+    fn stratified_draws_steady_the_draw_frequency() {
+        let _cache = cache_guard();
+        let distribution = DixonColes::get_probability_matrix(1.3, 1.1, DEFAULT_RHO, MAX_GOALS);
+        let one_all = distribution.probability(1, 1);
+        let n_sims = 500;
+        // Share of 1-1 results among `n_sims` draws of one fixture, over 200 independent runs.
+        let spread = |stratified: bool| {
+            let shares: Vec<f64> = (0..200)
+                .map(|run| {
+                    let stratification = Stratification::new(1, n_sims, run).unwrap();
+                    let mut rng = ChaCha8Rng::seed_from_u64(326 + run);
+                    let draws = (0..n_sims)
+                        .filter(|&sim_index| {
+                            let u = if stratified {
+                                stratification.uniform(sim_index, 0, &mut rng)
+                            } else {
+                                rand::Rng::gen(&mut rng)
+                            };
+                            DixonColes::score_at(&distribution, u) == (1, 1)
+                        })
+                        .count();
+                    draws as f64 / n_sims as f64
+                })
+                .collect();
+            let mean = shares.iter().sum::<f64>() / shares.len() as f64;
+            let variance =
+                shares.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / (shares.len() - 1) as f64;
+            (mean, variance)
+        };
+        let ((plain_mean, plain_variance), (strata_mean, strata_variance)) =
+            (spread(false), spread(true));
+        assert!((plain_mean - one_all).abs() < 0.005 && (strata_mean - one_all).abs() < 0.005);
+        assert!(
+            strata_variance < plain_variance / 10.0,
+            "{strata_variance} vs {plain_variance}"
+        );
+
+        let input = sample_league(4);
+        let tally = run_bulk_stratified(&input, 300, 326, &BulkOptions::default()).unwrap();
+        assert!(tally
+            .position_counts
+            .iter()
+            .all(|counts| counts.iter().sum::<u64>() == 300));
+    }
+
+    #[test]
+    fn sobol_points_fill_every_stratum() {
+        use crate::qmc::Sobol;
+
+        let sobol = Sobol::new(12);
+        assert_eq!(sobol.dimensions(), 12);
+        // The first 2^m points put exactly one coordinate in each interval [k / 2^m, ...).
+        for m in [3, 6] {
+            let n = 1u32 << m;
+            let points: Vec<Vec<f64>> = (0..n).map(|idx| sobol.point(idx)).collect();
+            for dimension in 0..12 {
+                let mut strata: Vec<u32> = points
+                    .iter()
+                    .map(|point| (point[dimension] * n as f64) as u32)
+                    .collect();
+                strata.sort_unstable();
+                assert_eq!(strata, (0..n).collect::<Vec<_>>(), "dimension {dimension}");
+            }
+        }
+        let shifted = Sobol::shifted(12, 325).point(5);
+        assert!(shifted.iter().all(|u| (0.0..1.0).contains(u)));
+        assert_ne!(shifted, sobol.point(5));
+    }
+
+    #[test]
+    fn qmc_reaches_the_title_odds_with_fewer_seasons() {
+        let _cache = cache_guard();
+        let input = league_from_strengths(4, |idx| 0.9 + idx as f64 * 0.2);
+        let n_reference = 400_000;
+        let reference = run_bulk_simulations(&input, n_reference, 325, &BulkOptions::default());
+        let truth = reference.position_counts[3][0] as f64 / n_reference as f64;
+        // Root-mean-square error of the leader's title probability over independent runs.
+        let rmse = |n_sims: usize, qmc: bool| {
+            let runs = 30;
+            let squared: f64 = (0..runs)
+                .map(|run| {
+                    let seed = 1_000 + run;
+                    let options = BulkOptions::default();
+                    let tally = if qmc {
+                        run_bulk_qmc(&input, n_sims, seed, &options).unwrap()
+                    } else {
+                        run_bulk_simulations(&input, n_sims, seed, &options)
+                    };
+                    assert_eq!(tally.position_counts[3].iter().sum::<u64>(), n_sims as u64);
+                    (tally.position_counts[3][0] as f64 / n_sims as f64 - truth).powi(2)
+                })
+                .sum();
+            (squared / runs as f64).sqrt()
+        };
+        for n_sims in [1024, 4096] {
+            let (qmc, doubled_mc) = (rmse(n_sims, true), rmse(2 * n_sims, false));
+            assert!(
+                qmc < doubled_mc,
+                "{n_sims}: qmc {qmc} vs mc at twice the seasons {doubled_mc}"
+            );
+        }
+    }
+
+    #[test]
+    fn competitiveness_falls_when_one_team_dominates() {
+        let _cache = cache_guard();
+        let config = ModelConfig {
+            home_advantage: Some(HOME_ADVANTAGE),
+            ..ModelConfig::default()
+        };
+        let index = |dominant_goals: i64| {
+            let rows: Vec<(String, Stats)> = (0..4)
+                .map(|idx| {
+                    let goals_for = if idx == 0 { dominant_goals } else { 12 };
+                    let stats = Stats {
+                        matches: 10,
+                        goals_for,
+                        goals_against: 12,
+                        ..Stats::default()
+                    };
+                    (format!("Team {idx}"), stats)
+                })
+                .collect();
+            let league = League::new(rows.clone(), &rows, &rows, &config).unwrap();
+            let mut fixtures = Vec::new();
+            for home in &rows {
+                for away in &rows {
+                    if home.0 != away.0 {
+                        fixtures.push(league.fixture(&home.0, &away.0).unwrap());
+                    }
+                }
+            }
+            let input = league.simulation_input(&fixtures, &config);
+            let tally = run_bulk_simulations(&input, 20_000, 313, &BulkOptions::default());
+            competitiveness(&tally.position_counts, 20_000)
+        };
+        let equal = index(12);
+        let dominated = index(40);
+        assert!(equal > 0.97, "equal {equal}");
+        assert!(
+            dominated < equal - 0.1,
+            "dominated {dominated} vs equal {equal}"
+        );
+
+        assert_eq!(competitiveness(&[vec![5, 0], vec![0, 5]], 5), 0.0);
+        assert!((competitiveness(&[vec![5, 5], vec![5, 5]], 10) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn zone_shares_add_up_to_zone_sizes_across_the_league() {
+        let _cache = cache_guard();
+        let input = sample_league(8);
+        let n_sims = 400;
+        let tally = run_bulk_simulations(&input, n_sims, 5, &BulkOptions::default());
+        let shares: Vec<(f64, f64, f64)> = tally
+            .position_counts
+            .iter()
+            .map(|counts| zone_shares(counts, 3, 2, n_sims))
+            .collect();
+        let relegation: f64 = shares.iter().map(|share| share.0).sum();
+        let promotion: f64 = shares.iter().map(|share| share.1).sum();
+        let mid_table: f64 = shares.iter().map(|share| share.2).sum();
+        assert!((relegation - 3.0).abs() < 1e-9);
+        assert!((promotion - 2.0).abs() < 1e-9);
+        assert!((relegation + promotion + mid_table - input.teams.len() as f64).abs() < 1e-9);
+    }
+
+    fn record(points: i64, goals_for: i64, goals_against: i64) -> Stats {
+        Stats {
+            points,
+            goals_for,
+            goals_against,
+            ..Stats::default()
+        }
+    }
+
+    #[test]
+    fn goal_difference_decides_whether_a_points_chase_is_still_alive() {
+        // Chaser can reach Leader's 33 points in its last match, so only goal difference can
+        // lift it to first: from -5 it can climb to at most -5 + MAX_GOALS.
+        let season = |leader_goal_difference: i64| RemainingSeason {
+            teams: ["Chaser", "Leader", "Minnow"].map(str::to_string).to_vec(),
+            stats: vec![
+                record(30, 20, 25),
+                record(33, 40, 40 - leader_goal_difference),
+                record(10, 5, 30),
+            ],
+            fixtures: vec![(0, 2)],
+        };
+        let ceiling = MAX_GOALS as i64 - 5;
+        assert_eq!(season(ceiling - 1).best_possible_position(0), 1);
+        // Level on goal difference, Leader's extra goals scored keep it ahead.
+        assert_eq!(season(ceiling).best_possible_position(0), 2);
+
+        let stranded = RemainingSeason {
+            fixtures: Vec::new(),
+            ..season(ceiling - 1)
+        };
+        assert_eq!(stranded.best_possible_position(0), 2);
+        assert_eq!(stranded.best_possible_position(2), 3);
+    }
+
+    #[test]
+    fn rivals_meeting_each_other_cannot_both_stay_below_a_chaser() {
+        // X can reach only 3 points, and one of A or B has at least 4 once they meet.
+        let season = |rival_points: i64| RemainingSeason {
+            teams: ["A", "B", "C", "X"].map(str::to_string).to_vec(),
+            stats: vec![
+                record(rival_points, 10, 10),
+                record(rival_points, 10, 10),
+                record(0, 0, 10),
+                record(0, 0, 0),
+            ],
+            fixtures: vec![(0, 1), (3, 2)],
+        };
+        assert_eq!(season(3).best_possible_position(3), 2);
+        // From 2 points each, a draw leaves both level with X but behind on goal difference.
+        assert_eq!(season(2).best_possible_position(3), 1);
+    }
+
+    #[test]
+    fn goal_difference_can_secure_a_place_a_rival_matches_on_points() {
+        // Rival can still draw level on 40 points but gains at most MAX_GOALS of goal difference.
+        let season = |rival_goal_difference: i64| RemainingSeason {
+            teams: ["Safe", "Rival", "Other"].map(str::to_string).to_vec(),
+            stats: vec![
+                record(40, 50, 20),
+                record(37, 30, 30 - rival_goal_difference),
+                record(20, 10, 40),
+            ],
+            fixtures: vec![(1, 2)],
+        };
+        assert_eq!(
+            season(30 - MAX_GOALS as i64 - 1).worst_possible_position(0),
+            1
+        );
+        assert_eq!(
+            season(30 - MAX_GOALS as i64 + 1).worst_possible_position(0),
+            2
+        );
+        assert_eq!(season(0).worst_possible_position(2), 3);
+    }
+
+    #[test]
+    fn chasers_meeting_each_other_cannot_both_pass() {
+        // A and B both need a win to pass Safe's 6 points, and they have only each other left.
+        let season = |chaser_points: i64| RemainingSeason {
+            teams: ["A", "B", "Safe"].map(str::to_string).to_vec(),
+            stats: vec![
+                record(chaser_points, 10, 10),
+                record(chaser_points, 10, 10),
+                record(6, 10, 10),
+            ],
+            fixtures: vec![(0, 1)],
+        };
+        assert_eq!(season(4).worst_possible_position(2), 2);
+        // From 5 points a draw lifts both level with Safe and ahead on goals scored.
+        assert_eq!(season(5).worst_possible_position(2), 3);
+    }
+
+    #[test]
+    fn one_goal_scenarios_bound_a_mid_table_finish() {
+        // Everyone but the leader still has to play Minnow once; even six Minnow wins (every
+        // rival is assumed to win in the worst case) leave it below Target.
+        let teams = ["Leader", "B", "C", "D", "E", "F", "Target", "Minnow"];
+        let points = [30, 22, 21, 21, 19, 18, 20, 0];
+        let season = RemainingSeason {
+            teams: teams.map(str::to_string).to_vec(),
+            stats: points.iter().map(|&pts| record(pts, 20, 20)).collect(),
+            fixtures: (1..7).map(|team| (team, 7)).collect(),
+        };
+        let (best, worst) = (
+            season.best_case_position(6, 1),
+            season.worst_case_position(6, 1),
+        );
+        assert_eq!((best, worst), (2, 7));
+        assert_eq!(worst - best, 5);
+
+        // One goal of difference separates Target from E once both finish on 22 points.
+        let level = RemainingSeason {
+            stats: vec![
+                record(30, 20, 20),
+                record(22, 20, 20),
+                record(21, 20, 20),
+                record(21, 20, 20),
+                record(19, 20, 21),
+                record(18, 20, 20),
+                record(22, 20, 20),
+                record(0, 20, 20),
+            ],
+            ..season
+        };
+        assert_eq!(level.best_case_position(6, 1), 2);
+        assert_eq!(level.worst_case_position(6, 1), 6);
+    }
+
+    #[test]
+    fn title_threshold_falls_as_fixtures_are_played() {
+        // A double round-robin of three teams with the last four games still to play.
+        let fixtures = [(0, 1), (1, 2), (2, 0), (1, 0)];
+        let season = |played: usize| RemainingSeason {
+            teams: ["Leader", "Second", "Third"].map(str::to_string).to_vec(),
+            stats: vec![record(12, 14, 6), record(7, 9, 9), record(4, 6, 14)],
+            fixtures: fixtures[played..].to_vec(),
+        };
+        // Second can still win three times (16 points); later only the leader's 12 stands.
+        let thresholds: Vec<i64> = (0..=fixtures.len())
+            .map(|played| season(played).title_threshold())
+            .collect();
+        assert_eq!(thresholds, [16, 13, 12, 12, 12]);
+        assert!(thresholds.windows(2).all(|pair| pair[0] >= pair[1]));
+
+        // On the final matchday a chaser two points back can still finish above 12.
+        let close = RemainingSeason {
+            stats: vec![record(12, 14, 6), record(10, 9, 9), record(4, 6, 14)],
+            ..season(3)
+        };
+        assert_eq!(close.title_threshold(), 13);
+        let clear = RemainingSeason {
+            stats: vec![record(12, 14, 6), record(8, 9, 9), record(4, 6, 14)],
+            ..season(3)
+        };
+        assert_eq!(clear.title_threshold(), 12);
+    }
+
+    #[test]
+    fn equal_group_teams_finish_in_every_position_equally_often() {
+        let _cache = cache_guard();
+        let teams: Vec<String> = ["A", "B", "C", "D"].map(String::from).to_vec();
+        let lambdas: HashMap<String, (f64, f64)> = teams
+            .iter()
+            .map(|team| (team.clone(), (1.3, 1.3)))
+            .collect();
+        for double_round_robin in [false, true] {
+            let input = group_stage_input(&teams, &lambdas, double_round_robin, None).unwrap();
+            assert_eq!(
+                input.fixtures.len(),
+                if double_round_robin { 12 } else { 6 }
+            );
+            let n_sims = 100_000;
+            let tally = run_bulk_simulations(&input, n_sims, 303, &BulkOptions::default());
+            for counts in &tally.position_counts {
+                for &count in counts {
+                    let share = count as f64 / n_sims as f64;
+                    assert!((share - 0.25).abs() < 0.01, "share {share:.4}");
+                }
+            }
+        }
+        assert!(group_stage_input(&teams[..1], &lambdas, true, None).is_err());
+        assert!(group_stage_input(&teams, &HashMap::new(), true, None).is_err());
+    }
+
+    #[test]
+    fn stronger_playoff_teams_win_the_bracket_more_often() {
+        let _cache = cache_guard();
+        let records = HashMap::from([
+            ("Strong".to_string(), (80, 25, 40)),
+            ("Good".to_string(), (60, 40, 40)),
+            ("Fair".to_string(), (45, 50, 40)),
+            ("Weak".to_string(), (30, 70, 40)),
+        ]);
+        let bracket = [
+            ("Strong".to_string(), "Weak".to_string()),
+            ("Good".to_string(), "Fair".to_string()),
+        ];
+        let playoff = Playoff::new(&bracket, &records, DEFAULT_PENALTY_RATE).unwrap();
+        let counts = playoff.win_counts(4_000, 8);
+        assert_eq!(counts.iter().sum::<u64>(), 4_000);
+        assert!(counts[0] > counts[2] && counts[2] > counts[3] && counts[3] > counts[1]);
+        assert!(Playoff::new(&bracket, &HashMap::new(), 0.75).is_err());
+    }
+
+    #[test]
+    fn shootouts_between_perfect_or_hopeless_takers_still_finish() {
+        let mut rng = ChaCha8Rng::seed_from_u64(271);
+        for rate in [0.0, 1.0] {
+            let first_wins = (0..200)
+                .filter(|_| penalty_shootout(&mut rng, rate, rate))
+                .count();
+            assert!(first_wins > 0 && first_wins < 200, "rate {rate}");
+        }
+    }
+
+    #[test]
+    fn two_leg_ties_send_level_aggregates_to_penalties_without_away_goals() {
+        let _cache = cache_guard();
+        let tie = |away_goals_rule| TwoLegTie {
+            first_leg: DixonColes::get_probability_matrix(1.4, 1.0, DEFAULT_RHO, MAX_GOALS),
+            second_leg: DixonColes::get_probability_matrix(1.5, 0.9, DEFAULT_RHO, MAX_GOALS),
+            away_goals_rule,
+            penalty_rate: DEFAULT_PENALTY_RATE,
+        };
+        let plain = tie(false).simulate(5_000, 21);
+        assert_eq!(plain.team1 + plain.team2, 5_000);
+        assert!(plain.level_on_aggregate > 0);
+        assert_eq!(plain.shootouts, plain.level_on_aggregate);
+
+        let away_goals = tie(true).simulate(5_000, 21);
+        assert_eq!(away_goals.team1 + away_goals.team2, 5_000);
+        assert_eq!(away_goals.level_on_aggregate, plain.level_on_aggregate);
+        assert!(away_goals.shootouts < away_goals.level_on_aggregate);
+    }
+
+    #[test]
+    fn third_place_wins_the_championship_playoff_more_often_than_sixth() {
+        let _cache = cache_guard();
+        let ranks = HashMap::from([
+            ("Third".to_string(), 3),
+            ("Fourth".to_string(), 4),
+            ("Fifth".to_string(), 5),
+            ("Sixth".to_string(), 6),
+        ]);
+        let stats = HashMap::from([
+            ("Third".to_string(), (1.9, 0.9)),
+            ("Fourth".to_string(), (1.6, 1.1)),
+            ("Fifth".to_string(), (1.4, 1.2)),
+            ("Sixth".to_string(), (1.1, 1.4)),
+        ]);
+        let playoff = ChampionshipPlayoff::new(&ranks, &stats, DEFAULT_PENALTY_RATE).unwrap();
+        assert_eq!(playoff.teams, ["Third", "Fourth", "Fifth", "Sixth"]);
+        let counts = playoff.win_counts(4_000, 302);
+        assert_eq!(counts.iter().sum::<u64>(), 4_000);
+        assert!(
+            counts[0] > counts[3],
+            "third {} v sixth {}",
+            counts[0],
+            counts[3]
+        );
+        assert!(counts[0] > counts[1] && counts[2] > counts[3]);
+
+        let mut tied = ranks.clone();
+        tied.insert("Sixth".to_string(), 5);
+        assert!(ChampionshipPlayoff::new(&tied, &stats, DEFAULT_PENALTY_RATE).is_err());
+        let mut missing = stats;
+        missing.remove("Fifth");
+        assert!(ChampionshipPlayoff::new(&ranks, &missing, DEFAULT_PENALTY_RATE).is_err());
+    }
+
+    #[test]
+    fn champions_league_group_favourites_go_further_than_runners_up() {
+        let _cache = cache_guard();
+        let mut lambdas = HashMap::new();
+        let groups: Vec<Vec<String>> = (0..8)
+            .map(|group| {
+                (0..4)
+                    .map(|seed| {
+                        let team = format!("G{group}S{seed}");
+                        let strength = [2.0, 1.5, 1.2, 0.9][seed];
+                        lambdas.insert(team.clone(), (strength, 2.4 - strength));
+                        team
+                    })
+                    .collect()
+            })
+            .collect();
+        let tournament = ChampionsLeague::new(&groups, &lambdas, DEFAULT_PENALTY_RATE).unwrap();
+        assert_eq!(tournament.stages(), [16, 8, 4, 2, 1]);
+        let n_sims = 2_000;
+        let reached = tournament.reach_counts(n_sims, 304);
+        for stage in 0..5 {
+            let total: u64 = reached.iter().map(|counts| counts[stage]).sum();
+            assert_eq!(total, [16, 8, 4, 2, 1][stage] * n_sims as u64);
+        }
+        // Seed 0 usually wins its group and hosts the second leg against a runner-up.
+        for group in 0..8 {
+            let (favourite, second) = (&reached[group * 4], &reached[group * 4 + 1]);
+            assert!(favourite[0] > second[0]);
+            assert!(favourite[1] > second[1]);
+        }
+
+        assert!(ChampionsLeague::new(&groups[..3], &lambdas, DEFAULT_PENALTY_RATE).is_err());
+        let repeated = vec![groups[0].clone(), groups[0].clone()];
+        assert!(ChampionsLeague::new(&repeated, &lambdas, DEFAULT_PENALTY_RATE).is_err());
+    }
+
+    #[test]
+    fn mls_table_leaders_usually_make_the_playoffs() {
+        let _cache = cache_guard();
+        let config = ModelConfig {
+            home_advantage: Some(HOME_ADVANTAGE),
+            ..ModelConfig::default()
+        };
+        // Nine teams per conference after 20 games; points and goals fall with the table.
+        let rows: Vec<(String, Stats)> = ["East", "West"]
+            .iter()
+            .flat_map(|conference| {
+                (0..9).map(move |place| {
+                    let stats = Stats {
+                        matches: 20,
+                        points: 44 - 3 * place,
+                        goals_for: 38 - 2 * place,
+                        goals_against: 18 + 2 * place,
+                        ..Stats::default()
+                    };
+                    (format!("{conference} {place}"), stats)
+                })
+            })
+            .collect();
+        let league = League::new(rows.clone(), &rows, &rows, &config).unwrap();
+        let mut fixtures = Vec::new();
+        for (home, away) in (0..18).flat_map(|home| (0..18).map(move |away| (home, away))) {
+            let same_conference = (home < 9) == (away < 9);
+            if home != away && (same_conference || (home + away) % 5 == 0) {
+                fixtures.push(league.fixture(&rows[home].0, &rows[away].0).unwrap());
+            }
+        }
+        let season = MlsSeason::new(league, 9, &fixtures, &config).unwrap();
+        let n_sims = 2_000;
+        let tally = season.tally(n_sims, 305);
+
+        let totals = [0, 1, 2].map(|column| tally.iter().map(|counts| counts[column]).sum::<u64>());
+        assert_eq!(totals, [14, 4, 1].map(|teams| teams * n_sims as u64));
+        for leader in [0, 9] {
+            assert!(tally[leader][0] as f64 / n_sims as f64 > 0.5);
+            assert!(tally[leader][0] > tally[leader + 8][0]);
+            assert!(tally[leader][2] > tally[leader + 8][2]);
+        }
+
+        let small = League::new(rows[..12].to_vec(), &[], &[], &config).unwrap();
+        assert!(MlsSeason::new(small, 6, &[], &config).is_err());
+    }
+
+    #[test]
+    fn swiss_rounds_never_repeat_a_pairing() {
+        let _cache = cache_guard();
+        let teams: Vec<String> = (0..36).map(|idx| format!("Team {idx}")).collect();
+        let lambdas: HashMap<String, (f64, f64)> = teams
+            .iter()
+            .enumerate()
+            .map(|(idx, team)| {
+                (
+                    team.clone(),
+                    (2.2 - idx as f64 * 0.03, 0.9 + idx as f64 * 0.03),
+                )
+            })
+            .collect();
+        let tournament = SwissTournament::new(teams.clone(), &lambdas, 8).unwrap();
+        for sim_index in 0..200 {
+            let mut rng = ChaCha8Rng::seed_from_u64(simulation_seed(306, sim_index));
+            let (order, games) = tournament.play(&mut rng);
+            assert_eq!(order.len(), 36);
+            assert_eq!(games.len(), 8 * 18);
+            let mut pairings = std::collections::HashSet::new();
+            for &(home, away) in &games {
+                assert_ne!(home, away);
+                assert!(pairings.insert((home.min(away), home.max(away))));
+            }
+        }
+
+        let tally = tournament.tally(500, 306);
+        for counts in &tally {
+            assert_eq!(counts[1] + counts[2], 500);
+        }
+        assert!(tally[0][0] > tally[35][0]);
+
+        // Six teams can play three rounds without a rematch, but not four.
+        let few = teams[..6].to_vec();
+        let full = SwissTournament::new(few.clone(), &lambdas, 3).unwrap();
+        assert_eq!(full.play(&mut ChaCha8Rng::seed_from_u64(1)).1.len(), 9);
+        assert!(SwissTournament::new(few, &lambdas, 4).is_err());
+        assert!(SwissTournament::new(teams[..5].to_vec(), &lambdas, 2).is_err());
+    }
+
+    #[test]
+    fn equal_penalty_takers_split_shootouts_evenly() {
+        let n_sims = 100_000;
+        let (team1, team2) = shootout_wins(0.75, 0.75, n_sims, 404);
+        assert_eq!(team1 + team2, n_sims as u64);
+        let share = team1 as f64 / n_sims as f64;
+        assert!((share - 0.5).abs() < 0.01, "team1 won {share:.4}");
+
+        let (better, _) = shootout_wins(0.85, 0.65, 10_000, 404);
+        assert!(better > 5_000);
+        let (certain, _) = shootout_wins(1.0, 1.0, 100, 404);
+        assert!(certain > 0 && certain < 100);
+    }
+
+    #[test]
+    fn neutral_venue_drops_home_advantage_and_draws_more_often() {
+        let _cache = cache_guard();
+        let ratings = LeagueRatings {
+            avg_league_goals: 1.3,
+            home_advantage: 1.5,
+            home_attack: vec![1.0; 2],
+            home_defense: vec![1.0; 2],
+            away_attack: vec![1.0; 2],
+            away_defense: vec![1.0; 2],
+        };
+        let draws = |neutral: bool| {
+            let fixture = ParsedFixture {
+                home_idx: 0,
+                away_idx: 1,
+                neutral,
+                result: None,
+            };
+            let distribution = ratings
+                .fixture_simulation(&fixture, &ModelConfig::default())
+                .distribution;
+            let mut rng = ChaCha8Rng::seed_from_u64(74);
+            (0..10_000)
+                .filter(|_| {
+                    let (home, away) =
+                        DixonColes::simulate_from_distribution(&mut rng, &distribution);
+                    home == away
+                })
+                .count()
+        };
+        let (home_draws, neutral_draws) = (draws(false), draws(true));
+        assert!(
+            neutral_draws > home_draws,
+            "neutral {neutral_draws} vs home {home_draws}"
+        );
+    }
+
+    #[test]
+    fn point_deduction_drags_a_team_down_the_table() {
+        let _cache = cache_guard();
+        let average_position = |input: &SimulationInput, team: usize| {
+            let tally = run_bulk_simulations(input, 1_000, 31, &BulkOptions::default());
+            let counts = &tally.position_counts[team];
+            let weighted: u64 = counts
+                .iter()
+                .enumerate()
+                .map(|(pos_idx, &count)| (pos_idx as u64 + 1) * count)
+                .sum();
+            weighted as f64 / 1_000.0
+        };
+        let mut input = sample_league(8);
+        let untouched = average_position(&input, 4);
+        input
+            .apply_deductions(&HashMap::from([("Team 04".to_string(), 10)]))
+            .unwrap();
+        assert_eq!(input.initial_stats[4].points, -10);
+        assert!(average_position(&input, 4) > untouched + 1.0);
+        assert!(input
+            .apply_deductions(&HashMap::from([("Nobody".to_string(), 1)]))
+            .is_err());
+    }
+
+    #[test]
+    fn walkover_applies_the_awarded_score_every_season() {
+        let _cache = cache_guard();
+        let ratings = LeagueRatings {
+            avg_league_goals: 1.3,
+            home_advantage: HOME_ADVANTAGE,
+            home_attack: vec![1.0; 2],
+            home_defense: vec![1.0; 2],
+            away_attack: vec![1.0; 2],
+            away_defense: vec![1.0; 2],
+        };
+        let walkover = ParsedFixture {
+            home_idx: 0,
+            away_idx: 1,
+            neutral: false,
+            result: Some((3, 0)),
+        };
+        let input = SimulationInput {
+            teams: ["Home", "Away"].map(str::to_string).to_vec(),
+            initial_stats: vec![Stats::default(); 2],
+            fixtures: vec![ratings.fixture_simulation(&walkover, &ModelConfig::default())],
+            tiebreakers: Vec::new(),
+        };
+        let mut rng = ChaCha8Rng::seed_from_u64(276);
+        for _ in 0..100 {
+            let season = simulate_single_season(&input, &mut rng);
+            assert_eq!(season.final_stats[0].goals_for, 3);
+            assert_eq!(season.final_stats[1].goals_for, 0);
+            assert_eq!(season.final_stats[0].points, 3);
+        }
+    }
+
+    #[test]
+    fn fixed_scores_come_back_for_every_uniform_and_still_use_a_draw() {
+        let walkover = ProbabilityDistribution::fixed(3, 0, MAX_GOALS);
+        for u in [0.0, 1e-12, 0.5, 1.0] {
+            assert_eq!(DixonColes::score_at(&walkover, u), (3, 0), "u = {u}");
+        }
+        let (mut sampled, mut reference) = (
+            ChaCha8Rng::seed_from_u64(276),
+            ChaCha8Rng::seed_from_u64(276),
+        );
+        DixonColes::simulate_from_distribution(&mut sampled, &walkover);
+        rand::Rng::gen::<f64>(&mut reference);
+        assert_eq!(
+            rand::Rng::gen::<u64>(&mut sampled),
+            rand::Rng::gen::<u64>(&mut reference)
+        );
+    }
+
+    #[test]
+    fn recent_goals_outweigh_early_ones_in_form_lambdas() {
+        let played = |home_goals| MatchResult {
+            home_idx: 0,
+            away_idx: 2,
+            home_goals,
+            away_goals: 0,
+        };
+        let in_form = [played(0), played(0), played(5)];
+        let early = [played(5), played(0), played(0)];
+        let mut ratings = LeagueRatings {
+            avg_league_goals: 1.4,
+            home_advantage: HOME_ADVANTAGE,
+            home_attack: vec![1.0; 3],
+            home_defense: vec![1.0; 3],
+            away_attack: vec![1.0; 3],
+            away_defense: vec![1.0; 3],
+        };
+        let fixture = ParsedFixture {
+            home_idx: 0,
+            away_idx: 1,
+            neutral: false,
+            result: None,
+        };
+
+        let mut lambda = |results: &[MatchResult]| {
+            ratings.apply_form(&form_scoring_rates(results, 3, 5, 0.5));
+            ratings.lambdas(&fixture).0
+        };
+        assert!(lambda(&in_form) > lambda(&early));
+        assert_eq!(form_scoring_rates(&early, 3, 5, 0.5)[1], None);
+    }
+
+    #[test]
+    fn strong_attack_against_weak_defense_scores_above_average() {
+        let _cache = cache_guard();
+        let ratings = LeagueRatings::from_strengths(
+            1.4,
+            HOME_ADVANTAGE,
+            &[(1.0, 1.0), (1.0, 1.0), (1.6, 1.0), (1.0, 1.5)],
+        );
+        let fixture = |home_idx, away_idx| ParsedFixture {
+            home_idx,
+            away_idx,
+            neutral: false,
+            result: None,
+        };
+        let mut rng = ChaCha8Rng::seed_from_u64(278);
+        let mut mean_goals = |fixture: ParsedFixture| {
+            let simulation = ratings.fixture_simulation(&fixture, &ModelConfig::default());
+            let total: i64 = (0..20_000)
+                .map(|_| {
+                    let (home_goals, away_goals) =
+                        DixonColes::simulate_from_distribution(&mut rng, &simulation.distribution);
+                    home_goals + away_goals
+                })
+                .sum();
+            total as f64 / 20_000.0
+        };
+        let average = mean_goals(fixture(0, 1));
+        let mismatch = mean_goals(fixture(2, 3));
+        assert!(mismatch > average + 0.5, "{mismatch} vs {average}");
+    }
+
+    #[test]
+    fn likelihood_fit_recovers_synthetic_team_strengths() {
+        let _cache = cache_guard();
+        let attack = [1.3, 1.1, 0.9, 1.0 / (1.3 * 1.1 * 0.9)];
+        let defense = [0.8, 1.0, 1.1, 1.0 / (0.8 * 1.1)];
+        let (mu, home_advantage) = (1.3, 1.25);
+        let mut rng = ChaCha8Rng::seed_from_u64(279);
+        let mut results = Vec::new();
+        for _ in 0..500 {
+            for home_idx in 0..4 {
+                for away_idx in (0..4).filter(|&away_idx| away_idx != home_idx) {
+                    let distribution = DixonColes::get_probability_matrix(
+                        mu * home_advantage * attack[home_idx] * defense[away_idx],
+                        mu * attack[away_idx] * defense[home_idx],
+                        DEFAULT_RHO,
+                        MAX_GOALS,
+                    );
+                    let (home_goals, away_goals) =
+                        DixonColes::simulate_from_distribution(&mut rng, &distribution);
+                    results.push(MatchResult {
+                        home_idx,
+                        away_idx,
+                        home_goals,
+                        away_goals,
+                    });
+                }
+            }
+        }
+
+        let weights = vec![1.0; results.len()];
+        let fit = fit_dixon_coles_params(&results, &weights, 4, 0.0, 5000, 1e-10);
+        assert!(fit.converged);
+        for idx in 0..4 {
+            assert!(
+                (fit.attack[idx] - attack[idx]).abs() < 0.05,
+                "{:?}",
+                fit.attack
+            );
+            assert!(
+                (fit.defense[idx] - defense[idx]).abs() < 0.05,
+                "{:?}",
+                fit.defense
+            );
+        }
+        assert!((fit.mu - mu).abs() < 0.05);
+        assert!((fit.home_advantage - home_advantage).abs() < 0.05);
+    }
+
+    #[test]
+    fn adam_finds_the_minimum_of_a_quadratic() {
+        use crate::optimizer::{numeric_gradient, Adam};
+
+        // f(x, y) = (x - 3)^2 + 10 (y + 1)^2 + 2, minimised at (3, -1) with value 2.
+        let quadratic = |params: &[f64]| {
+            let (x, y) = (params[0], params[1]);
+            (x - 3.0).powi(2) + 10.0 * (y + 1.0).powi(2) + 2.0
+        };
+        let exact = |params: &[f64]| {
+            let gradient = vec![2.0 * (params[0] - 3.0), 20.0 * (params[1] + 1.0)];
+            (quadratic(params), gradient)
+        };
+        let adam = Adam {
+            learning_rate: 0.1,
+            tolerance: 1e-9,
+            ..Adam::default()
+        };
+        let (params, loss, converged) = adam.minimize(vec![0.0, 0.0], exact);
+        assert!(converged);
+        assert!((params[0] - 3.0).abs() < 1e-8 && (params[1] + 1.0).abs() < 1e-8);
+        assert!((loss - 2.0).abs() < 1e-12);
+
+        // Forward differences bias the gradient by about h, so the minimum moves by ~1e-6.
+        let numeric = Adam {
+            tolerance: 1e-4,
+            ..adam
+        };
+        let (params, _, converged) =
+            numeric.minimize(vec![0.0, 0.0], |params| numeric_gradient(quadratic, params));
+        assert!(converged);
+        assert!((params[0] - 3.0).abs() < 1e-4 && (params[1] + 1.0).abs() < 1e-4);
+
+        let short = Adam {
+            max_iter: 5,
+            ..adam
+        };
+        assert!(!short.minimize(vec![0.0, 0.0], exact).2);
+    }
+
+    #[test]
+    fn poisson_regression_recovers_synthetic_team_strengths() {
+        let attack = [1.3, 1.1, 0.9, 1.0 / (1.3 * 1.1 * 0.9)];
+        let defense = [0.8, 1.0, 1.1, 1.0 / (0.8 * 1.1)];
+        let (mu, home_advantage) = (1.3, 1.25);
+        let mut rng = ChaCha8Rng::seed_from_u64(320);
+        let mut results = Vec::new();
+        for _ in 0..500 {
+            for home_idx in 0..4 {
+                for away_idx in (0..4).filter(|&away_idx| away_idx != home_idx) {
+                    let distribution = DixonColes::get_probability_matrix(
+                        mu * home_advantage * attack[home_idx] * defense[away_idx],
+                        mu * attack[away_idx] * defense[home_idx],
+                        0.0,
+                        MAX_GOALS,
+                    );
+                    let (home_goals, away_goals) =
+                        DixonColes::simulate_from_distribution(&mut rng, &distribution);
+                    results.push(MatchResult {
+                        home_idx,
+                        away_idx,
+                        home_goals,
+                        away_goals,
+                    });
+                }
+            }
+        }
+
+        let fit = fit_poisson_irls(&results, 4).unwrap();
+        for idx in 0..4 {
+            assert!((fit.attack[idx] - attack[idx]).abs() < 0.05, "{fit:?}");
+            assert!((fit.defense[idx] - defense[idx]).abs() < 0.05, "{fit:?}");
+        }
+        assert!((fit.mu - mu).abs() < 0.05, "{fit:?}");
+        assert!(
+            (fit.home_advantage - home_advantage).abs() < 0.05,
+            "{fit:?}"
+        );
+        assert!(fit_poisson_irls(&results[..1], 1).is_err());
+    }
+
+    #[test]
+    fn time_decay_tracks_strength_changes_late_in_the_season() {
+        let _cache = cache_guard();
+        let early_attack = [0.7, 1.4, 1.0, 1.0 / 0.98];
+        let late_attack = [1.4, 0.7, 1.0, 1.0 / 0.98];
+        let rounds = 120;
+        let mut rng = ChaCha8Rng::seed_from_u64(280);
+        let (mut results, mut days_ago) = (Vec::new(), Vec::new());
+        for round in 0..rounds {
+            let attack = if round < rounds / 2 {
+                early_attack
+            } else {
+                late_attack
+            };
+            for home_idx in 0..4 {
+                for away_idx in (0..4).filter(|&away_idx| away_idx != home_idx) {
+                    let distribution = DixonColes::get_probability_matrix(
+                        1.3 * HOME_ADVANTAGE * attack[home_idx],
+                        1.3 * attack[away_idx],
+                        DEFAULT_RHO,
+                        MAX_GOALS,
+                    );
+                    let (home_goals, away_goals) =
+                        DixonColes::simulate_from_distribution(&mut rng, &distribution);
+                    results.push(MatchResult {
+                        home_idx,
+                        away_idx,
+                        home_goals,
+                        away_goals,
+                    });
+                    days_ago.push((rounds - 1 - round) as i64);
+                }
+            }
+        }
+
+        let fit = |weights: &[f64]| fit_dixon_coles_params(&results, weights, 4, 0.0, 5000, 1e-10);
+        let plain = fit(&vec![1.0; results.len()]);
+        let decayed = fit(&time_decay_weights(&days_ago, 0.1));
+        for (idx, truth) in late_attack.iter().enumerate().take(2) {
+            let plain_error = (plain.attack[idx] - truth).abs();
+            let decayed_error = (decayed.attack[idx] - truth).abs();
+            assert!(
+                decayed_error < plain_error,
+                "{decayed_error} vs {plain_error}"
+            );
+        }
+    }
+
+    #[test]
+    fn equal_elo_ratings_give_the_league_rate_plus_home_advantage() {
+        let (lambda_h, lambda_a) = elo_lambdas(1600.0, 1600.0, 0.0, 1.4);
+        assert!((lambda_h - 1.4 * HOME_ADVANTAGE).abs() < 1e-12);
+        assert!((lambda_a - 1.4).abs() < 1e-12);
+
+        let (stronger_h, stronger_a) = elo_lambdas(1800.0, 1600.0, 0.0, 1.4);
+        assert!(stronger_h > lambda_h && stronger_a < lambda_a);
+
+        let (home, away) = elo_update(1600.0, 1600.0, 0, 1, 20.0);
+        assert_eq!((home, away), (1590.0, 1610.0));
+    }
+
+    #[test]
+    fn negative_binomial_spreads_goals_and_tends_to_poisson() {
+        let _cache = cache_guard();
+        let poisson = DixonColes::precompute_probability_matrix(1.5, 1.1, 0.0, MAX_GOALS);
+        let near_poisson = NegativeBinomial::score_matrix(1.5, 1.1, 1e7, MAX_GOALS);
+        for (left, right) in poisson.probabilities().zip(near_poisson.probabilities()) {
+            assert!((left - right).abs() < 1e-6);
+        }
+
+        let home_goal_variance = |distribution: &ProbabilityDistribution| {
+            let mut rng = ChaCha8Rng::seed_from_u64(282);
+            let goals: Vec<f64> = (0..50_000)
+                .map(|_| DixonColes::simulate_from_distribution(&mut rng, distribution).0 as f64)
+                .collect();
+            let mean = goals.iter().sum::<f64>() / goals.len() as f64;
+            goals.iter().map(|goal| (goal - mean).powi(2)).sum::<f64>() / goals.len() as f64
+        };
+        let overdispersed = NegativeBinomial::score_matrix(1.5, 1.1, 2.0, MAX_GOALS);
+        assert!(home_goal_variance(&overdispersed) > 1.3 * home_goal_variance(&poisson));
+    }
+
+    #[test]
+    fn negative_binomial_sampler_matches_gamma_poisson_moments() {
+        let moments = |lambda: f64, dispersion: f64| {
+            let mut rng = ChaCha8Rng::seed_from_u64(282);
+            let goals: Vec<f64> = (0..200_000)
+                .map(|_| {
+                    NegativeBinomial::simulate_match_nb(&mut rng, lambda, 0.9, dispersion).0 as f64
+                })
+                .collect();
+            let n = goals.len() as f64;
+            let mean = goals.iter().sum::<f64>() / n;
+            let variance = goals.iter().map(|goal| (goal - mean).powi(2)).sum::<f64>() / n;
+            (mean, variance)
+        };
+        for (lambda, dispersion) in [(1.5, 2.0), (1.1, 0.5), (2.0, 1e7)] {
+            let (mean, variance) = moments(lambda, dispersion);
+            let expected = lambda + lambda * lambda / dispersion;
+            assert!(
+                (mean - lambda).abs() < 0.02,
+                "{lambda} {dispersion}: {mean}"
+            );
+            assert!(
+                (variance - expected).abs() < 0.03 * expected,
+                "{lambda} {dispersion}: {variance} vs {expected}"
+            );
+        }
+        let (_, near_poisson) = moments(1.5, 1e7);
+        let (_, overdispersed) = moments(1.5, 2.0);
+        assert!((near_poisson - 1.5).abs() < 0.03, "{near_poisson}");
+        assert!(overdispersed > 1.3 * near_poisson);
+    }
+
+    #[test]
+    fn zero_inflation_adds_goalless_draws_and_stays_normalized() {
+        let _cache = cache_guard();
+        let goalless_rate = |zero_inflation: f64| {
+            let distribution = ZeroInflatedDixonColes::score_matrix(
+                1.4,
+                1.1,
+                DEFAULT_RHO,
+                zero_inflation,
+                MAX_GOALS,
+            );
+            let total: f64 = distribution.probabilities().sum();
+            assert!((total - 1.0).abs() < 1e-9);
+            let mut rng = ChaCha8Rng::seed_from_u64(283);
+            let goalless = (0..20_000)
+                .filter(|_| {
+                    DixonColes::simulate_from_distribution(&mut rng, &distribution) == (0, 0)
+                })
+                .count();
+            goalless as f64 / 20_000.0
+        };
+        let (plain, inflated, heavy) = (goalless_rate(0.0), goalless_rate(0.1), goalless_rate(0.3));
+        assert!(
+            plain < inflated && inflated < heavy,
+            "{plain} {inflated} {heavy}"
+        );
+    }
+
+    #[test]
+    fn bivariate_poisson_goals_covary_by_the_shared_rate() {
+        let (lambda_1, lambda_2, lambda_3) = (1.2, 0.9, 0.3);
+        let distribution =
+            BivariatePoissonModel::score_matrix(lambda_1, lambda_2, lambda_3, MAX_GOALS);
+        let mut rng = ChaCha8Rng::seed_from_u64(284);
+        let samples: Vec<(f64, f64)> = (0..200_000)
+            .map(|_| {
+                let (home, away) = DixonColes::simulate_from_distribution(&mut rng, &distribution);
+                (home as f64, away as f64)
+            })
+            .collect();
+        let n = samples.len() as f64;
+        let mean_home = samples.iter().map(|(home, _)| home).sum::<f64>() / n;
+        let mean_away = samples.iter().map(|(_, away)| away).sum::<f64>() / n;
+        let covariance = samples
+            .iter()
+            .map(|(home, away)| (home - mean_home) * (away - mean_away))
+            .sum::<f64>()
+            / n;
+        assert!((mean_home - (lambda_1 + lambda_3)).abs() < 0.02);
+        assert!((covariance - lambda_3).abs() < 0.02, "{covariance}");
+    }
+
+    #[test]
+    fn bivariate_poisson_rejects_lambda_3_above_a_fixture_lambda() {
+        let mut config = ModelConfig {
+            score_model: ScoreModel::BivariatePoisson { lambda_3: 0.4 },
+            ..ModelConfig::default()
+        };
+        assert!(config.check_lambdas(1.5, 0.4).is_ok());
+        assert!(config.check_lambdas(1.5, 0.3).is_err());
+        config.red_cards = RedCards::validated(0.1, 0.5).unwrap();
+        assert!(config.check_lambdas(1.5, 0.6).is_err());
+
+        let league = League {
+            teams: ["Home", "Away"].map(str::to_string).to_vec(),
+            initial_stats: vec![Stats::default(); 2],
+            team_to_idx: TeamIndex::new(),
+            ratings: LeagueRatings::from_strengths(1.0, 1.0, &[(1.0, 1.0), (0.2, 1.0)]),
+        };
+        let fixture = ParsedFixture {
+            home_idx: 0,
+            away_idx: 1,
+            neutral: false,
+            result: None,
+        };
+        let err = league.check_score_model(&[fixture], &config).unwrap_err();
+        assert!(err.starts_with("Home vs Away: lambda_3 = 0.4"), "{err}");
+        let walkover = ParsedFixture {
+            result: Some((3, 0)),
+            ..fixture
+        };
+        assert!(league.check_score_model(&[walkover], &config).is_ok());
+    }
+
+    #[test]
+    fn bivariate_poisson_sampler_shares_the_common_goals() {
+        let (lambda_1, lambda_2, lambda_3) = (1.2, 0.9, 0.3);
+        let mut rng = ChaCha8Rng::seed_from_u64(284);
+        let samples: Vec<(f64, f64)> = (0..200_000)
+            .map(|_| {
+                let (home, away) =
+                    BivariatePoissonModel::simulate_match(&mut rng, lambda_1, lambda_2, lambda_3);
+                (home as f64, away as f64)
+            })
+            .collect();
+        let n = samples.len() as f64;
+        let mean_home = samples.iter().map(|(home, _)| home).sum::<f64>() / n;
+        let mean_away = samples.iter().map(|(_, away)| away).sum::<f64>() / n;
+        let covariance = samples
+            .iter()
+            .map(|(home, away)| (home - mean_home) * (away - mean_away))
+            .sum::<f64>()
+            / n;
+        assert!((mean_home - (lambda_1 + lambda_3)).abs() < 0.02);
+        assert!((mean_away - (lambda_2 + lambda_3)).abs() < 0.02);
+        assert!((covariance - lambda_3).abs() < 0.02, "{covariance}");
+    }
+
+    #[test]
+    fn asian_handicap_lines_follow_the_result_probabilities() {
+        let _cache = cache_guard();
+        let distribution = DixonColes::get_probability_matrix(1.6, 1.1, DEFAULT_RHO, MAX_GOALS);
+        let (home_win, draw, away_win) = distribution.outcome_probabilities();
+        let close = |left: (f64, f64), right: (f64, f64)| {
+            (left.0 - right.0).abs() < 1e-12 && (left.1 - right.1).abs() < 1e-12
+        };
+        assert!(close(
+            distribution.asian_handicap_split(0.0),
+            (home_win + 0.5 * draw, away_win + 0.5 * draw)
+        ));
+        assert!(close(
+            distribution.asian_handicap_split(0.5),
+            (home_win + draw, away_win)
+        ));
+        assert!(close(
+            distribution.asian_handicap_split(-0.5),
+            (home_win, draw + away_win)
+        ));
+        let quarter = distribution.asian_handicap_split(0.25);
+        assert!((quarter.0 + quarter.1 - 1.0).abs() < 1e-9);
+        assert!(quarter.0 > home_win + 0.5 * draw && quarter.0 < home_win + draw);
+    }
+
+    #[test]
+    fn backtest_estimates_home_advantage_from_the_replayed_results() {
+        let _cache = cache_guard();
+        let teams: Vec<String> = (0..4).map(|idx| format!("Team {idx}")).collect();
+        let played = |home_idx, away_idx, home_goals, away_goals| MatchResult {
+            home_idx,
+            away_idx,
+            home_goals,
+            away_goals,
+        };
+        // Every side scores and concedes once per match, so only the venue separates them.
+        let results = [
+            (1, played(0, 1, 1, 1)),
+            (1, played(2, 3, 1, 1)),
+            (2, played(1, 2, 1, 1)),
+            (2, played(3, 0, 1, 1)),
+            (3, played(0, 2, 1, 1)),
+        ];
+        let entries = replay_forecasts(
+            &teams,
+            vec![Stats::default(); 4],
+            &results,
+            &ModelConfig::default(),
+            20_000,
+            289,
+        );
+        let home_edge = |entry: &BacktestEntry| entry.predicted[0] - entry.predicted[2];
+        // Before any result the default advantage applies; level venue goals then remove it.
+        assert!(home_edge(&entries[0]) > 0.05);
+        assert!(
+            home_edge(&entries[4]).abs() < 0.02,
+            "{}",
+            home_edge(&entries[4])
+        );
+
+        assert_eq!(estimated_home_advantage(12, 10), 1.2);
+        assert_eq!(estimated_home_advantage(30, 10), 1.5);
+        assert_eq!(estimated_home_advantage(3, 0), HOME_ADVANTAGE);
+    }
+
+    #[test]
+    fn forecast_scores_reward_confidence_in_the_observed_result() {
+        let perfect = [
+            ([1.0, 0.0, 0.0], 0),
+            ([0.0, 1.0, 0.0], 1),
+            ([0.0, 0.0, 1.0], 2),
+        ];
+        assert_eq!(mean_score(&perfect, brier), 0.0);
+        assert_eq!(mean_score(&perfect, log_loss_term), 0.0);
+        assert_eq!(mean_score(&perfect, ranked_probability), 0.0);
+
+        let third = 1.0 / 3.0;
+        let uniform = [([third; 3], 0), ([third; 3], 1), ([third; 3], 2)];
+        assert!((mean_score(&uniform, brier) - 2.0 / 3.0).abs() < 1e-12);
+        assert!((mean_score(&uniform, log_loss_term) - 3f64.ln()).abs() < 1e-12);
+        // An away win forecast as a home win is further off than one forecast as a draw.
+        assert!(ranked_probability(&[1.0, 0.0, 0.0], 2) > ranked_probability(&[0.0, 1.0, 0.0], 2));
+    }
+
+    #[test]
+    fn position_rps_is_zero_for_a_certain_correct_forecast() {
+        let certain_third = [0.0, 0.0, 1.0, 0.0, 0.0];
+        assert_eq!(position_rps(&certain_third, 3), Ok(0.0));
+        let near_miss = position_rps(&certain_third, 4).unwrap();
+        let far_miss = position_rps(&certain_third, 5).unwrap();
+        assert!(0.0 < near_miss && near_miss < far_miss);
+        assert!(position_rps(&certain_third, 6).is_err());
+    }
+
+    #[test]
+    fn replay_scores_one_forecast_per_historical_match() {
+        let _cache = cache_guard();
+        let stats = vec![record(6, 5, 2), record(3, 3, 3), record(1, 2, 5)];
+        let played = |home_idx, away_idx, home_goals, away_goals| MatchResult {
+            home_idx,
+            away_idx,
+            home_goals,
+            away_goals,
+        };
+        let results = [
+            (4, played(0, 1, 2, 0)),
+            (4, played(2, 0, 1, 1)),
+            (5, played(1, 2, 0, 3)),
+            (6, played(1, 0, 1, 2)),
+        ];
+        let teams: Vec<String> = (0..3).map(|idx| format!("Team {idx}")).collect();
+        let entries =
+            replay_forecasts(&teams, stats, &results, &ModelConfig::default(), 2_000, 289);
+        assert_eq!(entries.len(), results.len());
+        assert_eq!(
+            entries.iter().map(|entry| entry.actual).collect::<Vec<_>>(),
+            [0, 1, 2, 2]
+        );
+        for entry in &entries {
+            assert!((entry.predicted.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+            assert!(entry.brier >= 0.0 && entry.rps >= 0.0);
+        }
+    }
+
+    #[test]
+    fn progress_counter_reaches_the_simulation_count_without_overshooting() {
+        let _cache = cache_guard();
+        let input = sample_league(10);
+        let n_sims = 3_000;
+        let done = std::sync::atomic::AtomicBool::new(false);
+        let highest_seen = std::thread::scope(|scope| {
+            let poller = scope.spawn(|| {
+                let mut highest = 0;
+                while !done.load(AtomicOrdering::Relaxed) {
+                    highest = highest.max(simulation_progress());
+                }
+                highest
+            });
+            run_bulk_simulations(&input, n_sims, 290, &BulkOptions::default());
+            done.store(true, AtomicOrdering::Relaxed);
+            poller.join().unwrap()
+        });
+        assert!(highest_seen <= n_sims);
+        assert_eq!(simulation_progress(), n_sims);
+    }
+
+    #[test]
+    fn alphabetical_position_rows_each_cover_every_season() {
+        let _cache = cache_guard();
+        let mut input = sample_league(5);
+        input.teams = ["Rovers", "Albion", "United", "City", "Athletic"]
+            .map(str::to_string)
+            .to_vec();
+        let n_sims = 700;
+        let tally = run_bulk_simulations(&input, n_sims, 291, &BulkOptions::default());
+        let (teams, rows) = position_counts_by_name(&input.teams, &tally.position_counts);
+        assert_eq!(teams, ["Albion", "Athletic", "City", "Rovers", "United"]);
+        assert_eq!(rows[0], tally.position_counts[1]);
+        assert!(rows
+            .iter()
+            .all(|row| row.iter().sum::<u64>() == n_sims as u64));
+    }
+
+    #[test]
+    fn simulation_config_survives_a_json_round_trip() {
+        let configs = [
+            SimulationConfig::new(None, None, None, None, None, None, None, None).unwrap(),
+            SimulationConfig::new(
+                Some(1.1734),
+                Some(-0.13),
+                Some(14),
+                Some(u64::MAX),
+                Some(250_000),
+                Some("negative_binomial"),
+                Some(3.7),
+                None,
+            )
+            .unwrap(),
+            SimulationConfig::new(
+                None,
+                Some(0.1 + 0.2),
+                None,
+                Some(7),
+                Some(1),
+                Some("bivariate_poisson"),
+                None,
+                Some(0.15),
+            )
+            .unwrap(),
+        ];
+        let from_json = |json: &str| serde_json::from_str::<SimulationConfig>(json);
+        for config in configs {
+            let json = serde_json::to_string(&config).unwrap();
+            assert_eq!(from_json(&json).unwrap(), config);
+        }
+        assert!(from_json(r#"{"rho": 2.0}"#).is_err());
+        assert!(from_json(r#"{"tiebreak": null}"#).is_err());
+        assert!(from_json(r#"{"model": 3}"#).is_err());
+    }
+
+    #[test]
+    fn resumed_checkpoint_matches_an_uninterrupted_run() {
+        let _cache = cache_guard();
+        let input = sample_league(8);
+        let options = BulkOptions::default();
+        let path = std::env::temp_dir().join(format!("league-sim-{}.ckpt", std::process::id()));
+        let settings = CheckpointSettings::new(
+            &ModelConfig::default(),
+            &[TiebreakerStage::HeadToHead],
+            Some(&HashMap::from([("Team 03".to_string(), 6)])),
+        );
+        let start = BulkCheckpoint {
+            base_seed: 293,
+            completed: 0,
+            settings: settings.clone(),
+            tally: BulkTally::new(input.teams.len(), &options),
+        };
+        start
+            .extend(&input, 600, &options, &path, Some(250))
+            .unwrap();
+
+        let halfway = BulkCheckpoint::load(&path).unwrap();
+        assert_eq!(halfway.completed, 600);
+        assert_eq!(halfway.settings.mismatch(&settings), None);
+        let other_rho = CheckpointSettings {
+            rho: 0.0,
+            ..settings.clone()
+        };
+        assert_eq!(halfway.settings.mismatch(&other_rho), Some("rho"));
+        let no_deductions = CheckpointSettings {
+            deductions: Vec::new(),
+            ..settings
+        };
+        assert_eq!(
+            halfway.settings.mismatch(&no_deductions),
+            Some("deductions")
+        );
+        let resumed = halfway.extend(&input, 600, &options, &path, None).unwrap();
+        std::fs::write(&path, b"LOSCKPT1").unwrap();
+        assert!(BulkCheckpoint::load(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+
+        let uninterrupted = run_bulk_simulations(&input, 1_200, 293, &options);
+        assert_eq!(resumed.completed, 1_200);
+        assert_eq!(resumed.tally.position_counts, uninterrupted.position_counts);
+    }
+
+    #[test]
+    fn streamed_seasons_aggregate_like_a_bulk_run() {
+        let _cache = cache_guard();
+        let input = Arc::new(sample_league(8));
+        let options = BulkOptions::default();
+        let mut streamed = BulkTally::new(input.teams.len(), &options);
+        let mut seasons = 0;
+        for season in stream_seasons(Arc::clone(&input), 500, 294) {
+            streamed.record(season, &options);
+            seasons += 1;
+        }
+        assert_eq!(seasons, 500);
+
+        let bulk = run_bulk_simulations(&input, 500, 294, &options);
+        assert_eq!(streamed.position_counts, bulk.position_counts);
+    }
+
+    #[test]
+    fn an_unread_stream_blocks_without_stalling_other_runs() {
+        let _cache = cache_guard();
+        let input = Arc::new(sample_league(8));
+        let stream = stream_seasons(Arc::clone(&input), 1_000_000, 294);
+        std::thread::sleep(Duration::from_millis(100));
+        let bulk = run_bulk_simulations(&input, 200, 294, &BulkOptions::default());
+        assert_eq!(bulk.seasons(), 200);
+        // Unbounded, the workers would have run thousands of seasons per thread ahead by now;
+        // bounded, only the refills that race the draining below arrive past the buffer.
+        let bound = STREAM_BUFFER_PER_THREAD * rayon::current_num_threads();
+        let buffered = stream.try_iter().count();
+        assert!(buffered < 50 * bound, "{buffered}");
+        assert!(stream.recv().is_ok());
+    }
+
+    #[test]
+    fn single_head_to_head_criteria_split_only_on_their_own_measure() {
+        let mut rng = ChaCha8Rng::seed_from_u64(298);
+        let by_points = vec![TiebreakerStage::Points, TiebreakerStage::HeadToHeadPoints];
+        let season = simulate_single_season(&group_of_four(by_points), &mut rng);
+        assert_eq!(season.order, vec![0, 1, 2, 3]);
+
+        let by_difference = vec![
+            TiebreakerStage::Points,
+            TiebreakerStage::HeadToHeadPoints,
+            TiebreakerStage::HeadToHeadGoalDifference,
+        ];
+        let season = simulate_single_season(&group_of_four(by_difference), &mut rng);
+        assert_eq!(season.order, vec![0, 2, 1, 3]);
+
+        let results = [
+            MatchResult {
+                home_idx: 0,
+                away_idx: 1,
+                home_goals: 1,
+                away_goals: 2,
+            },
+            MatchResult {
+                home_idx: 1,
+                away_idx: 0,
+                home_goals: 1,
+                away_goals: 0,
+            },
+        ];
+        let table = head_to_head_table(&[0, 1], &results);
+        assert_eq!((table[0].away_goals, table[1].away_goals), (0, 2));
+        assert_eq!(
+            TiebreakerStage::from_name("head_to_head_away_goals").unwrap(),
+            TiebreakerStage::HeadToHeadAwayGoals
+        );
+        assert!(TiebreakerStage::from_name("away_goals").is_err());
+    }
+
+    #[test]
+    fn head_to_head_resorts_a_three_way_tie_recursively() {
+        // A, B and C end level on 15 points, 10 scored and 8 conceded. Among the three, A takes
+        // 12 points while B and C both have 3 points, 2 scored and 5 conceded, so only their
+        // own two games (C won 2-0, B won 1-0) split them: C goes above B.
+        let results = [
+            (0, 2, 2, 0),
+            (2, 0, 0, 2),
+            (0, 1, 2, 1),
+            (1, 0, 0, 1),
+            (2, 1, 2, 0),
+            (1, 2, 1, 0),
+        ];
+        let initial_stats = [(3, 3, 7), (12, 8, 3), (12, 8, 3)].map(|(points, gf, ga)| Stats {
+            points,
+            goals_for: gf,
+            goals_against: ga,
+            ..Stats::default()
+        });
+        let input = |tiebreakers| SimulationInput {
+            teams: ["A", "B", "C"].map(str::to_string).to_vec(),
+            initial_stats: initial_stats.to_vec(),
+            fixtures: results
+                .iter()
+                .map(|&(home_idx, away_idx, gh, ga)| FixtureSimulation {
+                    home_idx,
+                    away_idx,
+                    distribution: ProbabilityDistribution::fixed(gh, ga, MAX_GOALS),
+                })
+                .collect(),
+            tiebreakers,
+        };
+        let mut rng = ChaCha8Rng::seed_from_u64(299);
+
+        let by_name = simulate_single_season(&input(Vec::new()), &mut rng);
+        assert!(by_name
+            .final_stats
+            .iter()
+            .all(|stats| { (stats.points, stats.goals_for, stats.goals_against) == (15, 10, 8) }));
+        assert_eq!(by_name.order, vec![0, 1, 2]);
+
+        let stages = vec![
+            TiebreakerStage::Points,
+            TiebreakerStage::GoalDifference,
+            TiebreakerStage::HeadToHead,
+        ];
+        let with_h2h = simulate_single_season(&input(stages), &mut rng);
+        assert_eq!(with_h2h.order, vec![0, 2, 1]);
+    }
+
+    #[test]
+    fn split_season_adds_one_game_against_each_team_in_the_same_half() {
+        let _cache = cache_guard();
+        let strengths: Vec<(f64, f64)> = (0..12)
+            .map(|idx| (0.6 + idx as f64 * 0.08, 1.3 - idx as f64 * 0.05))
+            .collect();
+        let ratings = LeagueRatings::from_strengths(1.4, HOME_ADVANTAGE, &strengths);
+        let config = ModelConfig::default();
+        let mut pre_split = Vec::new();
+        for home_idx in 0..12 {
+            for away_idx in home_idx + 1..12 {
+                let fixture = ParsedFixture {
+                    home_idx,
+                    away_idx,
+                    neutral: false,
+                    result: None,
+                };
+                pre_split.push(ratings.fixture_simulation(&fixture, &config));
+            }
+        }
+        let input = SimulationInput {
+            teams: (0..12).map(|idx| format!("Team {idx:02}")).collect(),
+            initial_stats: vec![Stats::default(); 12],
+            fixtures: pre_split,
+            tiebreakers: Vec::new(),
+        };
+
+        for seed in 0..20 {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let first_phase = simulate_single_season(&input, &mut rng.clone());
+            let season = simulate_split_season(&input, &ratings, &config, 6, &mut rng);
+            assert!(season
+                .final_stats
+                .iter()
+                .all(|stats| stats.matches == 11 + 5));
+
+            let mut top_at_split = first_phase.order[..6].to_vec();
+            let mut top_at_end = season.order[..6].to_vec();
+            top_at_split.sort_unstable();
+            top_at_end.sort_unstable();
+            assert_eq!(top_at_split, top_at_end);
+        }
+    }
+
+    #[test]
+    fn conditioning_on_a_win_raises_title_odds() {
+        let _cache = cache_guard();
+        let config = ModelConfig {
+            home_advantage: Some(HOME_ADVANTAGE),
+            ..ModelConfig::default()
+        };
+        let rows: Vec<(String, Stats)> = [
+            ("Leaders", 30, 20, 14),
+            ("Chasers", 29, 19, 14),
+            ("Mid", 20, 16, 16),
+            ("Low", 12, 12, 22),
+        ]
+        .iter()
+        .map(|&(team, points, goals_for, goals_against)| {
+            let stats = Stats {
+                matches: 14,
+                points,
+                goals_for,
+                goals_against,
+                ..Stats::default()
+            };
+            (team.to_string(), stats)
+        })
+        .collect();
+        let league = || League::new(rows.clone(), &rows, &rows, &config).unwrap();
+        let pairs = [
+            ("Chasers", "Leaders"),
+            ("Leaders", "Mid"),
+            ("Low", "Chasers"),
+        ];
+        let fixtures: Vec<ParsedFixture> = pairs
+            .iter()
+            .map(|(home, away)| league().fixture(home, away).unwrap())
+            .collect();
+        let title_odds = |fixed: HashMap<usize, (usize, usize)>| {
+            let mut fixtures = fixtures.clone();
+            fix_results(&mut fixtures, &fixed).unwrap();
+            let input = league().simulation_input(&fixtures, &config);
+            let tally = run_bulk_simulations(&input, 10_000, 309, &BulkOptions::default());
+            tally.position_counts[1][0]
+        };
+        let unconditional = title_odds(HashMap::new());
+        let chasers_win = title_odds(HashMap::from([(0, (2, 0))]));
+        assert!(
+            chasers_win > unconditional,
+            "{chasers_win} vs {unconditional}"
+        );
+
+        let mut fixtures = fixtures.clone();
+        assert!(fix_results(&mut fixtures, &HashMap::from([(3, (1, 0))])).is_err());
+    }
+
+    #[test]
+    fn shared_seeds_shrink_scenario_delta_noise() {
+        let _cache = cache_guard();
+        let config = ModelConfig {
+            home_advantage: Some(HOME_ADVANTAGE),
+            ..ModelConfig::default()
+        };
+        let rows: Vec<(String, Stats)> = (0..6)
+            .map(|idx| {
+                let stats = Stats {
+                    matches: 20,
+                    points: 36 - 2 * idx,
+                    goals_for: 30 - idx,
+                    goals_against: 22 + idx,
+                    ..Stats::default()
+                };
+                (format!("Team {idx}"), stats)
+            })
+            .collect();
+        let league = League::new(rows.clone(), &rows, &rows, &config).unwrap();
+        let mut fixtures = Vec::new();
+        for home in &rows {
+            for away in &rows {
+                if home.0 != away.0 {
+                    fixtures.push(league.fixture(&home.0, &away.0).unwrap());
+                }
+            }
+        }
+        let win = HashMap::from([(0, (1, 0))]);
+        let draw = HashMap::from([(0, (1, 1))]);
+        let spread = |deltas: Vec<Vec<f64>>| deltas.iter().flatten().map(|d| d.abs()).sum::<f64>();
+        let delta = |a, b, seeds| scenario_delta(&league, &fixtures, [a, b], &config, 1_000, seeds);
+
+        assert_eq!(spread(delta(&win, &win, [310, 310]).unwrap()), 0.0);
+        assert!(spread(delta(&win, &win, [310, 311]).unwrap()) > 0.0);
+        let shared = spread(delta(&win, &draw, [310, 310]).unwrap());
+        let independent = spread(delta(&win, &draw, [310, 311]).unwrap());
+        assert!(shared < independent, "{shared} vs {independent}");
+        assert!(delta(&HashMap::from([(99, (1, 0))]), &draw, [1, 1]).is_err());
+    }
+
+    #[test]
+    fn a_final_day_clash_between_the_top_two_matters_most() {
+        let _cache = cache_guard();
+        let config = ModelConfig {
+            home_advantage: Some(HOME_ADVANTAGE),
+            ..ModelConfig::default()
+        };
+        let rows: Vec<(String, Stats)> = [("A", 30), ("B", 29), ("C", 12), ("D", 10)]
+            .iter()
+            .map(|&(team, points)| {
+                let stats = Stats {
+                    matches: 14,
+                    points,
+                    goals_for: 20,
+                    goals_against: 20,
+                    ..Stats::default()
+                };
+                (team.to_string(), stats)
+            })
+            .collect();
+        let league = League::new(rows.clone(), &rows, &rows, &config).unwrap();
+        let fixtures: Vec<ParsedFixture> = [("C", "D"), ("A", "C"), ("D", "B"), ("A", "B")]
+            .iter()
+            .map(|(home, away)| league.fixture(home, away).unwrap())
+            .collect();
+        let scores = fixture_importance_scores(&league, &fixtures, &config, 4_000, 314);
+        assert_eq!(scores.len(), 4);
+        assert_eq!(scores[0].fixture, 3);
+        assert!(scores[0].delta_home_title > 0.0 && scores[0].delta_away_title < 0.0);
+        assert!(scores
+            .windows(2)
+            .all(|pair| pair[0].importance_score >= pair[1].importance_score));
+        let bottom_clash = scores.iter().find(|score| score.fixture == 0).unwrap();
+        assert!(bottom_clash.importance_score < scores[0].importance_score / 4.0);
+    }
+
+    #[test]
+    fn survival_fixtures_matter_more_in_a_relegation_battle() {
+        let _cache = cache_guard();
+        let config = ModelConfig {
+            home_advantage: Some(HOME_ADVANTAGE),
+            ..ModelConfig::default()
+        };
+        // Eight teams with the bottom three relegated: E, F and G are within a point of each
+        // other around the cut, while D is safely mid-table.
+        let points = [40, 36, 30, 26, 16, 15, 15, 12];
+        let rows: Vec<(String, Stats)> = ["A", "B", "C", "D", "E", "F", "G", "H"]
+            .iter()
+            .zip(points)
+            .map(|(team, points)| {
+                let stats = Stats {
+                    matches: 20,
+                    points,
+                    goals_for: 25,
+                    goals_against: 25,
+                    ..Stats::default()
+                };
+                (team.to_string(), stats)
+            })
+            .collect();
+        let league = League::new(rows.clone(), &rows, &rows, &config).unwrap();
+        let fixtures: Vec<ParsedFixture> = [("G", "A"), ("D", "B"), ("F", "H"), ("C", "E")]
+            .iter()
+            .map(|(home, away)| league.fixture(home, away).unwrap())
+            .collect();
+        let value = |team: usize| {
+            let values = marginal_values(
+                &league,
+                &fixtures,
+                team,
+                SeasonMetric::RelegationSurvival,
+                &config,
+                4_000,
+                315,
+            );
+            assert_eq!(values.len(), 1);
+            let value = values[0].clone();
+            assert!(value.p_win >= value.p_draw && value.p_draw >= value.p_loss);
+            assert!(value.expected_value >= value.p_loss && value.expected_value <= value.p_win);
+            value.p_win - value.p_loss
+        };
+        let (battler, mid_table) = (value(6), value(3));
+        assert!(battler > 0.2, "battler {battler}");
+        assert!(
+            battler > mid_table + 0.2,
+            "battler {battler} vs mid-table {mid_table}"
+        );
+
+        assert_eq!(SeasonMetric::parse("top4"), Ok(SeasonMetric::Top4));
+        assert!(SeasonMetric::parse("europe").is_err());
+        assert_eq!(SeasonMetric::Top4.share(&[1, 1, 1, 1, 6], 10), 0.4);
+    }
+
+    #[test]
+    fn red_cards_lower_the_carded_sides_goals() {
+        let _cache = cache_guard();
+        assert!(RedCards::validated(1.5, 0.3).is_err());
+        assert!(RedCards::validated(0.03, 1.0).is_err());
+        assert_eq!(RedCards::validated(0.0, 0.3), Ok(None));
+
+        let mean_goals = |red_cards: Option<RedCards>| {
+            let config = ModelConfig {
+                red_cards,
+                ..ModelConfig::default()
+            };
+            let distribution = config.distribution(1.6, 1.2);
+            let mut rng = ChaCha8Rng::seed_from_u64(308);
+            let n_sims = 100_000;
+            let (mut home, mut away) = (0, 0);
+            for _ in 0..n_sims {
+                let (gh, ga) = DixonColes::simulate_from_distribution(&mut rng, &distribution);
+                home += gh;
+                away += ga;
+            }
+            (home as f64 / n_sims as f64, away as f64 / n_sims as f64)
+        };
+        let clean = mean_goals(None);
+        // Every match has a card, so each side is a man down in half of them.
+        let carded = mean_goals(RedCards::validated(1.0, DEFAULT_RED_CARD_PENALTY).unwrap());
+        assert!(carded.0 < clean.0 * 0.9, "home {carded:?} vs {clean:?}");
+        assert!(carded.1 < clean.1 * 0.9, "away {carded:?} vs {clean:?}");
+        let rare = mean_goals(RedCards::validated(DEFAULT_RED_CARD_RATE, 0.3).unwrap());
+        assert!(rare.0 < clean.0 && rare.0 > carded.0);
+    }
+
+    #[test]
+    fn congested_teams_score_fewer_goals() {
+        let _cache = cache_guard();
+        let config = ModelConfig {
+            home_advantage: Some(HOME_ADVANTAGE),
+            ..ModelConfig::default()
+        };
+        let rows: Vec<(String, Stats)> = ["A", "B", "C", "D"]
+            .iter()
+            .map(|team| {
+                let stats = Stats {
+                    matches: 10,
+                    goals_for: 14,
+                    goals_against: 14,
+                    ..Stats::default()
+                };
+                (team.to_string(), stats)
+            })
+            .collect();
+        let league = || League::new(rows.clone(), &rows, &rows, &config).unwrap();
+        let fixtures: Vec<ParsedFixture> = [("A", "B"), ("C", "A"), ("A", "D")]
+            .iter()
+            .map(|(home, away)| league().fixture(home, away).unwrap())
+            .collect();
+        let fatigue = Fatigue {
+            window: DEFAULT_FATIGUE_WINDOW,
+            factor: DEFAULT_FATIGUE_FACTOR,
+        };
+        assert_eq!(
+            fatigue.tired_sides(&fixtures, &[0, 3, 6]),
+            [(false, false), (false, true), (true, false)]
+        );
+        assert!(league()
+            .fatigued_simulation_input(&fixtures, &[0, 3], &fatigue, &config)
+            .is_err());
+
+        // Three games in seven days against the same games ten days apart.
+        let goals_for_a = |dates: &[i64]| {
+            let input = league()
+                .fatigued_simulation_input(&fixtures, dates, &fatigue, &config)
+                .unwrap();
+            let mut rng = ChaCha8Rng::seed_from_u64(307);
+            (0..20_000)
+                .map(|_| simulate_single_season(&input, &mut rng).final_stats[0].goals_for - 14)
+                .sum::<i64>()
+        };
+        let (congested, spaced) = (goals_for_a(&[0, 3, 6]), goals_for_a(&[0, 10, 20]));
+        assert!(
+            (congested as f64) < spaced as f64 * 0.97,
+            "congested {congested} vs spaced {spaced}"
+        );
+    }
+
+    #[test]
+    fn weaker_teams_spend_fewer_seasons_in_the_top_division() {
+        let _cache = cache_guard();
+        let config = ModelConfig {
+            home_advantage: Some(HOME_ADVANTAGE),
+            ..ModelConfig::default()
+        };
+        // Six teams per division; goal records fall steadily from the first to the last team.
+        let division = |prefix: &str| {
+            let rows: Vec<(String, Stats)> = (0..6)
+                .map(|idx| {
+                    let stats = Stats {
+                        matches: 10,
+                        goals_for: 20 - 2 * idx,
+                        goals_against: 8 + 2 * idx,
+                        ..Stats::default()
+                    };
+                    (format!("{prefix} {idx}"), stats)
+                })
+                .collect();
+            League::new(rows.clone(), &rows, &rows, &config).unwrap()
+        };
+        let pyramid = Pyramid::new(
+            [(division("Top"), &[]), (division("Lower"), &[])],
+            &config,
+            2,
+            2,
+            6,
+        )
+        .unwrap();
+        let counts = simulate_pyramid(&pyramid, 6, 400, 301);
+        let mean_seasons: Vec<f64> = counts
+            .iter()
+            .map(|counts| {
+                let total: u64 = counts.iter().sum();
+                assert_eq!(total, 400);
+                let seasons: u64 = counts.iter().enumerate().map(|(k, &c)| k as u64 * c).sum();
+                seasons as f64 / 400.0
+            })
+            .collect();
+        // Everyone starting on top is there in year one; nobody below is.
+        assert!(counts[..6].iter().all(|counts| counts[0] == 0));
+        assert!(counts[6..].iter().all(|counts| counts[6] == 0));
+        assert!(mean_seasons[0] > mean_seasons[5] + 1.0);
+        assert!(mean_seasons[6] > mean_seasons[11] + 1.0);
+        assert!(mean_seasons[..6].iter().sum::<f64>() > mean_seasons[6..].iter().sum::<f64>());
+
+        assert!(Pyramid::new(
+            [(division("Top"), &[]), (division("Top"), &[])],
+            &config,
+            2,
+            2,
+            6
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn strings_decode_every_escape_and_round_trip() {
+        let text = json::parse(r#""a\"b\\c\/d\be\ff\ng\rh\t\u00e9\ud83d\ude00""#).unwrap();
+        let decoded = text.as_str().unwrap();
+        assert_eq!(decoded, "a\"b\\c/d\u{8}e\u{c}f\ng\rh\té😀");
+        let mut out = String::new();
+        json::write_string(&mut out, decoded);
+        assert_eq!(json::parse(&out).unwrap(), text);
+    }
+
+    #[test]
+    fn non_finite_numbers_are_not_json() {
+        for text in ["NaN", "inf", "-Infinity", "[1, NaN]"] {
+            assert!(json::parse(text).is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn table_records_must_be_whole_numbers() {
+        let header = r#"["Team", "M", "W", "D", "L", "G", "GA", "PTS"]"#;
+        let table = |matches: &str| {
+            json::parse(&format!(
+                r#"[{header}, ["Ash", {matches}, 1, 0, 0, 2, 1, 3]]"#
+            ))
+            .unwrap()
+        };
+        assert_eq!(json::table_rows(&table("1")).unwrap()[0].1.matches, 1);
+        let err = json::table_rows(&table("1.5")).unwrap_err();
+        assert!(err.contains("matches at column 1 for Ash"), "{err}");
+        assert!(json::table_rows(&table("\"1\"")).is_err());
+    }
+
+    #[test]
+    fn fixtures_read_the_same_shapes_as_python() {
+        let json = json::parse(
+            r#"[
+                {"h": {"title": "Ash"}, "a": {"title": "Birch"}},
+                {"h": {"title": "Birch"}, "a": {"title": "Ash"}, "neutral": true},
+                {"h": {"title": "Ash"}, "a": {"title": "Birch"}, "result": [2, 1]},
+                {"h": {"title": "Birch"}, "a": {"title": "Ash"}, "result": null},
+                ["Birch", "Ash"]
+            ]"#,
+        )
+        .unwrap();
+        let team_to_idx = TeamIndex::from([("Ash".to_string(), 0), ("Birch".to_string(), 1)]);
+        let parsed: Vec<_> = json::fixtures(&json)
+            .unwrap()
+            .iter()
+            .map(|spec| spec.resolve(&team_to_idx).unwrap())
+            .collect();
+        let summary: Vec<_> = parsed
+            .iter()
+            .map(|fixture| (fixture.home_idx, fixture.neutral, fixture.result))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (0, false, None),
+                (1, true, None),
+                (0, false, Some((2, 1))),
+                (1, false, None),
+                (1, false, None),
+            ]
+        );
+
+        let bad = |text: &str| json::fixtures(&json::parse(text).unwrap()).unwrap_err();
+        let shape = bad(r#"[{"h": {"title": "Ash"}, "a": {"title": "Birch"}, "result": [2]}]"#);
+        assert!(shape.contains("(home, away) pair"), "{shape}");
+        assert!(bad(r#"[["Ash"]]"#).contains("(home_team, away_team)"));
+        let negative = json::fixtures(
+            &json::parse(
+                r#"[{"h": {"title": "Ash"}, "a": {"title": "Birch"}, "result": [-1, 0]}]"#,
+            )
+            .unwrap(),
+        )
+        .unwrap()[0]
+            .resolve(&team_to_idx)
+            .unwrap_err();
+        assert!(negative.contains("cannot be negative"), "{negative}");
     }
 }
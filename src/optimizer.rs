@@ -0,0 +1,90 @@
+//! First-order optimisation over flat parameter vectors, for likelihoods whose derivatives are
+//! not worth deriving by hand.
+
+/// Forward-difference step used by `numeric_gradient`.
+pub const GRADIENT_STEP: f64 = 1e-6;
+
+/// Adam (adaptive moment estimation) minimiser. The objective returns `(loss, gradient)`; to
+/// maximise a log-likelihood, hand it the negated value and gradient. An objective that
+/// evaluates a fresh mini-batch on every call works as well, since each step only looks at the
+/// latest gradient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Adam {
+    pub learning_rate: f64,
+    pub beta1: f64,
+    pub beta2: f64,
+    pub epsilon: f64,
+    pub max_iter: usize,
+    /// Converged once every gradient component is smaller than this in absolute value.
+    pub tolerance: f64,
+}
+
+impl Default for Adam {
+    fn default() -> Self {
+        Adam {
+            learning_rate: 0.01,
+            beta1: 0.9,
+            beta2: 0.999,
+            epsilon: 1e-8,
+            max_iter: 10_000,
+            tolerance: 1e-8,
+        }
+    }
+}
+
+impl Adam {
+    /// Runs up to `max_iter` steps from `initial` and returns `(best_params, best_loss,
+    /// converged)`, where the best parameters are the lowest-loss point evaluated.
+    pub fn minimize(
+        &self,
+        initial: Vec<f64>,
+        objective: impl Fn(&[f64]) -> (f64, Vec<f64>),
+    ) -> (Vec<f64>, f64, bool) {
+        let mut params = initial;
+        let mut first_moment = vec![0.0; params.len()];
+        let mut second_moment = vec![0.0; params.len()];
+        let (mut best_params, mut best_loss) = (params.clone(), f64::INFINITY);
+
+        for step in 1..=self.max_iter {
+            let (loss, gradient) = objective(&params);
+            if loss < best_loss {
+                best_loss = loss;
+                best_params.clone_from(&params);
+            }
+            if gradient.iter().all(|slope| slope.abs() < self.tolerance) {
+                return (best_params, best_loss, true);
+            }
+            let first_correction = 1.0 - self.beta1.powi(step as i32);
+            let second_correction = 1.0 - self.beta2.powi(step as i32);
+            for (idx, slope) in gradient.into_iter().enumerate() {
+                first_moment[idx] = self.beta1 * first_moment[idx] + (1.0 - self.beta1) * slope;
+                second_moment[idx] =
+                    self.beta2 * second_moment[idx] + (1.0 - self.beta2) * slope * slope;
+                let mean = first_moment[idx] / first_correction;
+                let variance = second_moment[idx] / second_correction;
+                params[idx] -= self.learning_rate * mean / (variance.sqrt() + self.epsilon);
+            }
+        }
+        let (loss, _) = objective(&params);
+        if loss < best_loss {
+            return (params, loss, false);
+        }
+        (best_params, best_loss, false)
+    }
+}
+
+/// Forward-difference gradient of `f` at `params` with step `GRADIENT_STEP`, alongside `f`
+/// itself, in the `(loss, gradient)` shape `Adam::minimize` expects.
+pub fn numeric_gradient(f: impl Fn(&[f64]) -> f64, params: &[f64]) -> (f64, Vec<f64>) {
+    let value = f(params);
+    let mut shifted = params.to_vec();
+    let gradient = (0..params.len())
+        .map(|idx| {
+            shifted[idx] += GRADIENT_STEP;
+            let slope = (f(&shifted) - value) / GRADIENT_STEP;
+            shifted[idx] = params[idx];
+            slope
+        })
+        .collect();
+    (value, gradient)
+}
@@ -0,0 +1,154 @@
+//! Sobol low-discrepancy points for quasi-Monte Carlo season simulation. Each simulated
+//! season is one point, each fixture one coordinate.
+
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+const BITS: usize = 32;
+/// Seed for the initial direction numbers, so every run uses the same sequence.
+const DIRECTION_SEED: u64 = 0x50B0_1D1E;
+
+/// Gray-code Sobol generator with a random digital shift per coordinate. Point `i` depends only
+/// on `i`, so any partition of `0..n` across threads yields the same points.
+#[derive(Debug, Clone)]
+pub struct Sobol {
+    /// `[dimension][bit]` direction numbers, scaled to 32 bits.
+    directions: Vec<[u32; BITS]>,
+    /// XORed into every coordinate; all zero gives the plain sequence.
+    shift: Vec<u32>,
+}
+
+impl Sobol {
+    /// The first `dimensions` coordinates with no shift: dimension 0 is the van der Corput
+    /// sequence, later ones follow successive primitive polynomials over GF(2).
+    pub fn new(dimensions: usize) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(DIRECTION_SEED);
+        let mut directions = Vec::with_capacity(dimensions);
+        if dimensions > 0 {
+            directions.push(std::array::from_fn(|bit| 1u32 << (BITS - 1 - bit)));
+        }
+        for polynomial in primitive_polynomials().take(dimensions.saturating_sub(1)) {
+            directions.push(polynomial_directions(polynomial, &mut rng));
+        }
+        Sobol {
+            shift: vec![0; dimensions],
+            directions,
+        }
+    }
+
+    /// Like `new`, with every coordinate XORed by a shift drawn from `seed`. Shifted sequences
+    /// keep their low discrepancy while each one is an unbiased estimator.
+    pub fn shifted(dimensions: usize, seed: u64) -> Self {
+        let mut sobol = Self::new(dimensions);
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        sobol.shift.iter_mut().for_each(|shift| *shift = rng.gen());
+        sobol
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.directions.len()
+    }
+
+    /// Point `index` of the sequence, every coordinate in `[0, 1)`.
+    pub fn point(&self, index: u32) -> Vec<f64> {
+        let gray = index ^ (index >> 1);
+        self.directions
+            .iter()
+            .zip(&self.shift)
+            .map(|(directions, shift)| {
+                let bits = (0..BITS)
+                    .filter(|bit| gray >> bit & 1 == 1)
+                    .fold(*shift, |acc, bit| acc ^ directions[bit]);
+                bits as f64 / (1u64 << BITS) as f64
+            })
+            .collect()
+    }
+}
+
+/// Direction numbers for a primitive polynomial `x^s + a_1 x^(s-1) + ... + 1`, stored with the
+/// leading and constant bits set. The first `s` odd `m_k < 2^k` are drawn from `rng`.
+fn polynomial_directions(polynomial: u32, rng: &mut ChaCha8Rng) -> [u32; BITS] {
+    let degree = (31 - polynomial.leading_zeros()) as usize;
+    let mut m = [0u64; BITS];
+    for k in 0..BITS {
+        m[k] = if k < degree {
+            (rng.gen_range(0..1u64 << k) << 1) | 1
+        } else {
+            let mut value = m[k - degree] ^ (m[k - degree] << degree);
+            for j in 1..degree {
+                if polynomial >> (degree - j) & 1 == 1 {
+                    value ^= m[k - j] << j;
+                }
+            }
+            value
+        };
+    }
+    std::array::from_fn(|k| (m[k] << (BITS - 1 - k)) as u32)
+}
+
+/// Primitive polynomials over GF(2) by increasing degree, as bit masks (`x^2 + x + 1` is `0b111`).
+fn primitive_polynomials() -> impl Iterator<Item = u32> {
+    (1..BITS as u32).flat_map(|degree| {
+        let order = (1u64 << degree) - 1;
+        let factors = prime_factors(order);
+        ((1u32 << degree) + 1..1u32 << (degree + 1))
+            .step_by(2)
+            .filter(move |&polynomial| {
+                x_power_mod(order, polynomial) == 1
+                    && factors
+                        .iter()
+                        .all(|factor| x_power_mod(order / factor, polynomial) != 1)
+            })
+    })
+}
+
+/// `x^exponent mod polynomial` in GF(2)[x].
+fn x_power_mod(exponent: u64, polynomial: u32) -> u32 {
+    let degree = 31 - polynomial.leading_zeros();
+    let multiply = |a: u32, b: u32| {
+        let (mut a, mut b, mut product) = (a as u64, b, 0u64);
+        while b > 0 {
+            if b & 1 == 1 {
+                product ^= a;
+            }
+            b >>= 1;
+            a <<= 1;
+            if a >> degree & 1 == 1 {
+                a ^= polynomial as u64;
+            }
+        }
+        product as u32
+    };
+    if degree == 1 {
+        // Modulo x + 1, x is 1.
+        return 1;
+    }
+    let (mut result, mut base, mut exponent) = (1u32, 0b10u32, exponent);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = multiply(result, base);
+        }
+        base = multiply(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+fn prime_factors(mut value: u64) -> Vec<u64> {
+    let mut factors = Vec::new();
+    let mut divisor = 2;
+    while divisor * divisor <= value {
+        if value.is_multiple_of(divisor) {
+            factors.push(divisor);
+            while value.is_multiple_of(divisor) {
+                value /= divisor;
+            }
+        }
+        divisor += 1;
+    }
+    if value > 1 {
+        factors.push(value);
+    }
+    factors
+}
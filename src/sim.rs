@@ -0,0 +1,2524 @@
+//! The simulation engine on plain Rust types: score models, league ratings, single seasons
+//! and seeded bulk runs. The Python bindings in the crate root are thin shims over it.
+
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rand_distr::{Distribution, Gamma, Poisson};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+pub const HOME_ADVANTAGE: f64 = 1.25;
+pub const DEFAULT_LAMBDA: f64 = 1.0;
+pub const DEFAULT_RHO: f64 = -0.1;
+pub const MAX_GOALS: usize = 10;
+pub const DEFAULT_DISPERSION: f64 = 5.0;
+pub const DEFAULT_LAMBDA_3: f64 = 0.1;
+pub(crate) const DEFAULT_CACHE_CAPACITY: usize = 4096;
+pub(crate) const CACHE_CAPACITY_ENV: &str = "LEAGUE_SIM_CACHE_CAPACITY";
+/// Unread seasons `stream_seasons` buffers per worker thread.
+pub const STREAM_BUFFER_PER_THREAD: usize = 4;
+
+pub(crate) type MatrixKey = (u64, u64, u64, usize);
+
+lazy_static! {
+    pub(crate) static ref PROBABILITY_CACHE: ProbabilityCache = ProbabilityCache::new(
+        cache_capacity(std::env::var(CACHE_CAPACITY_ENV).ok().as_deref())
+    );
+    /// `ln(k!)` for `k` in `0..=MAX_GOALS`, the goal counts of the default score grid.
+    static ref LOG_FACTORIAL: Vec<f64> = (0..=MAX_GOALS as i64).map(sum_log_factorial).collect();
+}
+
+fn sum_log_factorial(k: i64) -> f64 {
+    (1..=k).map(|i| (i as f64).ln()).sum()
+}
+
+/// `ln(k!)` from `LOG_FACTORIAL`, summed directly past a widened `max_goals`.
+fn log_factorial(k: i64) -> f64 {
+    LOG_FACTORIAL
+        .get(k as usize)
+        .copied()
+        .unwrap_or_else(|| sum_log_factorial(k))
+}
+
+/// Entry cap for `PROBABILITY_CACHE`; unset or invalid values fall back to the default.
+pub(crate) fn cache_capacity(value: Option<&str>) -> NonZeroUsize {
+    value
+        .and_then(|raw| raw.trim().parse::<NonZeroUsize>().ok())
+        .unwrap_or(NonZeroUsize::new(DEFAULT_CACHE_CAPACITY).expect("default capacity is non-zero"))
+}
+
+/// Share of a full `BoundedCache` evicted in one pass, as a divisor of its capacity.
+const EVICTION_BATCH_DIVISOR: usize = 8;
+
+struct Stamped<V> {
+    value: V,
+    last_used: AtomicU64,
+}
+
+/// A map from `K` to `V` capped at `capacity` entries, sharded so lookups from many threads do
+/// not queue on one lock. Once full, an insert evicts the least recently used eighth of the
+/// entries in a single scan, so misses cost a scan only once every few inserts.
+pub(crate) struct BoundedCache<K, V> {
+    entries: DashMap<K, Stamped<V>>,
+    capacity: NonZeroUsize,
+    /// Entries stored plus slots claimed by inserts in flight; never above `capacity`.
+    reserved: AtomicUsize,
+    clock: AtomicU64,
+    evicting: Mutex<()>,
+}
+
+/// Score matrices by `MatrixKey`.
+pub(crate) type ProbabilityCache = BoundedCache<MatrixKey, ProbabilityDistribution>;
+
+impl<K: Copy + Eq + Hash, V: Clone> BoundedCache<K, V> {
+    pub(crate) fn new(capacity: NonZeroUsize) -> Self {
+        BoundedCache {
+            entries: DashMap::new(),
+            capacity,
+            reserved: AtomicUsize::new(0),
+            clock: AtomicU64::new(0),
+            evicting: Mutex::new(()),
+        }
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, AtomicOrdering::Relaxed)
+    }
+
+    /// Hits only read the clock, which moves on inserts, so concurrent lookups of a hot key write
+    /// nothing shared once its timestamp is current.
+    pub(crate) fn get(&self, key: &K) -> Option<V> {
+        let entry = self.entries.get(key)?;
+        let now = self.clock.load(AtomicOrdering::Relaxed);
+        if entry.last_used.load(AtomicOrdering::Relaxed) != now {
+            entry.last_used.store(now, AtomicOrdering::Relaxed);
+        }
+        Some(entry.value.clone())
+    }
+
+    pub(crate) fn contains(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Stores `value`, returning whether `key` was new.
+    pub(crate) fn insert(&self, key: K, value: V) -> bool {
+        let stamped = Stamped {
+            value,
+            last_used: AtomicU64::new(self.tick()),
+        };
+        if let Some(mut entry) = self.entries.get_mut(&key) {
+            *entry = stamped;
+            return false;
+        }
+        self.reserve_slot();
+        match self.entries.entry(key) {
+            Entry::Occupied(mut entry) => {
+                // Another thread stored the key since the check above; give the slot back.
+                entry.insert(stamped);
+                self.reserved.fetch_sub(1, AtomicOrdering::AcqRel);
+                false
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(stamped);
+                true
+            }
+        }
+    }
+
+    /// Claims room for one more entry, evicting first when every slot is taken.
+    fn reserve_slot(&self) {
+        loop {
+            let taken = self.reserved.load(AtomicOrdering::Acquire);
+            if taken < self.capacity.get() {
+                if self
+                    .reserved
+                    .compare_exchange_weak(
+                        taken,
+                        taken + 1,
+                        AtomicOrdering::AcqRel,
+                        AtomicOrdering::Relaxed,
+                    )
+                    .is_ok()
+                {
+                    return;
+                }
+                continue;
+            }
+            self.evict_oldest();
+        }
+    }
+
+    /// Removes the least recently used batch; threads that find another one evicting wait for it
+    /// and then see the freed slots.
+    fn evict_oldest(&self) {
+        let _evicting = self
+            .evicting
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner());
+        if self.reserved.load(AtomicOrdering::Acquire) < self.capacity.get() {
+            return;
+        }
+        let mut stamps: Vec<(u64, K)> = self
+            .entries
+            .iter()
+            .map(|entry| (entry.last_used.load(AtomicOrdering::Relaxed), *entry.key()))
+            .collect();
+        let batch = (self.capacity.get() / EVICTION_BATCH_DIVISOR)
+            .max(1)
+            .min(stamps.len());
+        if batch == 0 {
+            // Every slot is claimed by an insert that has not landed yet.
+            std::thread::yield_now();
+            return;
+        }
+        stamps.select_nth_unstable_by_key(batch - 1, |(last_used, _)| *last_used);
+        for (_, key) in &stamps[..batch] {
+            if self.entries.remove(key).is_some() {
+                self.reserved.fetch_sub(1, AtomicOrdering::AcqRel);
+            }
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity.get()
+    }
+
+    pub(crate) fn clear(&self) {
+        let mut removed = 0;
+        self.entries.retain(|_, _| {
+            removed += 1;
+            false
+        });
+        self.reserved.fetch_sub(removed, AtomicOrdering::AcqRel);
+    }
+}
+
+pub(crate) static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+pub(crate) static CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// Seasons finished by the latest `run_bulk_simulations` call, for polling from Python.
+pub(crate) static SIMULATION_PROGRESS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone)]
+pub struct FixtureSimulation {
+    pub home_idx: usize,
+    pub away_idx: usize,
+    pub distribution: ProbabilityDistribution,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub matches: i64,
+    pub wins: i64,
+    pub draws: i64,
+    pub losses: i64,
+    pub goals_for: i64,
+    pub goals_against: i64,
+    pub points: i64,
+}
+
+impl Stats {
+    pub fn goal_difference(&self) -> i64 {
+        self.goals_for - self.goals_against
+    }
+
+    pub fn record_result(&mut self, scored: i64, conceded: i64) {
+        self.matches += 1;
+        self.goals_for += scored;
+        self.goals_against += conceded;
+        match scored.cmp(&conceded) {
+            Ordering::Greater => {
+                self.wins += 1;
+                self.points += 3;
+            }
+            Ordering::Equal => {
+                self.draws += 1;
+                self.points += 1;
+            }
+            Ordering::Less => self.losses += 1,
+        }
+    }
+}
+
+/// Goal distribution behind each fixture's score matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ScoreModel {
+    /// Independent Poisson goals with the low-score `rho` correction.
+    DixonColes,
+    /// Independent Poisson goals; `rho` is ignored.
+    Poisson,
+    /// Independent negative binomial goals with variance `lambda + lambda^2 / dispersion`.
+    NegativeBinomial { dispersion: f64 },
+    /// Dixon-Coles with an extra `zero_inflation` share of the mass moved onto 0-0.
+    ZeroInflated { zero_inflation: f64 },
+    /// Goals share a latent `Poisson(lambda_3)` term, giving both sides covariance `lambda_3`.
+    /// Inputs with a fixture whose smaller lambda is below `lambda_3` are rejected.
+    BivariatePoisson { lambda_3: f64 },
+}
+
+impl ScoreModel {
+    pub fn parse(
+        model: Option<&str>,
+        dispersion: Option<f64>,
+        lambda_3: Option<f64>,
+    ) -> Result<Self, String> {
+        let model = match model.unwrap_or("dixon_coles") {
+            "dixon_coles" => ScoreModel::DixonColes,
+            "poisson" => ScoreModel::Poisson,
+            "negative_binomial" => {
+                let dispersion = dispersion.unwrap_or(DEFAULT_DISPERSION);
+                if !(dispersion > 0.0 && dispersion.is_finite()) {
+                    return Err(format!(
+                        "dispersion must be strictly positive, got {dispersion}"
+                    ));
+                }
+                ScoreModel::NegativeBinomial { dispersion }
+            }
+            "bivariate_poisson" => {
+                let lambda_3 = lambda_3.unwrap_or(DEFAULT_LAMBDA_3);
+                if !(lambda_3 >= 0.0 && lambda_3.is_finite()) {
+                    return Err(format!(
+                        "lambda_3 must be a finite non-negative expected goal count, got {lambda_3}"
+                    ));
+                }
+                ScoreModel::BivariatePoisson { lambda_3 }
+            }
+            other => {
+                return Err(format!(
+                    "Unknown score model {other:?}; expected \"dixon_coles\", \"poisson\", \"negative_binomial\" or \"bivariate_poisson\""
+                ))
+            }
+        };
+        if dispersion.is_some() && !matches!(model, ScoreModel::NegativeBinomial { .. }) {
+            return Err("dispersion only applies to the negative_binomial model".to_string());
+        }
+        if lambda_3.is_some() && !matches!(model, ScoreModel::BivariatePoisson { .. }) {
+            return Err("lambda_3 only applies to the bivariate_poisson model".to_string());
+        }
+        Ok(model)
+    }
+
+    /// The `model=` name that selects this variant; zero inflation has its own entry point.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScoreModel::DixonColes => "dixon_coles",
+            ScoreModel::Poisson => "poisson",
+            ScoreModel::NegativeBinomial { .. } => "negative_binomial",
+            ScoreModel::ZeroInflated { .. } => "zero_inflated",
+            ScoreModel::BivariatePoisson { .. } => "bivariate_poisson",
+        }
+    }
+}
+
+/// Score-model settings shared by every fixture of one simulation call.
+#[derive(Debug, Clone, Copy)]
+pub struct ModelConfig {
+    pub rho: f64,
+    /// Fixed home multiplier; `None` estimates it from the home/away tables.
+    pub home_advantage: Option<f64>,
+    pub max_goals: usize,
+    pub score_model: ScoreModel,
+    pub red_cards: Option<RedCards>,
+}
+
+/// Red cards in the first hour: `rate` of matches see one, shown to either side with equal
+/// probability, and the side left short scores at `1 - penalty` times its usual rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RedCards {
+    pub rate: f64,
+    pub penalty: f64,
+}
+
+impl RedCards {
+    /// `None` when no red cards are drawn at all.
+    pub fn validated(rate: f64, penalty: f64) -> Result<Option<Self>, String> {
+        if !(0.0..=1.0).contains(&rate) {
+            return Err(format!("red_card_rate must be in [0.0, 1.0], got {rate}"));
+        }
+        if !(0.0..1.0).contains(&penalty) {
+            return Err(format!(
+                "red_card_penalty must be in [0.0, 1.0), got {penalty}"
+            ));
+        }
+        Ok((rate > 0.0).then_some(RedCards { rate, penalty }))
+    }
+}
+
+impl Default for ModelConfig {
+    fn default() -> Self {
+        ModelConfig {
+            rho: DEFAULT_RHO,
+            home_advantage: None,
+            max_goals: MAX_GOALS,
+            score_model: ScoreModel::DixonColes,
+            red_cards: None,
+        }
+    }
+}
+
+impl ModelConfig {
+    pub fn validated(
+        rho: Option<f64>,
+        home_advantage: Option<f64>,
+        max_goals: Option<usize>,
+    ) -> Result<Self, String> {
+        let mut config = ModelConfig::default();
+        if let Some(rho) = rho {
+            if !(rho > -1.0 && rho < 1.0) {
+                return Err(format!("rho must be in (-1.0, 1.0), got {rho}"));
+            }
+            config.rho = rho;
+        }
+        if let Some(home_advantage) = home_advantage {
+            if !(home_advantage > 0.0 && home_advantage.is_finite()) {
+                return Err(format!(
+                    "home_advantage must be strictly positive, got {home_advantage}"
+                ));
+            }
+            config.home_advantage = Some(home_advantage);
+        }
+        if let Some(max_goals) = max_goals {
+            if max_goals == 0 {
+                return Err("max_goals must be at least 1".to_string());
+            }
+            config.max_goals = max_goals;
+        }
+        Ok(config)
+    }
+
+    /// Score distribution of one fixture. With red cards the three cases (no card, home side
+    /// carded, away side carded) are blended by their probabilities, which samples the same as
+    /// drawing the card first and then the score.
+    pub fn distribution(&self, lambda_h: f64, lambda_a: f64) -> ProbabilityDistribution {
+        let Some(red_cards) = self.red_cards else {
+            return self.score_distribution(lambda_h, lambda_a);
+        };
+        let remaining = 1.0 - red_cards.penalty;
+        ProbabilityDistribution::mixture(&[
+            (
+                1.0 - red_cards.rate,
+                &self.score_distribution(lambda_h, lambda_a),
+            ),
+            (
+                red_cards.rate / 2.0,
+                &self.score_distribution(lambda_h * remaining, lambda_a),
+            ),
+            (
+                red_cards.rate / 2.0,
+                &self.score_distribution(lambda_h, lambda_a * remaining),
+            ),
+        ])
+    }
+
+    /// The score model's distribution, before any red-card adjustment.
+    pub fn score_distribution(&self, lambda_h: f64, lambda_a: f64) -> ProbabilityDistribution {
+        match self.score_model {
+            ScoreModel::DixonColes => {
+                DixonColes::get_probability_matrix(lambda_h, lambda_a, self.rho, self.max_goals)
+            }
+            ScoreModel::Poisson => {
+                DixonColes::get_probability_matrix(lambda_h, lambda_a, 0.0, self.max_goals)
+            }
+            ScoreModel::NegativeBinomial { dispersion } => {
+                NegativeBinomial::score_matrix(lambda_h, lambda_a, dispersion, self.max_goals)
+            }
+            ScoreModel::ZeroInflated { zero_inflation } => ZeroInflatedDixonColes::score_matrix(
+                lambda_h,
+                lambda_a,
+                self.rho,
+                zero_inflation,
+                self.max_goals,
+            ),
+            ScoreModel::BivariatePoisson { lambda_3 } => {
+                // The shared term counts towards both sides, so the fixture lambdas stay the means.
+                debug_assert!(self.check_lambdas(lambda_h, lambda_a).is_ok());
+                BivariatePoissonModel::score_matrix(
+                    lambda_h - lambda_3,
+                    lambda_a - lambda_3,
+                    lambda_3,
+                    self.max_goals,
+                )
+            }
+        }
+    }
+
+    /// Rejects a bivariate Poisson `lambda_3` above either side's expected goals, red-card
+    /// penalty included, since the shared term is part of both means.
+    pub fn check_lambdas(&self, lambda_h: f64, lambda_a: f64) -> Result<(), String> {
+        let ScoreModel::BivariatePoisson { lambda_3 } = self.score_model else {
+            return Ok(());
+        };
+        let remaining = self
+            .red_cards
+            .map_or(1.0, |red_cards| 1.0 - red_cards.penalty);
+        let smallest = lambda_h.min(lambda_a) * remaining;
+        if lambda_3 > smallest {
+            return Err(format!(
+                "lambda_3 = {lambda_3} exceeds an expected goal count of {smallest:.3}; it must not be above either side's lambda"
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SimulationInput {
+    pub teams: Vec<String>,
+    pub initial_stats: Vec<Stats>,
+    pub fixtures: Vec<FixtureSimulation>,
+    pub tiebreakers: Vec<TiebreakerStage>,
+}
+
+impl SimulationInput {
+    /// Expected points each team collects from the simulated fixtures, exact from the score matrices.
+    pub fn expected_points(&self) -> Vec<f64> {
+        let mut totals = vec![0.0; self.teams.len()];
+        for fixture in &self.fixtures {
+            let (home, away) = fixture.distribution.expected_points();
+            totals[fixture.home_idx] += home;
+            totals[fixture.away_idx] += away;
+        }
+        totals
+    }
+
+    /// Subtract regulatory point deductions (`team -> points removed`) from the starting table.
+    pub fn apply_deductions(&mut self, deductions: &HashMap<String, i64>) -> Result<(), String> {
+        for (team, points) in deductions {
+            let idx = self
+                .teams
+                .iter()
+                .position(|name| name == team)
+                .ok_or_else(|| format!("Team {team} in deductions not found in standings"))?;
+            self.initial_stats[idx].points -= points;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct SeasonResult {
+    pub order: Vec<usize>,
+    pub final_stats: Vec<Stats>,
+    /// Simulated scores in fixture order; empty unless they were requested or needed for head-to-head.
+    pub results: Vec<MatchResult>,
+}
+
+pub struct DixonColes {}
+
+impl DixonColes {
+    pub fn correction_factor(x: i64, y: i64, lambda_x: f64, lambda_y: f64, rho: f64) -> f64 {
+        match (x, y) {
+            (0, 0) => 1.0 - lambda_x * lambda_y * rho,
+            (0, 1) => 1.0 + lambda_x * rho,
+            (1, 0) => 1.0 + lambda_y * rho,
+            (1, 1) => 1.0 - rho,
+            _ => 1.0,
+        }
+    }
+
+    pub fn poisson_pmf(k: i64, lambda: f64) -> f64 {
+        if k < 0 {
+            return 0.0;
+        }
+        if lambda <= 0.0 {
+            // A side with no expected goals always scores zero.
+            return if k == 0 { 1.0 } else { 0.0 };
+        }
+        let k_float = k as f64;
+        let log_lambda = lambda.ln();
+        (-lambda + k_float * log_lambda - log_factorial(k)).exp()
+    }
+
+    /// `poisson_pmf(k, lambda)` for every `k` in `0..=max_k`, from `P(k) = P(k - 1) * lambda / k`.
+    pub fn poisson_pmf_batch(lambda: f64, max_k: usize) -> Vec<f64> {
+        let mut pmf = Vec::with_capacity(max_k + 1);
+        let mut p = Self::poisson_pmf(0, lambda);
+        pmf.push(p);
+        for k in 1..=max_k {
+            p *= lambda.max(0.0) / k as f64;
+            pmf.push(p);
+        }
+        pmf
+    }
+
+    pub fn result_probability(x: i64, y: i64, lambda_x: f64, lambda_y: f64, rho: f64) -> f64 {
+        let p_x = Self::poisson_pmf(x, lambda_x);
+        let p_y = Self::poisson_pmf(y, lambda_y);
+        let tau = Self::correction_factor(x, y, lambda_x, lambda_y, rho);
+        (p_x * p_y * tau).max(0.0)
+    }
+
+    pub fn precompute_probability_matrix(
+        lambda_h: f64,
+        lambda_a: f64,
+        rho: f64,
+        max_goals: usize,
+    ) -> ProbabilityDistribution {
+        let home_pmf = Self::poisson_pmf_batch(lambda_h, max_goals);
+        let away_pmf = Self::poisson_pmf_batch(lambda_a, max_goals);
+        let mut flat_probs = Vec::with_capacity((max_goals + 1) * (max_goals + 1));
+        for (h, p_h) in home_pmf.iter().enumerate() {
+            for (a, p_a) in away_pmf.iter().enumerate() {
+                let tau = Self::correction_factor(h as i64, a as i64, lambda_h, lambda_a, rho);
+                flat_probs.push((p_h * p_a * tau).max(0.0));
+            }
+        }
+        ProbabilityDistribution::from_probabilities(&flat_probs, max_goals + 1)
+    }
+
+    pub fn get_probability_matrix(
+        lambda_h: f64,
+        lambda_a: f64,
+        rho: f64,
+        max_goals: usize,
+    ) -> ProbabilityDistribution {
+        let key = (
+            lambda_h.to_bits(),
+            lambda_a.to_bits(),
+            rho.to_bits(),
+            max_goals,
+        );
+        if let Some(distribution) = PROBABILITY_CACHE.get(&key) {
+            CACHE_HITS.fetch_add(1, AtomicOrdering::Relaxed);
+            return distribution;
+        }
+        CACHE_MISSES.fetch_add(1, AtomicOrdering::Relaxed);
+        let distribution = Self::precompute_probability_matrix(lambda_h, lambda_a, rho, max_goals);
+        PROBABILITY_CACHE.insert(key, distribution.clone());
+        distribution
+    }
+
+    /// Computes and caches the matrix of every distinct `(lambda_h, lambda_a)` pair not cached
+    /// yet, one at a time, and returns how many were added; later lookups for them are all hits.
+    pub fn warm_cache(
+        pairs: impl IntoIterator<Item = (f64, f64)>,
+        rho: f64,
+        max_goals: usize,
+    ) -> usize {
+        let mut inserted = 0;
+        for (lambda_h, lambda_a) in pairs {
+            let key = (
+                lambda_h.to_bits(),
+                lambda_a.to_bits(),
+                rho.to_bits(),
+                max_goals,
+            );
+            if PROBABILITY_CACHE.contains(&key) {
+                continue;
+            }
+            let distribution =
+                Self::precompute_probability_matrix(lambda_h, lambda_a, rho, max_goals);
+            if PROBABILITY_CACHE.insert(key, distribution) {
+                inserted += 1;
+            }
+        }
+        inserted
+    }
+
+    /// `warm_cache` on the Rayon pool, each distinct missing matrix computed by one worker.
+    pub fn warm_cache_parallel(
+        pairs: impl IntoIterator<Item = (f64, f64)>,
+        rho: f64,
+        max_goals: usize,
+    ) -> usize {
+        let mut seen = HashSet::new();
+        let missing: Vec<MatrixKey> = pairs
+            .into_iter()
+            .map(|(lambda_h, lambda_a)| {
+                (
+                    lambda_h.to_bits(),
+                    lambda_a.to_bits(),
+                    rho.to_bits(),
+                    max_goals,
+                )
+            })
+            .filter(|key| !PROBABILITY_CACHE.contains(key) && seen.insert(*key))
+            .collect();
+        missing
+            .into_par_iter()
+            .filter(|&key| {
+                let (lambda_h, lambda_a) = (f64::from_bits(key.0), f64::from_bits(key.1));
+                let distribution =
+                    Self::precompute_probability_matrix(lambda_h, lambda_a, rho, max_goals);
+                PROBABILITY_CACHE.insert(key, distribution)
+            })
+            .count()
+    }
+
+    pub fn simulate_from_distribution<R: Rng>(
+        rng: &mut R,
+        pd: &ProbabilityDistribution,
+    ) -> (i64, i64) {
+        Self::simulate_antithetic(rng, pd, false)
+    }
+
+    /// Like `simulate_from_distribution`; with `antithetic` the CDF is looked up at `1 - u`, so
+    /// a season replayed from the same seed draws the mirror-image scores.
+    pub fn simulate_antithetic<R: Rng>(
+        rng: &mut R,
+        pd: &ProbabilityDistribution,
+        antithetic: bool,
+    ) -> (i64, i64) {
+        let u: f64 = rng.gen();
+        Self::score_at(pd, if antithetic { 1.0 - u } else { u })
+    }
+
+    /// The score whose CDF interval contains `u`, for externally supplied uniforms.
+    pub fn score_at(pd: &ProbabilityDistribution, u: f64) -> (i64, i64) {
+        // A CDF search would land on the zero-width entries before the score at `u = 0`.
+        if let Some((home_goals, away_goals)) = pd.fixed {
+            return (home_goals as i64, away_goals as i64);
+        }
+        let idx = cdf_sample_simd(&pd.cdf, u);
+        ((idx / pd.dim) as i64, (idx % pd.dim) as i64)
+    }
+}
+
+/// Index of the first CDF entry not below `u`, clamped to the last entry. A score grid has only
+/// `(max_goals + 1)^2` entries, so with AVX2 a linear scan four entries per comparison beats the
+/// branchy binary search; other CPUs fall back to `cdf_sample_binary`.
+pub fn cdf_sample_simd(cdf: &[f64], u: f64) -> usize {
+    #[cfg(target_arch = "x86_64")]
+    if std::arch::is_x86_feature_detected!("avx2") {
+        // SAFETY: AVX2 support was just checked at runtime.
+        let idx = unsafe { cdf_scan_avx2(cdf, u) };
+        return idx.min(cdf.len().saturating_sub(1));
+    }
+    cdf_sample_binary(cdf, u)
+}
+
+/// Binary-search version of `cdf_sample_simd`. On a tie, e.g. `u = 1` against a tail of
+/// zero-probability scores, both take the first matching entry.
+pub fn cdf_sample_binary(cdf: &[f64], u: f64) -> usize {
+    cdf.partition_point(|&value| value < u)
+        .min(cdf.len().saturating_sub(1))
+}
+
+/// Count of leading entries below `u`, stopping at the first block of four that is not all
+/// below; relies on `cdf` being sorted.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+fn cdf_scan_avx2(cdf: &[f64], u: f64) -> usize {
+    use std::arch::x86_64::{
+        _mm256_cmp_pd, _mm256_loadu_pd, _mm256_movemask_pd, _mm256_set1_pd, _CMP_LT_OQ,
+    };
+
+    let target = _mm256_set1_pd(u);
+    let blocks = cdf.chunks_exact(4);
+    let tail = blocks.remainder();
+    for (block_idx, block) in blocks.enumerate() {
+        // SAFETY: `block` holds exactly four contiguous f64s; the load is unaligned.
+        let values = unsafe { _mm256_loadu_pd(block.as_ptr()) };
+        let below = _mm256_movemask_pd(_mm256_cmp_pd::<_CMP_LT_OQ>(values, target)) as u32;
+        if below != 0b1111 {
+            return block_idx * 4 + below.trailing_ones() as usize;
+        }
+    }
+    cdf.len() - tail.len() + tail.iter().take_while(|&&value| value < u).count()
+}
+
+/// Gamma-Poisson mixture goals, for leagues whose scoring is overdispersed.
+pub struct NegativeBinomial {}
+
+impl NegativeBinomial {
+    /// `P(0..=max_goals)` with mean `lambda`, from the pmf recurrence.
+    pub fn pmf(lambda: f64, dispersion: f64, max_goals: usize) -> Vec<f64> {
+        let success = dispersion / (dispersion + lambda);
+        let mut pmf = Vec::with_capacity(max_goals + 1);
+        let mut probability = success.powf(dispersion);
+        for k in 0..=max_goals {
+            pmf.push(probability);
+            probability *= (k as f64 + dispersion) / (k as f64 + 1.0) * (1.0 - success);
+        }
+        pmf
+    }
+
+    pub fn score_matrix(
+        lambda_h: f64,
+        lambda_a: f64,
+        dispersion: f64,
+        max_goals: usize,
+    ) -> ProbabilityDistribution {
+        let home = Self::pmf(lambda_h, dispersion, max_goals);
+        let away = Self::pmf(lambda_a, dispersion, max_goals);
+        let flat_probs: Vec<f64> = home
+            .iter()
+            .flat_map(|p_home| away.iter().map(move |p_away| p_home * p_away))
+            .collect();
+        ProbabilityDistribution::from_probabilities(&flat_probs, max_goals + 1)
+    }
+
+    /// Goals with mean `lambda` and variance `lambda + lambda^2 / dispersion`: a Poisson draw
+    /// whose rate is itself drawn from `Gamma(dispersion, lambda / dispersion)`.
+    pub fn sample_goals<R: Rng>(rng: &mut R, lambda: f64, dispersion: f64) -> i64 {
+        let rate =
+            Gamma::new(dispersion, lambda / dispersion).map_or(lambda, |gamma| gamma.sample(rng));
+        match Poisson::new(rate) {
+            Ok(poisson) => poisson.sample(rng) as i64,
+            // A zero rate scores nothing.
+            Err(_) => 0,
+        }
+    }
+
+    /// One score sampled directly rather than from the capped `score_matrix`.
+    pub fn simulate_match_nb<R: Rng>(
+        rng: &mut R,
+        lambda_h: f64,
+        lambda_a: f64,
+        dispersion: f64,
+    ) -> (i64, i64) {
+        (
+            Self::sample_goals(rng, lambda_h, dispersion),
+            Self::sample_goals(rng, lambda_a, dispersion),
+        )
+    }
+}
+
+/// Dixon-Coles with extra goalless draws, for leagues where 0-0 beats the Poisson rate.
+pub struct ZeroInflatedDixonColes {}
+
+impl ZeroInflatedDixonColes {
+    /// Adds `zero_inflation` to the 0-0 cell, then renormalizes so the other scores shrink
+    /// proportionally.
+    pub fn score_matrix(
+        lambda_h: f64,
+        lambda_a: f64,
+        rho: f64,
+        zero_inflation: f64,
+        max_goals: usize,
+    ) -> ProbabilityDistribution {
+        let base = DixonColes::get_probability_matrix(lambda_h, lambda_a, rho, max_goals);
+        let mut flat_probs: Vec<f64> = base.probabilities().collect();
+        flat_probs[0] += zero_inflation / (1.0 - zero_inflation);
+        ProbabilityDistribution::from_probabilities(&flat_probs, base.dim)
+    }
+}
+
+/// `X = X0 + Z`, `Y = Y0 + Z` with independent `X0 ~ Poisson(lambda_1)`,
+/// `Y0 ~ Poisson(lambda_2)` and `Z ~ Poisson(lambda_3)`.
+pub struct BivariatePoissonModel;
+
+impl BivariatePoissonModel {
+    pub fn probability(x: usize, y: usize, lambda_1: f64, lambda_2: f64, lambda_3: f64) -> f64 {
+        (0..=x.min(y))
+            .map(|shared| {
+                DixonColes::poisson_pmf((x - shared) as i64, lambda_1)
+                    * DixonColes::poisson_pmf((y - shared) as i64, lambda_2)
+                    * DixonColes::poisson_pmf(shared as i64, lambda_3)
+            })
+            .sum()
+    }
+
+    pub fn score_matrix(
+        lambda_1: f64,
+        lambda_2: f64,
+        lambda_3: f64,
+        max_goals: usize,
+    ) -> ProbabilityDistribution {
+        let flat_probs: Vec<f64> = (0..=max_goals)
+            .flat_map(|x| {
+                (0..=max_goals).map(move |y| Self::probability(x, y, lambda_1, lambda_2, lambda_3))
+            })
+            .collect();
+        ProbabilityDistribution::from_probabilities(&flat_probs, max_goals + 1)
+    }
+
+    /// One score sampled directly as `(X0 + Z, Y0 + Z)`, uncapped by `max_goals`.
+    pub fn simulate_match<R: Rng>(
+        rng: &mut R,
+        lambda_1: f64,
+        lambda_2: f64,
+        lambda_3: f64,
+    ) -> (i64, i64) {
+        let mut goals = |lambda: f64| match Poisson::new(lambda) {
+            Ok(poisson) => poisson.sample(rng) as i64,
+            Err(_) => 0,
+        };
+        let shared = goals(lambda_3);
+        (goals(lambda_1) + shared, goals(lambda_2) + shared)
+    }
+}
+
+/// Cumulative score-matrix probabilities, row-major over `(home_goals, away_goals)`.
+#[derive(Debug, Clone)]
+pub struct ProbabilityDistribution {
+    pub(crate) cdf: Vec<f64>,
+    pub(crate) dim: usize,
+    /// The only possible score of a `fixed` distribution, which sampling returns as is.
+    pub(crate) fixed: Option<(usize, usize)>,
+}
+
+impl ProbabilityDistribution {
+    /// Normalizes row-major `(home, away)` score probabilities into a sampling CDF.
+    pub fn from_probabilities(probabilities: &[f64], dim: usize) -> Self {
+        let total: f64 = probabilities.iter().sum();
+        let mut cdf = Vec::with_capacity(probabilities.len());
+        let mut acc = 0.0;
+        for prob in probabilities {
+            acc += prob / total.max(f64::EPSILON);
+            cdf.push(acc);
+        }
+        if let Some(last) = cdf.last_mut() {
+            *last = 1.0;
+        }
+        ProbabilityDistribution {
+            cdf,
+            dim,
+            fixed: None,
+        }
+    }
+
+    /// Weighted blend of distributions over the same score grid; weights should sum to 1.
+    pub fn mixture(components: &[(f64, &ProbabilityDistribution)]) -> Self {
+        let dim = components.first().map_or(0, |(_, component)| component.dim);
+        let mut cdf = vec![0.0; dim * dim];
+        for (weight, component) in components {
+            debug_assert_eq!(component.dim, dim);
+            for (total, value) in cdf.iter_mut().zip(&component.cdf) {
+                *total += weight * value;
+            }
+        }
+        if let Some(last) = cdf.last_mut() {
+            *last = 1.0;
+        }
+        ProbabilityDistribution {
+            cdf,
+            dim,
+            fixed: None,
+        }
+    }
+
+    /// A point mass on one score, e.g. an awarded walkover. Sampling it still consumes a
+    /// uniform draw, so the other fixtures see the same random stream either way.
+    pub fn fixed(home_goals: usize, away_goals: usize, max_goals: usize) -> Self {
+        let dim = max_goals.max(home_goals).max(away_goals) + 1;
+        let target = home_goals * dim + away_goals;
+        let cdf = (0..dim * dim)
+            .map(|idx| if idx >= target { 1.0 } else { 0.0 })
+            .collect();
+        ProbabilityDistribution {
+            cdf,
+            dim,
+            fixed: Some((home_goals, away_goals)),
+        }
+    }
+
+    /// Point probabilities of each flattened `(home, away)` score, recovered from the CDF.
+    pub fn probabilities(&self) -> impl Iterator<Item = f64> + '_ {
+        self.cdf.iter().scan(0.0, |previous, &value| {
+            let probability = value - *previous;
+            *previous = value;
+            Some(probability)
+        })
+    }
+
+    /// Probability of one score; zero outside the matrix.
+    pub fn probability(&self, home_goals: usize, away_goals: usize) -> f64 {
+        if home_goals >= self.dim || away_goals >= self.dim {
+            return 0.0;
+        }
+        let idx = home_goals * self.dim + away_goals;
+        let previous = if idx == 0 { 0.0 } else { self.cdf[idx - 1] };
+        self.cdf[idx] - previous
+    }
+
+    /// Home and away goal distributions, the row and column sums of the score matrix.
+    pub fn goal_marginals(&self) -> (Vec<f64>, Vec<f64>) {
+        let (mut home, mut away) = (vec![0.0; self.dim], vec![0.0; self.dim]);
+        for ((home_goals, away_goals), probability) in self.scorelines() {
+            home[home_goals] += probability;
+            away[away_goals] += probability;
+        }
+        (home, away)
+    }
+
+    /// `((home_goals, away_goals), probability)` for every score in the matrix.
+    pub fn scorelines(&self) -> impl Iterator<Item = ((usize, usize), f64)> + '_ {
+        self.probabilities()
+            .enumerate()
+            .map(|(idx, probability)| ((idx / self.dim, idx % self.dim), probability))
+    }
+
+    /// `(over, under, push)` for a total-goals line; only whole-number lines can push.
+    pub fn total_goals_split(&self, line: f64) -> (f64, f64, f64) {
+        let (mut over, mut under, mut push) = (0.0, 0.0, 0.0);
+        for ((home, away), probability) in self.scorelines() {
+            match ((home + away) as f64).partial_cmp(&line) {
+                Some(Ordering::Greater) => over += probability,
+                Some(Ordering::Less) => under += probability,
+                _ => push += probability,
+            }
+        }
+        (over, under, push)
+    }
+
+    /// `(p_home, p_away)` with `handicap` goals added to the home score; pushes are split
+    /// evenly and quarter lines average the two neighbouring half-goal lines.
+    pub fn asian_handicap_split(&self, handicap: f64) -> (f64, f64) {
+        let quarters = handicap * 4.0;
+        if quarters.rem_euclid(2.0) == 1.0 {
+            let (low_home, low_away) = self.asian_handicap_split(handicap - 0.25);
+            let (high_home, high_away) = self.asian_handicap_split(handicap + 0.25);
+            return ((low_home + high_home) / 2.0, (low_away + high_away) / 2.0);
+        }
+        let (mut home_side, mut away_side) = (0.0, 0.0);
+        for ((home, away), probability) in self.scorelines() {
+            match (home as f64 + handicap).partial_cmp(&(away as f64)) {
+                Some(Ordering::Greater) => home_side += probability,
+                Some(Ordering::Less) => away_side += probability,
+                _ => {
+                    home_side += probability / 2.0;
+                    away_side += probability / 2.0;
+                }
+            }
+        }
+        (home_side, away_side)
+    }
+
+    pub fn both_teams_score(&self) -> f64 {
+        self.scorelines()
+            .filter(|&((home, away), _)| home >= 1 && away >= 1)
+            .map(|(_, probability)| probability)
+            .sum()
+    }
+
+    /// `(home keeps a clean sheet, away keeps a clean sheet)`.
+    pub fn clean_sheets(&self) -> (f64, f64) {
+        let (mut home, mut away) = (0.0, 0.0);
+        for ((home_goals, away_goals), probability) in self.scorelines() {
+            if away_goals == 0 {
+                home += probability;
+            }
+            if home_goals == 0 {
+                away += probability;
+            }
+        }
+        (home, away)
+    }
+
+    pub fn outcome_probabilities(&self) -> (f64, f64, f64) {
+        let (mut home, mut draw, mut away) = (0.0, 0.0, 0.0);
+        for (idx, probability) in self.probabilities().enumerate() {
+            match (idx / self.dim).cmp(&(idx % self.dim)) {
+                Ordering::Greater => home += probability,
+                Ordering::Equal => draw += probability,
+                Ordering::Less => away += probability,
+            }
+        }
+        (home, draw, away)
+    }
+
+    /// `(xpts_home, xpts_away)`: three points per win and one per draw, in expectation.
+    pub fn expected_points(&self) -> (f64, f64) {
+        let (home, draw, away) = self.outcome_probabilities();
+        (3.0 * home + draw, 3.0 * away + draw)
+    }
+}
+
+pub fn match_outcome_probs(
+    lambda_h: f64,
+    lambda_a: f64,
+    rho: f64,
+    max_goals: usize,
+) -> (f64, f64, f64) {
+    DixonColes::get_probability_matrix(lambda_h, lambda_a, rho, max_goals).outcome_probabilities()
+}
+
+pub type TeamIndex = HashMap<String, usize>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParsedFixture {
+    pub home_idx: usize,
+    pub away_idx: usize,
+    /// Played at a neutral ground, so the listed home side gets no home advantage.
+    pub neutral: bool,
+    /// Score awarded without playing (walkover), applied instead of simulating.
+    pub result: Option<(usize, usize)>,
+}
+
+/// One remaining fixture as the Python and JSON entry points read it, before its teams are
+/// looked up: SofaScore `{"h", "a"}` objects with optional `"neutral"` and `"result"`, or plain
+/// `(home, away)` pairs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixtureSpec {
+    pub home: String,
+    pub away: String,
+    pub neutral: bool,
+    pub result: Option<(i64, i64)>,
+}
+
+impl FixtureSpec {
+    pub fn new(home: &str, away: &str) -> Self {
+        FixtureSpec {
+            home: home.to_string(),
+            away: away.to_string(),
+            neutral: false,
+            result: None,
+        }
+    }
+
+    /// The error for a `"result"` that is not a `(home, away)` pair of goal counts.
+    pub fn result_shape_error(&self) -> String {
+        format!(
+            "Fixture {} vs {} result must be a (home, away) pair",
+            self.home, self.away
+        )
+    }
+
+    pub fn resolve(&self, team_to_idx: &TeamIndex) -> Result<ParsedFixture, String> {
+        let idx = |team: &str| {
+            team_to_idx
+                .get(team)
+                .copied()
+                .ok_or_else(|| format!("Team {team} not found in standings"))
+        };
+        let (home_idx, away_idx) = (idx(&self.home)?, idx(&self.away)?);
+        let result = match self.result {
+            Some((home_goals, away_goals)) if home_goals < 0 || away_goals < 0 => {
+                return Err(format!(
+                    "Fixture {} vs {} result cannot be negative",
+                    self.home, self.away
+                ));
+            }
+            Some((home_goals, away_goals)) => Some((home_goals as usize, away_goals as usize)),
+            None => None,
+        };
+        Ok(ParsedFixture {
+            home_idx,
+            away_idx,
+            neutral: self.neutral,
+            result,
+        })
+    }
+}
+
+/// Pins the listed fixtures (by index) to a given `(home_goals, away_goals)` score, so only the
+/// rest are simulated.
+pub fn fix_results(
+    fixtures: &mut [ParsedFixture],
+    fixed_results: &HashMap<usize, (usize, usize)>,
+) -> Result<(), String> {
+    for (&idx, &score) in fixed_results {
+        let count = fixtures.len();
+        let fixture = fixtures.get_mut(idx).ok_or_else(|| {
+            format!("fixed_results index {idx} is out of range for {count} fixtures")
+        })?;
+        fixture.result = Some(score);
+    }
+    Ok(())
+}
+
+/// Per-team attack/defense ratings relative to the league scoring rate.
+#[derive(Debug, Clone)]
+pub struct LeagueRatings {
+    pub avg_league_goals: f64,
+    pub home_advantage: f64,
+    pub home_attack: Vec<f64>,
+    pub home_defense: Vec<f64>,
+    pub away_attack: Vec<f64>,
+    pub away_defense: Vec<f64>,
+}
+
+/// Fixture congestion: a side that already played in the `window` days before a fixture scores
+/// at `factor` times its usual rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fatigue {
+    pub window: usize,
+    pub factor: f64,
+}
+
+impl Fatigue {
+    /// `(home tired, away tired)` per fixture, given each fixture's day of the season. Awarded
+    /// results were never played, so they tire nobody.
+    pub fn tired_sides(&self, fixtures: &[ParsedFixture], dates: &[i64]) -> Vec<(bool, bool)> {
+        let played_recently = |team: usize, day: i64| {
+            fixtures.iter().zip(dates).any(|(other, &other_day)| {
+                other.result.is_none()
+                    && (other.home_idx == team || other.away_idx == team)
+                    && other_day < day
+                    && day - other_day <= self.window as i64
+            })
+        };
+        fixtures
+            .iter()
+            .zip(dates)
+            .map(|(fixture, &day)| {
+                (
+                    played_recently(fixture.home_idx, day),
+                    played_recently(fixture.away_idx, day),
+                )
+            })
+            .collect()
+    }
+}
+
+impl LeagueRatings {
+    pub fn fixture_simulation(
+        &self,
+        fixture: &ParsedFixture,
+        config: &ModelConfig,
+    ) -> FixtureSimulation {
+        self.scaled_fixture_simulation(fixture, config, (1.0, 1.0))
+    }
+
+    /// Like `fixture_simulation`, with the home and away expected goals multiplied by `scale`.
+    pub fn scaled_fixture_simulation(
+        &self,
+        fixture: &ParsedFixture,
+        config: &ModelConfig,
+        scale: (f64, f64),
+    ) -> FixtureSimulation {
+        let (home_idx, away_idx) = (fixture.home_idx, fixture.away_idx);
+        if let Some((home_goals, away_goals)) = fixture.result {
+            return FixtureSimulation {
+                home_idx,
+                away_idx,
+                distribution: ProbabilityDistribution::fixed(
+                    home_goals,
+                    away_goals,
+                    config.max_goals,
+                ),
+            };
+        }
+        let (lambda_h, lambda_a) = self.lambdas(fixture);
+        FixtureSimulation {
+            home_idx,
+            away_idx,
+            distribution: config.distribution(lambda_h * scale.0, lambda_a * scale.1),
+        }
+    }
+}
+
+impl LeagueRatings {
+    /// Dixon-Coles `(attack, defense)` strengths shared by home and away games, so that
+    /// `lambda_h = attack_h * defense_a * mu * delta`.
+    pub fn from_strengths(
+        avg_league_goals: f64,
+        home_advantage: f64,
+        strengths: &[(f64, f64)],
+    ) -> Self {
+        let (attack, defense): (Vec<f64>, Vec<f64>) = strengths.iter().copied().unzip();
+        LeagueRatings {
+            avg_league_goals,
+            home_advantage,
+            home_attack: attack.clone(),
+            home_defense: defense.clone(),
+            away_attack: attack,
+            away_defense: defense,
+        }
+    }
+
+    pub fn lambdas(&self, fixture: &ParsedFixture) -> (f64, f64) {
+        let home_advantage = if fixture.neutral {
+            1.0
+        } else {
+            self.home_advantage
+        };
+        calculate_lambdas(
+            self.avg_league_goals,
+            (
+                self.home_attack[fixture.home_idx],
+                self.home_defense[fixture.home_idx],
+            ),
+            (
+                self.away_attack[fixture.away_idx],
+                self.away_defense[fixture.away_idx],
+            ),
+            home_advantage,
+        )
+    }
+
+    /// Replaces home and away ratings with `(scored, conceded)` per-match form rates where known.
+    pub fn apply_form(&mut self, form_rates: &[Option<(f64, f64)>]) {
+        if self.avg_league_goals <= 0.0 {
+            return;
+        }
+        for (idx, rates) in form_rates.iter().enumerate() {
+            if let Some((scored, conceded)) = *rates {
+                let attack = scored / self.avg_league_goals;
+                let defense = conceded / self.avg_league_goals;
+                self.home_attack[idx] = attack;
+                self.away_attack[idx] = attack;
+                self.home_defense[idx] = defense;
+                self.away_defense[idx] = defense;
+            }
+        }
+    }
+}
+
+/// Expected goals for both sides from `(attack, defense)` ratings relative to the league average.
+pub fn calculate_lambdas(
+    avg_league_goals: f64,
+    home_ratings: (f64, f64),
+    away_ratings: (f64, f64),
+    home_advantage: f64,
+) -> (f64, f64) {
+    let (home_attack, home_defense) = home_ratings;
+    let (away_attack, away_defense) = away_ratings;
+    let lambda_h = avg_league_goals * home_attack * away_defense * home_advantage;
+    let lambda_a = avg_league_goals * away_attack * home_defense;
+    (lambda_h, lambda_a)
+}
+
+/// Goals per team per match across the league, or `DEFAULT_LAMBDA` before any games.
+pub fn league_goal_rate(stats: &[Stats]) -> f64 {
+    let total_gf: i64 = stats.iter().map(|stats| stats.goals_for).sum();
+    let total_matches: i64 = stats.iter().map(|stats| stats.matches).sum();
+    if total_matches > 0 {
+        total_gf as f64 / total_matches as f64
+    } else {
+        DEFAULT_LAMBDA
+    }
+}
+
+/// Home goals over away goals, clamped to `[1.0, 1.5]`, or `HOME_ADVANTAGE` before any away goal.
+pub fn estimated_home_advantage(home_goals: i64, away_goals: i64) -> f64 {
+    if away_goals > 0 {
+        (home_goals as f64 / away_goals as f64).clamp(1.0, 1.5)
+    } else {
+        HOME_ADVANTAGE
+    }
+}
+
+/// Standings and ratings parsed from the tables, before any fixtures are attached.
+#[derive(Debug, Clone)]
+pub struct League {
+    pub teams: Vec<String>,
+    pub initial_stats: Vec<Stats>,
+    pub team_to_idx: TeamIndex,
+    pub ratings: LeagueRatings,
+}
+
+/// One problem found by `validate_fixtures`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixtureProblem {
+    pub error_type: &'static str,
+    pub fixture_idx: usize,
+    pub message: String,
+}
+
+/// Every problem in a list of `(fixture_idx, (home, away))` fixtures: teams missing from
+/// `team_to_idx`, repeated fixtures, teams playing themselves and teams with more than one home
+/// fixture more than away ones, or vice versa. An imbalance is reported at the team's last
+/// fixture.
+pub fn fixture_problems(
+    fixtures: &[(usize, (String, String))],
+    team_to_idx: &TeamIndex,
+) -> Vec<FixtureProblem> {
+    let mut problems = Vec::new();
+    let mut first_seen: HashMap<(&str, &str), usize> = HashMap::new();
+    // (home, away, last fixture) appearances of each team.
+    let mut venues: HashMap<&str, (usize, usize, usize)> = HashMap::new();
+    for &(fixture_idx, (ref home, ref away)) in fixtures {
+        for team in [home, away] {
+            if !team_to_idx.contains_key(team) {
+                problems.push(FixtureProblem {
+                    error_type: "unknown_team",
+                    fixture_idx,
+                    message: format!("Team {team} not found in standings"),
+                });
+            }
+        }
+        if home == away {
+            problems.push(FixtureProblem {
+                error_type: "self_play",
+                fixture_idx,
+                message: format!("{home} cannot play itself"),
+            });
+        }
+        match first_seen.get(&(home.as_str(), away.as_str())) {
+            Some(first) => problems.push(FixtureProblem {
+                error_type: "duplicate_fixture",
+                fixture_idx,
+                message: format!("{home} vs {away} is already fixture {first}"),
+            }),
+            None => {
+                first_seen.insert((home, away), fixture_idx);
+            }
+        }
+        let home_venues = venues.entry(home).or_default();
+        home_venues.0 += 1;
+        home_venues.2 = fixture_idx;
+        let away_venues = venues.entry(away).or_default();
+        away_venues.1 += 1;
+        away_venues.2 = fixture_idx;
+    }
+
+    let mut imbalanced: Vec<_> = venues
+        .into_iter()
+        .filter(|(_, (home, away, _))| home.abs_diff(*away) > 1)
+        .collect();
+    imbalanced.sort_by_key(|&(team, (_, _, last))| (last, team));
+    problems.extend(
+        imbalanced
+            .into_iter()
+            .map(|(team, (home, away, fixture_idx))| FixtureProblem {
+                error_type: "unbalanced_venues",
+                fixture_idx,
+                message: format!("{team} has {home} home and {away} away fixtures"),
+            }),
+    );
+    problems
+}
+
+/// Adds the `(home_goals, away_goals)` result of `home` against `away` to both rows of `stats`.
+pub fn record_played_match(
+    stats: &mut [Stats],
+    team_to_idx: &TeamIndex,
+    (home, away): (&str, &str),
+    (home_goals, away_goals): (i64, i64),
+) -> Result<(), String> {
+    if home_goals < 0 || away_goals < 0 {
+        return Err("Goals cannot be negative".to_string());
+    }
+    let idx = |team: &str| {
+        team_to_idx
+            .get(team)
+            .copied()
+            .ok_or_else(|| format!("Team {team} not found in standings"))
+    };
+    let (home_idx, away_idx) = (idx(home)?, idx(away)?);
+    if home_idx == away_idx {
+        return Err(format!("{home} cannot play itself"));
+    }
+    stats[home_idx].record_result(home_goals, away_goals);
+    stats[away_idx].record_result(away_goals, home_goals);
+    Ok(())
+}
+
+impl League {
+    /// Rates every team of `table` from its `home` and `away` records (matches and goals).
+    /// Teams missing from a venue table keep neutral ratings there.
+    pub fn new(
+        table: Vec<(String, Stats)>,
+        home: &[(String, Stats)],
+        away: &[(String, Stats)],
+        config: &ModelConfig,
+    ) -> Result<Self, String> {
+        let num_teams = table.len();
+        let mut teams = Vec::with_capacity(num_teams);
+        let mut initial_stats = Vec::with_capacity(num_teams);
+        let mut team_to_idx = TeamIndex::new();
+        for (team, stats) in table {
+            team_to_idx.insert(team.clone(), teams.len());
+            teams.push(team);
+            initial_stats.push(stats);
+        }
+        let venue_stats = |rows: &[(String, Stats)], venue: &str| {
+            let mut stats = vec![Stats::default(); num_teams];
+            for (team, record) in rows {
+                let idx = *team_to_idx.get(team).ok_or_else(|| {
+                    format!("Team {team} found in {venue} table but not base table")
+                })?;
+                stats[idx] = *record;
+            }
+            Ok::<_, String>(stats)
+        };
+        let home_stats = venue_stats(home, "home")?;
+        let away_stats = venue_stats(away, "away")?;
+
+        let avg_league_goals = league_goal_rate(&initial_stats);
+
+        let home_total_gf: i64 = home_stats.iter().map(|stats| stats.goals_for).sum();
+        let away_total_gf: i64 = away_stats.iter().map(|stats| stats.goals_for).sum();
+        let home_advantage = config
+            .home_advantage
+            .unwrap_or_else(|| estimated_home_advantage(home_total_gf, away_total_gf));
+
+        let rates = |stats: &Stats| {
+            if stats.matches > 0 && avg_league_goals > 0.0 {
+                let matches = stats.matches as f64;
+                (
+                    (stats.goals_for as f64 / matches) / avg_league_goals,
+                    (stats.goals_against as f64 / matches) / avg_league_goals,
+                )
+            } else {
+                (1.0, 1.0)
+            }
+        };
+        let (home_attack, home_defense) = home_stats.iter().map(rates).unzip();
+        let (away_attack, away_defense) = away_stats.iter().map(rates).unzip();
+
+        Ok(League {
+            teams,
+            initial_stats,
+            team_to_idx,
+            ratings: LeagueRatings {
+                avg_league_goals,
+                home_advantage,
+                home_attack,
+                home_defense,
+                away_attack,
+                away_defense,
+            },
+        })
+    }
+
+    /// A regular (non-neutral, unplayed) fixture between two teams of the table.
+    pub fn fixture(&self, home: &str, away: &str) -> Result<ParsedFixture, String> {
+        FixtureSpec::new(home, away).resolve(&self.team_to_idx)
+    }
+
+    /// Adds a played match to both sides' standings. Ratings stay as they were rated from the
+    /// tables.
+    pub fn record_result(
+        &mut self,
+        home: &str,
+        away: &str,
+        home_goals: i64,
+        away_goals: i64,
+    ) -> Result<(), String> {
+        record_played_match(
+            &mut self.initial_stats,
+            &self.team_to_idx,
+            (home, away),
+            (home_goals, away_goals),
+        )
+    }
+
+    /// Checks every unplayed fixture's expected goals with `ModelConfig::check_lambdas`.
+    pub fn check_score_model(
+        &self,
+        fixtures: &[ParsedFixture],
+        config: &ModelConfig,
+    ) -> Result<(), String> {
+        self.check_scaled_score_model(fixtures.iter().map(|fixture| (fixture, (1.0, 1.0))), config)
+    }
+
+    fn check_scaled_score_model<'a>(
+        &self,
+        fixtures: impl IntoIterator<Item = (&'a ParsedFixture, (f64, f64))>,
+        config: &ModelConfig,
+    ) -> Result<(), String> {
+        for (fixture, scale) in fixtures {
+            if fixture.result.is_some() {
+                continue;
+            }
+            let (lambda_h, lambda_a) = self.ratings.lambdas(fixture);
+            config
+                .check_lambdas(lambda_h * scale.0, lambda_a * scale.1)
+                .map_err(|err| {
+                    format!(
+                        "{} vs {}: {err}",
+                        self.teams[fixture.home_idx], self.teams[fixture.away_idx]
+                    )
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Attaches the remaining fixtures, each priced under `config`.
+    pub fn simulation_input(
+        self,
+        fixtures: &[ParsedFixture],
+        config: &ModelConfig,
+    ) -> SimulationInput {
+        let fixtures = fixtures
+            .iter()
+            .map(|fixture| self.ratings.fixture_simulation(fixture, config))
+            .collect();
+        SimulationInput {
+            teams: self.teams,
+            initial_stats: self.initial_stats,
+            fixtures,
+            tiebreakers: Vec::new(),
+        }
+    }
+
+    /// Like `simulation_input`, lowering the scoring rate of sides tired from an earlier
+    /// fixture; `dates` gives each fixture's day of the season.
+    pub fn fatigued_simulation_input(
+        self,
+        fixtures: &[ParsedFixture],
+        dates: &[i64],
+        fatigue: &Fatigue,
+        config: &ModelConfig,
+    ) -> Result<SimulationInput, String> {
+        if dates.len() != fixtures.len() {
+            return Err(format!(
+                "fixture_dates has {} entries for {} fixtures",
+                dates.len(),
+                fixtures.len()
+            ));
+        }
+        let scale = |tired: bool| if tired { fatigue.factor } else { 1.0 };
+        let tired = fatigue.tired_sides(fixtures, dates);
+        self.check_scaled_score_model(
+            fixtures
+                .iter()
+                .zip(&tired)
+                .map(|(fixture, &(home, away))| (fixture, (scale(home), scale(away)))),
+            config,
+        )?;
+        let fixtures = fixtures
+            .iter()
+            .zip(tired)
+            .map(|(fixture, (home, away))| {
+                self.ratings
+                    .scaled_fixture_simulation(fixture, config, (scale(home), scale(away)))
+            })
+            .collect();
+        Ok(SimulationInput {
+            teams: self.teams,
+            initial_stats: self.initial_stats,
+            fixtures,
+            tiebreakers: Vec::new(),
+        })
+    }
+}
+
+/// Seeded runs replay exactly; without a seed every call draws fresh entropy.
+pub fn season_rng(seed: Option<u64>) -> ChaCha8Rng {
+    match seed {
+        Some(value) => ChaCha8Rng::seed_from_u64(value),
+        None => ChaCha8Rng::from_entropy(),
+    }
+}
+
+pub fn simulate_single_season<R: Rng>(input: &SimulationInput, rng: &mut R) -> SeasonResult {
+    simulate_season_recording(input, rng, false)
+}
+
+pub fn simulate_season_recording<R: Rng>(
+    input: &SimulationInput,
+    rng: &mut R,
+    record_results: bool,
+) -> SeasonResult {
+    simulate_season_antithetic(input, rng, record_results, false)
+}
+
+/// `simulate_season_recording` with every score drawn through `DixonColes::simulate_antithetic`.
+pub fn simulate_season_antithetic<R: Rng>(
+    input: &SimulationInput,
+    rng: &mut R,
+    record_results: bool,
+    antithetic: bool,
+) -> SeasonResult {
+    simulate_season_scored(input, rng, record_results, |_, distribution, rng| {
+        DixonColes::simulate_antithetic(rng, distribution, antithetic)
+    })
+}
+
+/// Plays a season with `score(fixture_idx, distribution, rng)` drawing each fixture's score;
+/// `rng` is also what random tiebreakers draw from.
+pub fn simulate_season_scored<R: Rng>(
+    input: &SimulationInput,
+    rng: &mut R,
+    record_results: bool,
+    mut score: impl FnMut(usize, &ProbabilityDistribution, &mut R) -> (i64, i64),
+) -> SeasonResult {
+    let num_teams = input.teams.len();
+    let mut standings = input.initial_stats.clone();
+    let track_results =
+        record_results || input.tiebreakers.iter().any(TiebreakerStage::needs_results);
+    let mut results = Vec::with_capacity(if track_results {
+        input.fixtures.len()
+    } else {
+        0
+    });
+
+    for (fixture_idx, fixture) in input.fixtures.iter().enumerate() {
+        let home_idx = fixture.home_idx;
+        let away_idx = fixture.away_idx;
+        let (gh, ga) = score(fixture_idx, &fixture.distribution, rng);
+        standings[home_idx].record_result(gh, ga);
+        standings[away_idx].record_result(ga, gh);
+        if track_results {
+            results.push(MatchResult {
+                home_idx,
+                away_idx,
+                home_goals: gh,
+                away_goals: ga,
+            });
+        }
+    }
+
+    let mut order: Vec<usize> = (0..num_teams).collect();
+    if input.tiebreakers.is_empty() {
+        sort_standings(&mut order, &input.teams, &standings);
+    } else {
+        let table = TableView {
+            teams: &input.teams,
+            standings: &standings,
+            results: &results,
+        };
+        apply_multistage_tiebreaker(&mut order, &table, &input.tiebreakers, rng);
+    }
+
+    SeasonResult {
+        order,
+        final_stats: standings,
+        results,
+    }
+}
+
+/// Plays `pre_split`, splits the table into its top `top_n` and the rest, then plays one more
+/// game between every pair inside each half (the higher-placed side at home). Teams stay in
+/// their half for the final order, even if a bottom-half side passes a top-half one on points.
+pub fn simulate_split_season<R: Rng>(
+    pre_split: &SimulationInput,
+    ratings: &LeagueRatings,
+    config: &ModelConfig,
+    top_n: usize,
+    rng: &mut R,
+) -> SeasonResult {
+    let first_phase = simulate_single_season(pre_split, rng);
+    let halves = first_phase
+        .order
+        .split_at(top_n.min(first_phase.order.len()));
+    let mut fixtures = Vec::new();
+    for half in [halves.0, halves.1] {
+        for (position, &home_idx) in half.iter().enumerate() {
+            for &away_idx in &half[position + 1..] {
+                let fixture = ParsedFixture {
+                    home_idx,
+                    away_idx,
+                    neutral: false,
+                    result: None,
+                };
+                fixtures.push(ratings.fixture_simulation(&fixture, config));
+            }
+        }
+    }
+    let post_split = SimulationInput {
+        teams: pre_split.teams.clone(),
+        initial_stats: first_phase.final_stats,
+        fixtures,
+        tiebreakers: pre_split.tiebreakers.clone(),
+    };
+    let second_phase = simulate_single_season(&post_split, rng);
+
+    let mut in_top = vec![false; pre_split.teams.len()];
+    for &team_idx in halves.0 {
+        in_top[team_idx] = true;
+    }
+    let (top, bottom): (Vec<usize>, Vec<usize>) =
+        second_phase.order.iter().partition(|&&team| in_top[team]);
+    SeasonResult {
+        order: top.into_iter().chain(bottom).collect(),
+        final_stats: second_phase.final_stats,
+        results: Vec::new(),
+    }
+}
+
+/// The default ranking: points, goal difference, goals scored, then team name.
+pub fn sort_standings(order: &mut [usize], teams: &[String], standings: &[Stats]) {
+    order.sort_by(|&left, &right| {
+        let a = &standings[left];
+        let b = &standings[right];
+        b.points
+            .cmp(&a.points)
+            .then(b.goal_difference().cmp(&a.goal_difference()))
+            .then(b.goals_for.cmp(&a.goals_for))
+            .then(teams[left].cmp(&teams[right]))
+    });
+}
+
+/// Two divisions linked by promotion and relegation. Teams are numbered top division first,
+/// then lower division, and keep the ratings of the table they started in.
+pub struct Pyramid {
+    pub teams: Vec<String>,
+    /// This season's remaining fixtures in each division, on division-local team indices.
+    pub first_season: [SimulationInput; 2],
+    /// `[division][home * teams + away]` score matrices for the double round-robins that follow.
+    pub matrices: [Vec<ProbabilityDistribution>; 2],
+    pub relegation_spots: usize,
+    pub promotion_spots: usize,
+}
+
+impl Pyramid {
+    pub fn new(
+        divisions: [(League, &[ParsedFixture]); 2],
+        config: &ModelConfig,
+        relegation_spots: usize,
+        promotion_spots: usize,
+        n_years: usize,
+    ) -> Result<Self, String> {
+        let [(top, top_fixtures), (lower, lower_fixtures)] = divisions;
+        let teams: Vec<String> = top.teams.iter().chain(&lower.teams).cloned().collect();
+        let mut seen = std::collections::HashSet::new();
+        if let Some(team) = teams.iter().find(|team| !seen.insert(*team)) {
+            return Err(format!("Team {team} appears in both divisions"));
+        }
+        let (mut top_size, mut lower_size) = (top.teams.len(), lower.teams.len());
+        for _ in 0..n_years {
+            if top_size <= relegation_spots || lower_size <= promotion_spots {
+                return Err(format!(
+                    "Relegating {relegation_spots} and promoting {promotion_spots} would leave a \
+                     division without enough teams"
+                ));
+            }
+            top_size = top_size - relegation_spots + promotion_spots;
+            lower_size = lower_size - promotion_spots + relegation_spots;
+        }
+
+        // (home attack, home defense, away attack, away defense) from each team's own table.
+        let team_ratings: Vec<(f64, f64, f64, f64)> = [&top.ratings, &lower.ratings]
+            .iter()
+            .flat_map(|ratings| {
+                (0..ratings.home_attack.len()).map(|idx| {
+                    (
+                        ratings.home_attack[idx],
+                        ratings.home_defense[idx],
+                        ratings.away_attack[idx],
+                        ratings.away_defense[idx],
+                    )
+                })
+            })
+            .collect();
+        let matrices = [&top.ratings, &lower.ratings].map(|division| {
+            let mut matrices = Vec::with_capacity(teams.len() * teams.len());
+            for home in &team_ratings {
+                for away in &team_ratings {
+                    let (lambda_h, lambda_a) = calculate_lambdas(
+                        division.avg_league_goals,
+                        (home.0, home.1),
+                        (away.2, away.3),
+                        division.home_advantage,
+                    );
+                    matrices.push(config.distribution(lambda_h, lambda_a));
+                }
+            }
+            matrices
+        });
+        Ok(Pyramid {
+            teams,
+            first_season: [
+                top.simulation_input(top_fixtures, config),
+                lower.simulation_input(lower_fixtures, config),
+            ],
+            matrices,
+            relegation_spots,
+            promotion_spots,
+        })
+    }
+
+    /// Final order (as pyramid team indices) of a fresh double round-robin among `members`.
+    fn play_round_robin<R: Rng>(
+        &self,
+        division: usize,
+        members: &[usize],
+        rng: &mut R,
+    ) -> Vec<usize> {
+        let num_teams = self.teams.len();
+        let mut standings = vec![Stats::default(); members.len()];
+        for (home_slot, &home) in members.iter().enumerate() {
+            for (away_slot, &away) in members.iter().enumerate() {
+                if home_slot != away_slot {
+                    let distribution = &self.matrices[division][home * num_teams + away];
+                    let (gh, ga) = DixonColes::simulate_from_distribution(rng, distribution);
+                    standings[home_slot].record_result(gh, ga);
+                    standings[away_slot].record_result(ga, gh);
+                }
+            }
+        }
+        let names: Vec<String> = members
+            .iter()
+            .map(|&team| self.teams[team].clone())
+            .collect();
+        let mut order: Vec<usize> = (0..members.len()).collect();
+        sort_standings(&mut order, &names, &standings);
+        order.into_iter().map(|slot| members[slot]).collect()
+    }
+
+    /// Seasons each team spends in the top division over `n_years`, starting with this one.
+    pub fn top_flight_seasons<R: Rng>(&self, n_years: usize, rng: &mut R) -> Vec<usize> {
+        let top_size = self.first_season[0].teams.len();
+        let mut seasons = vec![0; self.teams.len()];
+        let mut members: [Vec<usize>; 2] = Default::default();
+        for year in 0..n_years {
+            let [top, lower] = if year == 0 {
+                [0, 1].map(|division| {
+                    let offset = if division == 0 { 0 } else { top_size };
+                    simulate_single_season(&self.first_season[division], rng)
+                        .order
+                        .into_iter()
+                        .map(|idx| idx + offset)
+                        .collect::<Vec<usize>>()
+                })
+            } else {
+                [0, 1].map(|division| self.play_round_robin(division, &members[division], rng))
+            };
+            for &team in &top {
+                seasons[team] += 1;
+            }
+            let (stay_up, relegated) = top.split_at(top.len() - self.relegation_spots);
+            let (promoted, stay_down) = lower.split_at(self.promotion_spots);
+            members = [
+                stay_up.iter().chain(promoted).copied().collect(),
+                stay_down.iter().chain(relegated).copied().collect(),
+            ];
+        }
+        seasons
+    }
+}
+
+/// `[team][k]`: simulations in which the team spent `k` of the `n_years` seasons in the top division.
+pub fn simulate_pyramid(
+    pyramid: &Pyramid,
+    n_years: usize,
+    n_sims: usize,
+    base_seed: u64,
+) -> Vec<Vec<u64>> {
+    let num_teams = pyramid.teams.len();
+    (0..n_sims)
+        .into_par_iter()
+        .fold(
+            || vec![vec![0u64; n_years + 1]; num_teams],
+            |mut counts, sim_index| {
+                let mut rng = ChaCha8Rng::seed_from_u64(simulation_seed(base_seed, sim_index));
+                for (team, seasons) in pyramid
+                    .top_flight_seasons(n_years, &mut rng)
+                    .into_iter()
+                    .enumerate()
+                {
+                    counts[team][seasons] += 1;
+                }
+                counts
+            },
+        )
+        .reduce(
+            || vec![vec![0u64; n_years + 1]; num_teams],
+            |mut left, right| {
+                for (left, right) in left.iter_mut().zip(right) {
+                    for (left, right) in left.iter_mut().zip(right) {
+                        *left += right;
+                    }
+                }
+                left
+            },
+        )
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MatchResult {
+    pub home_idx: usize,
+    pub away_idx: usize,
+    pub home_goals: i64,
+    pub away_goals: i64,
+}
+
+/// One ranking criterion; later stages only separate teams still level on every earlier one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TiebreakerStage {
+    Points,
+    GoalDifference,
+    GoalsFor,
+    /// Points, then goal difference, then goals scored in the matches among the tied teams,
+    /// re-applied to any smaller group that is still level.
+    HeadToHead,
+    /// Points won in the matches among the tied teams.
+    HeadToHeadPoints,
+    /// Goal difference in the matches among the tied teams.
+    HeadToHeadGoalDifference,
+    /// Goals scored in the matches among the tied teams.
+    HeadToHeadGoalsFor,
+    /// Goals scored away from home in the matches among the tied teams.
+    HeadToHeadAwayGoals,
+    UefaCoefficient(HashMap<String, f64>),
+    RandomDraw,
+}
+
+impl TiebreakerStage {
+    /// Parses a stage name such as `"pts"`, `"gd"`, `"h2h"` or `"head_to_head_pts"`.
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "pts" | "points" => Ok(TiebreakerStage::Points),
+            "gd" | "goal_difference" => Ok(TiebreakerStage::GoalDifference),
+            "gf" | "goals_for" => Ok(TiebreakerStage::GoalsFor),
+            "h2h" | "head_to_head" => Ok(TiebreakerStage::HeadToHead),
+            "h2h_pts" | "head_to_head_pts" => Ok(TiebreakerStage::HeadToHeadPoints),
+            "h2h_gd" | "head_to_head_gd" => Ok(TiebreakerStage::HeadToHeadGoalDifference),
+            "h2h_gf" | "head_to_head_gf" => Ok(TiebreakerStage::HeadToHeadGoalsFor),
+            "h2h_away_goals" | "head_to_head_away_goals" => {
+                Ok(TiebreakerStage::HeadToHeadAwayGoals)
+            }
+            "random" | "random_draw" => Ok(TiebreakerStage::RandomDraw),
+            _ => Err(format!("Unknown tiebreaker stage '{name}'")),
+        }
+    }
+
+    /// Whether the stage ranks by the season's individual results rather than the table.
+    fn needs_results(&self) -> bool {
+        matches!(
+            self,
+            TiebreakerStage::HeadToHead
+                | TiebreakerStage::HeadToHeadPoints
+                | TiebreakerStage::HeadToHeadGoalDifference
+                | TiebreakerStage::HeadToHeadGoalsFor
+                | TiebreakerStage::HeadToHeadAwayGoals
+        )
+    }
+}
+
+pub(crate) struct TableView<'a> {
+    pub teams: &'a [String],
+    pub standings: &'a [Stats],
+    pub results: &'a [MatchResult],
+}
+
+/// Sort `group` by `key` (highest first) and cut it wherever the key changes.
+pub(crate) fn split_group<K: PartialOrd>(
+    group: &[usize],
+    key: impl Fn(usize) -> K,
+) -> Vec<Vec<usize>> {
+    let mut keyed: Vec<(usize, K)> = group.iter().map(|&idx| (idx, key(idx))).collect();
+    keyed.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+    let mut parts: Vec<Vec<usize>> = Vec::new();
+    for (position, (idx, _)) in keyed.iter().enumerate() {
+        let starts_new_part = position == 0
+            || keyed[position - 1].1.partial_cmp(&keyed[position].1) != Some(Ordering::Equal);
+        if starts_new_part {
+            parts.push(vec![*idx]);
+        } else if let Some(part) = parts.last_mut() {
+            part.push(*idx);
+        }
+    }
+    parts
+}
+
+/// Record of one tied team over the matches among the group only.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct HeadToHeadRecord {
+    pub stats: Stats,
+    pub away_goals: i64,
+}
+
+/// Mini-table of `group` (in group order) from the results between its members.
+pub(crate) fn head_to_head_table(
+    group: &[usize],
+    results: &[MatchResult],
+) -> Vec<HeadToHeadRecord> {
+    let mut mini_table = vec![HeadToHeadRecord::default(); group.len()];
+    let slot = |team: usize| group.iter().position(|&member| member == team);
+    for result in results {
+        if let (Some(home), Some(away)) = (slot(result.home_idx), slot(result.away_idx)) {
+            mini_table[home]
+                .stats
+                .record_result(result.home_goals, result.away_goals);
+            mini_table[away]
+                .stats
+                .record_result(result.away_goals, result.home_goals);
+            mini_table[away].away_goals += result.away_goals;
+        }
+    }
+    mini_table
+}
+
+/// Splits `group` once by a single head-to-head criterion, without re-applying it to subgroups.
+pub(crate) fn split_head_to_head_by<K: PartialOrd>(
+    group: &[usize],
+    results: &[MatchResult],
+    key: impl Fn(&HeadToHeadRecord) -> K,
+) -> Vec<Vec<usize>> {
+    let mini_table = head_to_head_table(group, results);
+    split_group(group, |team| {
+        let slot = group.iter().position(|&member| member == team);
+        key(&mini_table[slot.unwrap_or_default()])
+    })
+}
+
+pub(crate) fn split_head_to_head(group: &[usize], results: &[MatchResult]) -> Vec<Vec<usize>> {
+    let parts = split_head_to_head_by(group, results, |record| {
+        let stats = &record.stats;
+        (stats.points, stats.goal_difference(), stats.goals_for)
+    });
+    if parts.len() == 1 {
+        return parts;
+    }
+    parts
+        .into_iter()
+        .flat_map(|part| {
+            if part.len() > 1 {
+                split_head_to_head(&part, results)
+            } else {
+                vec![part]
+            }
+        })
+        .collect()
+}
+
+/// Rank `order` by chaining `stages`; teams level on every stage fall back to name order.
+pub(crate) fn apply_multistage_tiebreaker<R: Rng>(
+    order: &mut Vec<usize>,
+    table: &TableView,
+    stages: &[TiebreakerStage],
+    rng: &mut R,
+) {
+    let mut groups: Vec<Vec<usize>> = vec![order.clone()];
+    for stage in stages {
+        if groups.iter().all(|group| group.len() == 1) {
+            break;
+        }
+        groups = groups
+            .into_iter()
+            .flat_map(|group| {
+                if group.len() == 1 {
+                    return vec![group];
+                }
+                match stage {
+                    TiebreakerStage::Points => {
+                        split_group(&group, |idx| table.standings[idx].points)
+                    }
+                    TiebreakerStage::GoalDifference => {
+                        split_group(&group, |idx| table.standings[idx].goal_difference())
+                    }
+                    TiebreakerStage::GoalsFor => {
+                        split_group(&group, |idx| table.standings[idx].goals_for)
+                    }
+                    TiebreakerStage::HeadToHead => split_head_to_head(&group, table.results),
+                    TiebreakerStage::HeadToHeadPoints => {
+                        split_head_to_head_by(&group, table.results, |record| record.stats.points)
+                    }
+                    TiebreakerStage::HeadToHeadGoalDifference => {
+                        split_head_to_head_by(&group, table.results, |record| {
+                            record.stats.goal_difference()
+                        })
+                    }
+                    TiebreakerStage::HeadToHeadGoalsFor => {
+                        split_head_to_head_by(&group, table.results, |record| {
+                            record.stats.goals_for
+                        })
+                    }
+                    TiebreakerStage::HeadToHeadAwayGoals => {
+                        split_head_to_head_by(&group, table.results, |record| record.away_goals)
+                    }
+                    TiebreakerStage::UefaCoefficient(coefficients) => split_group(&group, |idx| {
+                        coefficients.get(&table.teams[idx]).copied().unwrap_or(0.0)
+                    }),
+                    TiebreakerStage::RandomDraw => {
+                        let draws: Vec<u64> = group.iter().map(|_| rng.gen()).collect();
+                        split_group(&group, |idx| {
+                            draws[group
+                                .iter()
+                                .position(|&member| member == idx)
+                                .unwrap_or_default()]
+                        })
+                    }
+                }
+            })
+            .collect();
+    }
+
+    order.clear();
+    for mut group in groups {
+        group.sort_by(|&left, &right| table.teams[left].cmp(&table.teams[right]));
+        order.extend(group);
+    }
+}
+
+/// Per-simulation seed: independent streams, yet the whole batch replays for a given base seed
+/// regardless of how Rayon splits the work.
+pub fn simulation_seed(base_seed: u64, sim_index: usize) -> u64 {
+    base_seed ^ (sim_index as u64).wrapping_mul(6364136223846793005)
+}
+
+/// Which optional aggregates `run_bulk_simulations` collects besides position counts.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BulkOptions {
+    pub top_k_tables: usize,
+    pub points_distribution: bool,
+    pub record: bool,
+    pub goals: bool,
+    pub clean_sheets: bool,
+    /// Pair seasons `2k` and `2k + 1` on one seed, the second drawing antithetic scores.
+    pub antithetic: bool,
+    /// Stop starting seasons once this passes; the tally covers the seasons already begun.
+    pub deadline: Option<Instant>,
+    /// Seasons per Rayon task; `None` splits the range evenly across the pool's threads.
+    pub chunk_size: Option<usize>,
+    /// Keep every season's finishing positions, not just their counts.
+    pub full_history: bool,
+}
+
+/// Seasons between wall-clock checks against `BulkOptions::deadline`.
+const DEADLINE_CHECK_EVERY: usize = 100;
+
+/// `[wins, draws, losses]` -> seasons ending with that many, for one team.
+pub type RecordHistograms = [HashMap<i64, u64>; 3];
+
+/// `[goals_for, goals_against]` -> seasons ending on that total, for one team.
+pub type GoalHistograms = [HashMap<i64, u64>; 2];
+
+/// Running totals over simulated seasons; each Rayon worker folds its own and they are merged.
+#[derive(Serialize, Deserialize)]
+pub struct BulkTally {
+    /// `[team][position]` finish counts.
+    pub position_counts: Vec<Vec<u64>>,
+    /// Most frequent final orders; pruned approximately once it outgrows `top_k_tables`.
+    pub tables: HashMap<Vec<usize>, u64>,
+    /// `[team]` final points -> seasons.
+    pub points: Option<Vec<HashMap<i64, u64>>>,
+    /// `[team]` final win/draw/loss counts -> seasons.
+    pub records: Option<Vec<RecordHistograms>>,
+    /// `[team]` final goals for/against -> seasons.
+    pub goals: Option<Vec<GoalHistograms>>,
+    /// `[team]` clean sheets kept in the simulated fixtures -> seasons.
+    pub clean_sheets: Option<Vec<HashMap<i64, u64>>>,
+    /// `[season][team]` zero-based finishing position, in season index order.
+    pub history: Option<Vec<Vec<usize>>>,
+}
+
+impl BulkTally {
+    pub fn new(num_teams: usize, options: &BulkOptions) -> Self {
+        BulkTally {
+            position_counts: vec![vec![0; num_teams]; num_teams],
+            tables: HashMap::new(),
+            points: options
+                .points_distribution
+                .then(|| vec![HashMap::new(); num_teams]),
+            records: options.record.then(|| vec![Default::default(); num_teams]),
+            goals: options.goals.then(|| vec![Default::default(); num_teams]),
+            clean_sheets: options
+                .clean_sheets
+                .then(|| vec![HashMap::new(); num_teams]),
+            history: options.full_history.then(Vec::new),
+        }
+    }
+
+    pub fn record(&mut self, season: SeasonResult, options: &BulkOptions) {
+        for (pos_idx, &team_idx) in season.order.iter().enumerate() {
+            self.position_counts[team_idx][pos_idx] += 1;
+        }
+        if let Some(history) = self.history.as_mut() {
+            let mut positions = vec![0; season.order.len()];
+            for (pos_idx, &team_idx) in season.order.iter().enumerate() {
+                positions[team_idx] = pos_idx;
+            }
+            history.push(positions);
+        }
+        if let Some(points) = self.points.as_mut() {
+            for (team_points, stats) in points.iter_mut().zip(&season.final_stats) {
+                *team_points.entry(stats.points).or_insert(0) += 1;
+            }
+        }
+        if let Some(clean_sheets) = self.clean_sheets.as_mut() {
+            let mut kept = vec![0; clean_sheets.len()];
+            for result in &season.results {
+                if result.away_goals == 0 {
+                    kept[result.home_idx] += 1;
+                }
+                if result.home_goals == 0 {
+                    kept[result.away_idx] += 1;
+                }
+            }
+            for (histogram, kept) in clean_sheets.iter_mut().zip(kept) {
+                *histogram.entry(kept).or_insert(0) += 1;
+            }
+        }
+        if let Some(goals) = self.goals.as_mut() {
+            for (team_goals, stats) in goals.iter_mut().zip(&season.final_stats) {
+                for (histogram, total) in team_goals
+                    .iter_mut()
+                    .zip([stats.goals_for, stats.goals_against])
+                {
+                    *histogram.entry(total).or_insert(0) += 1;
+                }
+            }
+        }
+        if let Some(records) = self.records.as_mut() {
+            for (record, stats) in records.iter_mut().zip(&season.final_stats) {
+                for (histogram, count) in
+                    record
+                        .iter_mut()
+                        .zip([stats.wins, stats.draws, stats.losses])
+                {
+                    *histogram.entry(count).or_insert(0) += 1;
+                }
+            }
+        }
+        *self.tables.entry(season.order).or_insert(0) += 1;
+        self.prune_tables(options.top_k_tables);
+    }
+
+    /// Seasons tallied so far; every season gives each team exactly one finishing position.
+    pub fn seasons(&self) -> u64 {
+        self.position_counts
+            .first()
+            .map_or(0, |counts| counts.iter().sum())
+    }
+
+    pub fn merge(mut self, other: BulkTally, options: &BulkOptions) -> Self {
+        for (left, right) in self.position_counts.iter_mut().zip(other.position_counts) {
+            for (left_count, right_count) in left.iter_mut().zip(right) {
+                *left_count += right_count;
+            }
+        }
+        if let (Some(left), Some(right)) = (self.points.as_mut(), other.points) {
+            for (left_points, right_points) in left.iter_mut().zip(right) {
+                for (points, count) in right_points {
+                    *left_points.entry(points).or_insert(0) += count;
+                }
+            }
+        }
+        if let (Some(left), Some(right)) = (self.clean_sheets.as_mut(), other.clean_sheets) {
+            for (left_counts, right_counts) in left.iter_mut().zip(right) {
+                for (kept, count) in right_counts {
+                    *left_counts.entry(kept).or_insert(0) += count;
+                }
+            }
+        }
+        if let (Some(left), Some(right)) = (self.goals.as_mut(), other.goals) {
+            for (left_goals, right_goals) in left.iter_mut().zip(right) {
+                for (left_histogram, right_histogram) in left_goals.iter_mut().zip(right_goals) {
+                    for (total, count) in right_histogram {
+                        *left_histogram.entry(total).or_insert(0) += count;
+                    }
+                }
+            }
+        }
+        if let (Some(left), Some(right)) = (self.records.as_mut(), other.records) {
+            for (left_record, right_record) in left.iter_mut().zip(right) {
+                for (left_histogram, right_histogram) in left_record.iter_mut().zip(right_record) {
+                    for (value, count) in right_histogram {
+                        *left_histogram.entry(value).or_insert(0) += count;
+                    }
+                }
+            }
+        }
+        // Rayon reduces neighbouring blocks left to right, so appending keeps season order.
+        if let (Some(left), Some(right)) = (self.history.as_mut(), other.history) {
+            left.extend(right);
+        }
+        for (table, count) in other.tables {
+            *self.tables.entry(table).or_insert(0) += count;
+        }
+        self.prune_tables(options.top_k_tables);
+        self
+    }
+
+    pub(crate) fn prune_tables(&mut self, top_k_tables: usize) {
+        if top_k_tables > 0 && self.tables.len() > top_k_tables * 4 {
+            let mut tables: Vec<(Vec<usize>, u64)> = self.tables.drain().collect();
+            tables.sort_by_key(|table| Reverse(table.1));
+            tables.truncate(top_k_tables * 2);
+            self.tables = tables.into_iter().collect();
+        }
+    }
+}
+
+pub fn run_bulk_simulations(
+    input: &SimulationInput,
+    n_sims: usize,
+    base_seed: u64,
+    options: &BulkOptions,
+) -> BulkTally {
+    SIMULATION_PROGRESS.store(0, AtomicOrdering::Relaxed);
+    run_bulk_range(input, 0..n_sims, base_seed, options)
+}
+
+/// Simulates seasons `sims.start..sims.end`; each index always draws the same season for a
+/// given base seed, so a run split into ranges tallies exactly like one uninterrupted run.
+/// Indices are handed to Rayon in `BulkOptions::chunk_size` blocks, each tallied serially; a range
+/// shorter than one block runs on the calling thread.
+pub fn run_bulk_range(
+    input: &SimulationInput,
+    sims: std::ops::Range<usize>,
+    base_seed: u64,
+    options: &BulkOptions,
+) -> BulkTally {
+    let chunk_size = options
+        .chunk_size
+        .unwrap_or_else(|| (sims.len() / rayon::current_num_threads()).max(1));
+    let expired = AtomicBool::new(false);
+    if sims.len() < chunk_size {
+        return tally_chunk(input, sims, base_seed, options, &expired);
+    }
+    // Blocks are computed from the range rather than collected into an index array, so a huge
+    // `n_sims` under a short deadline does not spend its budget allocating.
+    (0..sims.len().div_ceil(chunk_size))
+        .into_par_iter()
+        .map(|chunk| {
+            let start = sims.start + chunk * chunk_size;
+            let block = start..(start + chunk_size).min(sims.end);
+            tally_chunk(input, block, base_seed, options, &expired)
+        })
+        .reduce(
+            || BulkTally::new(input.teams.len(), options),
+            |left, right| left.merge(right, options),
+        )
+}
+
+/// Serial tally of one block of season indices for `run_bulk_range`. On the block's first
+/// index and every `DEADLINE_CHECK_EVERY` after it the clock is compared with
+/// `options.deadline`; once it has passed, `expired` stops this and every other block.
+fn tally_chunk(
+    input: &SimulationInput,
+    sims: impl Iterator<Item = usize>,
+    base_seed: u64,
+    options: &BulkOptions,
+    expired: &AtomicBool,
+) -> BulkTally {
+    let mut tally = BulkTally::new(input.teams.len(), options);
+    for (done, sim_index) in sims.enumerate() {
+        if let Some(deadline) = options.deadline {
+            if done.is_multiple_of(DEADLINE_CHECK_EVERY) && Instant::now() >= deadline {
+                expired.store(true, AtomicOrdering::Relaxed);
+            }
+            if expired.load(AtomicOrdering::Relaxed) {
+                break;
+            }
+        }
+        let (seed_index, antithetic) = if options.antithetic {
+            (sim_index / 2, sim_index % 2 == 1)
+        } else {
+            (sim_index, false)
+        };
+        let mut rng = ChaCha8Rng::seed_from_u64(simulation_seed(base_seed, seed_index));
+        // Clean sheets are counted from the simulated scores.
+        let season = simulate_season_antithetic(input, &mut rng, options.clean_sheets, antithetic);
+        tally.record(season, options);
+        SIMULATION_PROGRESS.fetch_add(1, AtomicOrdering::Relaxed);
+    }
+    tally
+}
+
+/// Folds simulated seasons into per-worker tallies and merges them, counting progress.
+fn tally_seasons(
+    seasons: impl ParallelIterator<Item = SeasonResult>,
+    num_teams: usize,
+    options: &BulkOptions,
+) -> BulkTally {
+    seasons
+        .fold(
+            || BulkTally::new(num_teams, options),
+            |mut tally, season| {
+                tally.record(season, options);
+                SIMULATION_PROGRESS.fetch_add(1, AtomicOrdering::Relaxed);
+                tally
+            },
+        )
+        .reduce(
+            || BulkTally::new(num_teams, options),
+            |left, right| left.merge(right, options),
+        )
+}
+
+/// Quasi-Monte Carlo version of `run_bulk_simulations`: season `i` takes its fixture scores from
+/// point `i` of a Sobol sequence shifted by `base_seed`, one coordinate per fixture. Points are
+/// computed independently, so Rayon may split the index range however it likes.
+pub fn run_bulk_qmc(
+    input: &SimulationInput,
+    n_sims: usize,
+    base_seed: u64,
+    options: &BulkOptions,
+) -> Result<BulkTally, String> {
+    let n_points = u32::try_from(n_sims)
+        .map_err(|_| format!("n_sims must be at most {} for Sobol sampling", u32::MAX))?;
+    SIMULATION_PROGRESS.store(0, AtomicOrdering::Relaxed);
+    let sobol = crate::qmc::Sobol::shifted(input.fixtures.len(), base_seed);
+    let seasons = (0..n_points).into_par_iter().map(|sim_index| {
+        let point = sobol.point(sim_index);
+        let mut rng = ChaCha8Rng::seed_from_u64(simulation_seed(base_seed, sim_index as usize));
+        simulate_season_scored(
+            input,
+            &mut rng,
+            options.clean_sheets,
+            |fixture_idx, distribution, _| DixonColes::score_at(distribution, point[fixture_idx]),
+        )
+    });
+    Ok(tally_seasons(seasons, input.teams.len(), options))
+}
+
+/// Latin hypercube layout of `n_sims` seasons: for every fixture, each season gets its own
+/// stratum `[k / n_sims, (k + 1) / n_sims)` of the uniform draw, in a shuffled order.
+pub struct Stratification {
+    n_sims: usize,
+    /// `[fixture][season]` stratum index.
+    strata: Vec<Vec<u32>>,
+}
+
+impl Stratification {
+    pub fn new(n_fixtures: usize, n_sims: usize, seed: u64) -> Result<Self, String> {
+        let n_strata = u32::try_from(n_sims).map_err(|_| {
+            format!(
+                "n_sims must be at most {} for stratified sampling",
+                u32::MAX
+            )
+        })?;
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let strata = (0..n_fixtures)
+            .map(|_| {
+                let mut strata: Vec<u32> = (0..n_strata).collect();
+                strata.shuffle(&mut rng);
+                strata
+            })
+            .collect();
+        Ok(Stratification { n_sims, strata })
+    }
+
+    /// Uniform draw for `fixture` in season `sim_index`, placed within its stratum by `rng`.
+    pub fn uniform<R: Rng>(&self, sim_index: usize, fixture: usize, rng: &mut R) -> f64 {
+        let jitter: f64 = rng.gen();
+        (self.strata[fixture][sim_index] as f64 + jitter) / self.n_sims as f64
+    }
+}
+
+/// `run_bulk_simulations` with every fixture's draws stratified across the `n_sims` seasons, so
+/// each score turns up in close to its exact share of them.
+pub fn run_bulk_stratified(
+    input: &SimulationInput,
+    n_sims: usize,
+    base_seed: u64,
+    options: &BulkOptions,
+) -> Result<BulkTally, String> {
+    SIMULATION_PROGRESS.store(0, AtomicOrdering::Relaxed);
+    let stratification = Stratification::new(input.fixtures.len(), n_sims, base_seed)?;
+    let seasons = (0..n_sims).into_par_iter().map(|sim_index| {
+        let mut rng = ChaCha8Rng::seed_from_u64(simulation_seed(base_seed, sim_index));
+        simulate_season_scored(
+            input,
+            &mut rng,
+            options.clean_sheets,
+            |fixture_idx, distribution, rng| {
+                let u = stratification.uniform(sim_index, fixture_idx, rng);
+                DixonColes::score_at(distribution, u)
+            },
+        )
+    });
+    Ok(tally_seasons(seasons, input.teams.len(), options))
+}
+
+/// Simulates seasons `0..n_sims` in the background, sending each one as it finishes.
+/// Seasons arrive in completion order; at most `STREAM_BUFFER_PER_THREAD` per worker wait
+/// unread before the workers block, and simulation stops once the receiver is dropped. The
+/// workers get a pool of their own, so a blocked stream never stalls other simulations.
+pub fn stream_seasons(
+    input: Arc<SimulationInput>,
+    n_sims: usize,
+    base_seed: u64,
+) -> Receiver<SeasonResult> {
+    let threads = rayon::current_num_threads();
+    let (sender, receiver) = mpsc::sync_channel(STREAM_BUFFER_PER_THREAD * threads);
+    std::thread::spawn(move || {
+        let stream = move || {
+            let _ = (0..n_sims)
+                .into_par_iter()
+                .try_for_each_with(sender, |sender, sim_index| {
+                    let mut rng = ChaCha8Rng::seed_from_u64(simulation_seed(base_seed, sim_index));
+                    sender.send(simulate_single_season(&input, &mut rng))
+                });
+        };
+        match rayon::ThreadPoolBuilder::new().num_threads(threads).build() {
+            Ok(pool) => pool.install(stream),
+            Err(_) => stream(),
+        }
+    });
+    receiver
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "ffi")]
+
+mod helpers;
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+use helpers::{fixtures_json, four_team_league, table_json};
+use league_outcome_simulator_rust::ffi::{
+    bulk_json, league_sim_bulk, LEAGUE_SIM_INVALID_INPUT, LEAGUE_SIM_NULL_POINTER, LEAGUE_SIM_OK,
+};
+
+extern "C" {
+    fn league_sim_test_bulk(
+        base_table_json: *const c_char,
+        fixtures_json: *const c_char,
+        n_sims: usize,
+        seed: u64,
+        buffer: *mut c_char,
+        capacity: usize,
+    ) -> i32;
+}
+
+fn league_json() -> (String, String) {
+    let (base_table, fixtures) = four_team_league();
+    (table_json(&base_table), fixtures_json(&fixtures))
+}
+
+fn call_from_c(base_table: &str, fixtures: &str, n_sims: usize, seed: u64) -> (i32, String) {
+    let base_table = CString::new(base_table).unwrap();
+    let fixtures = CString::new(fixtures).unwrap();
+    let mut buffer = vec![0 as c_char; 4096];
+    let status = unsafe {
+        league_sim_test_bulk(
+            base_table.as_ptr(),
+            fixtures.as_ptr(),
+            n_sims,
+            seed,
+            buffer.as_mut_ptr(),
+            buffer.len(),
+        )
+    };
+    let json = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+    (status, json.to_str().unwrap().to_string())
+}
+
+#[test]
+fn c_caller_matches_the_rust_native_path() {
+    let (base_table, fixtures) = league_json();
+    let (status, json) = call_from_c(&base_table, &fixtures, 500, 296);
+    assert_eq!(status, LEAGUE_SIM_OK);
+    assert_eq!(json, bulk_json(&base_table, &fixtures, 500, 296).unwrap());
+    assert!(json.starts_with("{\"Alpha FC\": ["));
+}
+
+#[test]
+fn unknown_fixture_team_is_reported_to_c() {
+    let (base_table, _) = league_json();
+    let fixtures = fixtures_json(&[("Alpha FC", "Echo Rovers")]);
+    let (status, _) = call_from_c(&base_table, &fixtures, 10, 1);
+    assert_eq!(status, LEAGUE_SIM_INVALID_INPUT);
+}
+
+#[test]
+fn null_inputs_are_rejected() {
+    let mut out = std::ptr::null_mut();
+    let mut len = 0;
+    let status =
+        unsafe { league_sim_bulk(std::ptr::null(), std::ptr::null(), 1, 1, &mut out, &mut len) };
+    assert_eq!(status, LEAGUE_SIM_NULL_POINTER);
+    assert!(out.is_null());
+}
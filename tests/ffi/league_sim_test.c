@@ -0,0 +1,27 @@
+/* C caller for tests/ffi.rs: drives league_sim_bulk exactly as a C program would. */
+#include <string.h>
+
+#include "league_sim.h"
+
+/* Copies the JSON result into `buffer` and frees it; returns the league_sim_bulk status, or
+ * -100 when `buffer` is too small or the reported length is wrong. */
+int32_t league_sim_test_bulk(const char *base_table_json,
+                             const char *fixtures_json,
+                             size_t n_sims,
+                             uint64_t seed,
+                             char *buffer,
+                             size_t capacity) {
+    char *json = NULL;
+    size_t len = 0;
+    int32_t status = league_sim_bulk(base_table_json, fixtures_json, n_sims, seed, &json, &len);
+    if (status != LEAGUE_SIM_OK) {
+        return json == NULL ? status : -100;
+    }
+    if (strlen(json) != len || len + 1 > capacity) {
+        league_sim_free(json);
+        return -100;
+    }
+    memcpy(buffer, json, len + 1);
+    league_sim_free(json);
+    return status;
+}
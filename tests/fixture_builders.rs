@@ -0,0 +1,55 @@
+mod helpers;
+
+use std::collections::{HashMap, HashSet};
+
+use helpers::{build_base_table, build_fixtures, standard_20_team_season, TABLE_HEADER};
+use league_outcome_simulator_rust::sim::{fixture_problems, TeamIndex};
+
+#[test]
+fn base_table_rows_are_consistent_records() {
+    let table = build_base_table(&[("Alpha", 3, 5, 2, 7), ("Bravo", 3, 1, 4, 1)]);
+    assert_eq!(table[0], TABLE_HEADER.map(str::to_string));
+    assert_eq!(table[1], ["Alpha", "3", "2", "1", "0", "5", "2", "7"]);
+    assert_eq!(table[2], ["Bravo", "3", "0", "1", "2", "1", "4", "1"]);
+}
+
+#[test]
+fn fixtures_use_snapshot_shape() {
+    let fixtures = build_fixtures(&[("Alpha", "Bravo")]);
+    assert_eq!(fixtures[0]["h"]["title"], "Alpha");
+    assert_eq!(fixtures[0]["a"]["title"], "Bravo");
+}
+
+#[test]
+fn standard_season_is_a_complete_double_round_robin() {
+    let (base_table, fixtures) = standard_20_team_season();
+    assert_eq!(base_table.len(), 21);
+    assert_eq!(fixtures.len(), 380);
+
+    let teams: HashSet<&str> = base_table[1..].iter().map(|row| row[0].as_str()).collect();
+    let mut seen = HashSet::new();
+    let mut appearances: HashMap<&str, (usize, usize)> = HashMap::new();
+    for (home, away) in &fixtures {
+        assert_ne!(home, away);
+        assert!(teams.contains(home.as_str()) && teams.contains(away.as_str()));
+        assert!(
+            seen.insert((home, away)),
+            "duplicate fixture {home} vs {away}"
+        );
+        appearances.entry(home).or_default().0 += 1;
+        appearances.entry(away).or_default().1 += 1;
+    }
+    assert!(appearances.values().all(|&counts| counts == (19, 19)));
+}
+
+#[test]
+fn standard_season_passes_fixture_validation() {
+    let (base_table, fixtures) = standard_20_team_season();
+    let team_to_idx: TeamIndex = base_table[1..]
+        .iter()
+        .enumerate()
+        .map(|(idx, row)| (row[0].clone(), idx))
+        .collect();
+    let indexed: Vec<(usize, (String, String))> = fixtures.into_iter().enumerate().collect();
+    assert_eq!(fixture_problems(&indexed, &team_to_idx), Vec::new());
+}
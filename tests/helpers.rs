@@ -0,0 +1,128 @@
+//! Builders for the table and fixture structures the simulator consumes.
+//!
+//! Integration tests include this file with `mod helpers;` so every suite
+//! shares the same well-formed inputs instead of hand-crafting them.
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+pub const TABLE_HEADER: [&str; 8] = ["Team", "M", "W", "D", "L", "G", "GA", "PTS"];
+
+/// Build a standings table (header row first) from `(team, matches, goals_for,
+/// goals_against, points)` tuples. The W/D/L split is the wins-first record
+/// that is consistent with the given points and matches.
+pub fn build_base_table(teams: &[(&str, i64, i64, i64, i64)]) -> Vec<Vec<String>> {
+    let mut table = vec![TABLE_HEADER
+        .iter()
+        .map(|column| column.to_string())
+        .collect()];
+    for &(team, matches, goals_for, goals_against, points) in teams {
+        let wins = (points / 3).min(matches);
+        let draws = (points - 3 * wins).min(matches - wins);
+        let losses = matches - wins - draws;
+        table.push(vec![
+            team.to_string(),
+            matches.to_string(),
+            wins.to_string(),
+            draws.to_string(),
+            losses.to_string(),
+            goals_for.to_string(),
+            goals_against.to_string(),
+            points.to_string(),
+        ]);
+    }
+    table
+}
+
+/// Build fixtures in the `{"h": {"title": ..}, "a": {"title": ..}}` shape
+/// used by SofaScore snapshots.
+pub fn build_fixtures(pairs: &[(&str, &str)]) -> Vec<HashMap<String, HashMap<String, String>>> {
+    pairs
+        .iter()
+        .map(|&(home, away)| {
+            let side = |team: &str| HashMap::from([("title".to_string(), team.to_string())]);
+            HashMap::from([("h".to_string(), side(home)), ("a".to_string(), side(away))])
+        })
+        .collect()
+}
+
+/// A Premier League sized case: 20 teams with empty records and the full
+/// 380-fixture double round-robin still to play.
+pub fn standard_20_team_season() -> (Vec<Vec<String>>, Vec<(String, String)>) {
+    let names: Vec<String> = (1..=20).map(|index| format!("Team {index:02}")).collect();
+    let rows: Vec<(&str, i64, i64, i64, i64)> = names
+        .iter()
+        .map(|name| (name.as_str(), 0, 0, 0, 0))
+        .collect();
+    let base_table = build_base_table(&rows);
+
+    let mut fixtures = Vec::with_capacity(names.len() * (names.len() - 1));
+    for home in &names {
+        for away in &names {
+            if home != away {
+                fixtures.push((home.clone(), away.clone()));
+            }
+        }
+    }
+    (base_table, fixtures)
+}
+
+/// `table` as the JSON array of rows the C ABI and `league-sim` read: team names quoted,
+/// record columns as numbers.
+pub fn table_json(table: &[Vec<String>]) -> String {
+    let rows: Vec<String> = table
+        .iter()
+        .enumerate()
+        .map(|(idx, row)| {
+            let cells: Vec<String> = row
+                .iter()
+                .enumerate()
+                .map(|(column, cell)| {
+                    if idx == 0 || column == 0 {
+                        format!("{cell:?}")
+                    } else {
+                        cell.clone()
+                    }
+                })
+                .collect();
+            format!("[{}]", cells.join(", "))
+        })
+        .collect();
+    format!("[{}]", rows.join(", "))
+}
+
+/// `pairs` as a JSON array of SofaScore-shaped fixtures, like `build_fixtures`.
+pub fn fixtures_json(pairs: &[(&str, &str)]) -> String {
+    let fixtures: Vec<String> = pairs
+        .iter()
+        .map(|(home, away)| {
+            format!(r#"{{"h": {{"title": {home:?}}}, "a": {{"title": {away:?}}}}}"#)
+        })
+        .collect();
+    format!("[{}]", fixtures.join(", "))
+}
+
+/// A snapshot object with `base_table` and `fixtures`, as `league-sim` reads it.
+pub fn snapshot_json(table: &[Vec<String>], pairs: &[(&str, &str)]) -> String {
+    format!(
+        r#"{{"base_table": {}, "fixtures": {}}}"#,
+        table_json(table),
+        fixtures_json(pairs)
+    )
+}
+
+/// A four-team league two matches in, with three fixtures left.
+pub fn four_team_league() -> (Vec<Vec<String>>, Vec<(&'static str, &'static str)>) {
+    let base_table = build_base_table(&[
+        ("Alpha FC", 2, 4, 1, 6),
+        ("Bravo United", 2, 2, 2, 3),
+        ("Charlie Town", 2, 1, 2, 1),
+        ("Delta City", 2, 1, 3, 1),
+    ]);
+    let fixtures = vec![
+        ("Alpha FC", "Delta City"),
+        ("Bravo United", "Charlie Town"),
+        ("Charlie Town", "Alpha FC"),
+    ];
+    (base_table, fixtures)
+}
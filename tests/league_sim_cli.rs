@@ -0,0 +1,116 @@
+mod helpers;
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+use helpers::{four_team_league, snapshot_json};
+
+fn snapshot() -> String {
+    let (base_table, fixtures) = four_team_league();
+    snapshot_json(&base_table, &fixtures)
+}
+
+/// `snapshot()` written to a file named `name` for the tests that pass a path.
+fn snapshot_file(name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    std::fs::write(&path, snapshot()).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+fn league_sim(args: &[&str], stdin: Option<&str>) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_league-sim"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("league-sim starts");
+    let mut pipe = child.stdin.take().unwrap();
+    pipe.write_all(stdin.unwrap_or_default().as_bytes())
+        .unwrap();
+    drop(pipe);
+    child.wait_with_output().unwrap()
+}
+
+fn shares(output: &Output) -> Vec<(String, Vec<f64>)> {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let teams: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_slice(&output.stdout).expect("output is a JSON object");
+    teams
+        .into_iter()
+        .map(|(team, row)| {
+            let Some(cells) = row.as_array() else {
+                panic!("{team} has no probability list");
+            };
+            let row = cells
+                .iter()
+                .map(|cell| {
+                    cell.as_f64()
+                        .unwrap_or_else(|| panic!("{team} has non-numeric share {cell}"))
+                })
+                .collect();
+            (team, row)
+        })
+        .collect()
+}
+
+#[test]
+fn json_output_is_a_probability_table_per_team() {
+    let snapshot = snapshot_file("json_output.json");
+    let output = league_sim(&[&snapshot, "--n-sims", "400", "--seed", "297"], None);
+    let table = shares(&output);
+    assert_eq!(table.len(), 4);
+    for (team, row) in &table {
+        assert_eq!(row.len(), 4);
+        assert!(
+            (row.iter().sum::<f64>() - 1.0).abs() < 1e-9,
+            "{team} shares sum to 1"
+        );
+    }
+}
+
+#[test]
+fn stdin_and_file_input_agree_for_a_fixed_seed() {
+    let path = snapshot_file("stdin_and_file.json");
+    let from_file = league_sim(&[&path, "--seed=11", "--n-sims=200"], None);
+    let from_stdin = league_sim(&["--seed", "11", "--n-sims", "200"], Some(&snapshot()));
+    assert_eq!(shares(&from_file), shares(&from_stdin));
+}
+
+#[test]
+fn progress_and_csv_output() {
+    let output = league_sim(
+        &["--n-sims", "100", "--format", "csv", "--progress"],
+        Some(&snapshot()),
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().next(), Some("team,1,2,3,4"));
+    assert_eq!(stdout.lines().count(), 5);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr.lines().count(), 10);
+    assert_eq!(stderr.lines().last(), Some("progress: 100% (100/100)"));
+}
+
+#[test]
+fn bad_flags_exit_with_usage_error() {
+    let output = league_sim(&["--rho", "2.0"], Some(&snapshot()));
+    assert_eq!(output.status.code(), Some(1));
+    let output = league_sim(&["--n-sims"], None);
+    assert_eq!(output.status.code(), Some(2));
+    let output = league_sim(&["--n-sims", "0"], Some(&snapshot()));
+    assert_eq!(output.status.code(), Some(2));
+    let output = league_sim(&["--format", "xml"], Some(&snapshot()));
+    assert_eq!(output.status.code(), Some(2));
+
+    let help = league_sim(&["-h"], None);
+    assert!(help.status.success());
+    assert!(String::from_utf8(help.stdout)
+        .unwrap()
+        .contains("Usage: league-sim [OPTIONS] [SNAPSHOT]"));
+}
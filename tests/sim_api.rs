@@ -0,0 +1,61 @@
+mod helpers;
+
+use league_outcome_simulator_rust::sim::{
+    run_bulk_simulations, season_rng, simulate_single_season, BulkOptions, League, ModelConfig,
+    ParsedFixture, Stats,
+};
+
+use helpers::standard_20_team_season;
+
+fn standard_league() -> (League, Vec<ParsedFixture>) {
+    let (base_table, pairs) = standard_20_team_season();
+    let table: Vec<(String, Stats)> = base_table[1..]
+        .iter()
+        .map(|row| (row[0].clone(), Stats::default()))
+        .collect();
+    let league = League::new(table, &[], &[], &ModelConfig::default()).unwrap();
+    let fixtures = pairs
+        .iter()
+        .map(|(home, away)| league.fixture(home, away).unwrap())
+        .collect();
+    (league, fixtures)
+}
+
+#[test]
+fn native_season_plays_every_fixture() {
+    let (league, fixtures) = standard_league();
+    let input = league.simulation_input(&fixtures, &ModelConfig::default());
+    let season = simulate_single_season(&input, &mut season_rng(Some(295)));
+
+    let mut order = season.order.clone();
+    order.sort_unstable();
+    assert_eq!(order, (0..20).collect::<Vec<_>>());
+    assert!(season.final_stats.iter().all(|stats| stats.matches == 38));
+    let wins: i64 = season.final_stats.iter().map(|stats| stats.wins).sum();
+    let losses: i64 = season.final_stats.iter().map(|stats| stats.losses).sum();
+    assert_eq!(wins, losses);
+}
+
+#[test]
+fn native_bulk_run_is_reproducible_from_its_seed() {
+    let (league, fixtures) = standard_league();
+    let input = league.simulation_input(&fixtures, &ModelConfig::default());
+    let options = BulkOptions::default();
+    let first = run_bulk_simulations(&input, 200, 7, &options);
+    let second = run_bulk_simulations(&input, 200, 7, &options);
+
+    assert_eq!(first.position_counts, second.position_counts);
+    for counts in &first.position_counts {
+        assert_eq!(counts.iter().sum::<u64>(), 200);
+    }
+}
+
+#[test]
+fn unknown_venue_team_is_rejected() {
+    let table = vec![("Alpha".to_string(), Stats::default())];
+    let home = [("Bravo".to_string(), Stats::default())];
+    let err = League::new(table, &home, &[], &ModelConfig::default())
+        .err()
+        .unwrap();
+    assert_eq!(err, "Team Bravo found in home table but not base table");
+}
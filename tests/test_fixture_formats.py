@@ -0,0 +1,52 @@
+from __future__ import annotations
+
+import json
+from pathlib import Path
+
+import pytest
+
+rust = pytest.importorskip("league_outcome_simulator_rust")
+
+SNAPSHOT = Path(__file__).parent / "fixtures" / "sample_snapshot.json"
+
+
+def _snapshot():
+    snapshot = json.loads(SNAPSHOT.read_text())
+    return (
+        snapshot["base_table"],
+        snapshot["fixtures"],
+        snapshot["home_table"],
+        snapshot["away_table"],
+    )
+
+
+def test_team_name_pairs_simulate_like_sofascore_dicts():
+    base_table, fixtures, home_table, away_table = _snapshot()
+    pairs = [(fixture["h"]["title"], fixture["a"]["title"]) for fixture in fixtures]
+    lists = [list(pair) for pair in pairs]
+
+    expected = rust.simulate_bulk(base_table, fixtures, home_table, away_table, 500, 339, 3)
+    for flat in (pairs, lists):
+        assert rust.simulate_bulk(base_table, flat, home_table, away_table, 500, 339, 3) == expected
+    assert rust.simulate_season(
+        base_table, pairs, home_table, away_table, 339
+    ) == rust.simulate_season(base_table, fixtures, home_table, away_table, 339)
+
+
+def test_team_name_pairs_need_exactly_two_names():
+    base_table, fixtures, home_table, away_table = _snapshot()
+    home = fixtures[0]["h"]["title"]
+    with pytest.raises(ValueError, match="home_team, away_team"):
+        rust.simulate_bulk(base_table, [(home,)], home_table, away_table, 5, 1, 0)
+
+
+def test_fixing_home_away_balance_keeps_the_fixture_format():
+    base_table, _, _, _ = _snapshot()
+    teams = sorted(row[0] for row in base_table[1:])
+    one_way = [(home, away) for i, home in enumerate(teams) for away in teams[i + 1 :]]
+
+    assert rust.check_home_away_balance(one_way, base_table)
+    for flat in (one_way, [{"h": {"title": h}, "a": {"title": a}} for h, a in one_way]):
+        fixed = rust.fix_home_away_balance(flat, base_table, seed=341)
+        assert type(fixed[0]) is type(flat[0])
+        assert rust.check_home_away_balance(fixed, base_table) == {}